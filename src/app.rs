@@ -8,7 +8,7 @@ use std::{
 use color_eyre::Result;
 use tokio::sync::{
     mpsc::{Receiver, Sender},
-    oneshot::Receiver as OSReceiver,
+    oneshot::{Receiver as OSReceiver, error::TryRecvError},
 };
 use tracing::{error, warn};
 use tui::{
@@ -20,17 +20,26 @@ use crate::{
     config::SharedCoreConfig,
     emotes::{
         ApplyCommand, DecodedEmote, DownloadedEmotes, Emotes, SharedEmotes, display_emote,
-        query_emotes,
+        query_channel_badges, query_emotes,
     },
     events::{Event, Events, InternalEvent, TwitchAction, TwitchEvent, TwitchNotification},
     handlers::{
+        automod::{AutoModQueue, SharedAutoModQueue},
+        channels::{Channels, SharedChannels},
+        chat_logs::ChatLogger,
+        chatters::SeenChatters,
+        config_watcher::ConfigWatcher,
         data::{KNOWN_CHATTERS, MessageData},
-        filters::Filters,
+        filters::{Filters, SharedFilters},
+        highlights::{Highlights, SharedHighlights},
+        notifier::{Notifier, ring_bell},
+        polls::{Polls, SharedPolls},
         state::State,
         storage::{SharedStorage, Storage},
+        whispers::{SharedWhispers, Whispers},
     },
     twitch::oauth::TwitchOauth,
-    ui::components::{Component, Components},
+    ui::components::{Component, Components, utils::popup_area},
     utils::sanitization::clean_channel_name,
 };
 
@@ -50,18 +59,48 @@ pub struct App {
     pub events: Events,
     pub twitch_tx: Sender<TwitchAction>,
 
-    pub messages: SharedMessages,
+    /// Every joined channel along with its messages
+    pub channels: SharedChannels,
+
+    /// Whisper conversations, which aren't tied to any channel
+    pub whispers: SharedWhispers,
+
+    /// Polls and predictions of the joined channels
+    pub polls: SharedPolls,
+
+    /// Messages held by `AutoMod` in the channels that the user moderates
+    pub automod_queue: SharedAutoModQueue,
 
     /// Data loaded in from a JSON file.
     pub storage: SharedStorage,
 
+    /// Writes messages to disk, if enabled
+    chat_logger: Option<ChatLogger>,
+
+    /// Alerts the user about messages, if enabled
+    notifier: Option<Notifier>,
+
+    /// Messages that are hidden from the chat
+    filters: SharedFilters,
+
+    /// Rules that make messages stand out
+    highlights: SharedHighlights,
+
+    /// Reloads the config when it changes on disk, if the files can be watched
+    _config_watcher: Option<ConfigWatcher>,
+
+    /// The chatters seen in each channel since twt started, for the `first_seen` filter condition
+    chatters: SeenChatters,
+
     /// States
     state: State,
     previous_state: Option<State>,
 
     /// Emote encoding pipeline
     pub emotes: SharedEmotes,
-    pub emotes_rx: OSReceiver<(DownloadedEmotes, DownloadedEmotes)>,
+    pub emotes_rx: OSReceiver<(DownloadedEmotes, DownloadedEmotes, DownloadedEmotes)>,
+    /// Badges of the channels opened next to the current one that are still being downloaded
+    pub badges_rx: Vec<OSReceiver<DownloadedEmotes>>,
     pub decoded_emotes_rx: Option<Receiver<Result<DecodedEmote, String>>>,
 
     pub running_stream: Option<Child>,
@@ -87,36 +126,169 @@ impl App {
         let first_state = config.terminal.first_state.clone();
 
         let storage = shared!(Storage::new(&config));
-        let filters = shared!(Filters::new(&config));
-        let messages = shared!(VecDeque::with_capacity(maximum_messages));
+        let (filters, filters_errors) = Filters::load(&config);
+        let filters = shared!(filters);
+        let highlights = shared!(Highlights::new(&config.highlights));
+        let channels = shared!(Channels::new(
+            config.twitch.channel.clone(),
+            maximum_messages
+        ));
+        let whispers = shared!(Whispers::new(maximum_messages));
+        let polls = shared!(Polls::default());
+        let automod_queue = shared!(AutoModQueue::default());
 
         let components = Components::builder()
             .config(&config)
             .twitch_oauth(twitch_oauth.clone())
-            .event_tx(event_tx)
+            .event_tx(event_tx.clone())
             .storage(storage.clone())
-            .filters(filters)
-            .messages(messages.clone())
+            .filters(filters.clone())
+            .highlights(highlights.clone())
+            .channels(channels.clone())
+            .whispers(whispers.clone())
+            .polls(polls.clone())
+            .automod_queue(automod_queue.clone())
             .emotes(&emotes)
             .build();
 
+        let chat_logger = config
+            .chat_logs
+            .enabled
+            .then(|| ChatLogger::new(&config.chat_logs));
+
+        let notifier = config
+            .notifications
+            .enabled
+            .then(|| Notifier::new(&config.notifications, &config.twitch.username));
+
+        let config_watcher = ConfigWatcher::new(&config, event_tx)
+            .inspect_err(|err| warn!("Failed to watch the config for changes: {err}"))
+            .ok();
+
         let emotes_rx = query_emotes(&config, twitch_oauth.clone(), config.twitch.channel.clone());
 
-        Self {
+        let mut app = Self {
             running: true,
             components,
             config,
             twitch_oauth,
             events,
             twitch_tx,
-            messages,
+            channels,
+            whispers,
+            polls,
+            automod_queue,
             storage,
+            chat_logger,
+            notifier,
+            filters,
+            highlights,
+            _config_watcher: config_watcher,
+            chatters: SeenChatters::default(),
             state: first_state,
             previous_state: None,
             emotes,
             emotes_rx,
+            badges_rx: vec![],
             decoded_emotes_rx,
             running_stream: None,
+        };
+
+        if !filters_errors.is_empty() {
+            app.show_config_errors(
+                "Some filters are invalid, so they were skipped.",
+                &filters_errors,
+            );
+        }
+
+        let channel = app.config.twitch.channel.clone();
+        app.load_chat_history(&channel);
+
+        app
+    }
+
+    /// Fills a newly joined channel with its most recent messages from the chat logs
+    fn load_chat_history(&mut self, channel: &str) {
+        let Some(chat_logger) = &mut self.chat_logger else {
+            return;
+        };
+
+        let Some(messages) = self.channels.borrow().messages(Some(channel)) else {
+            return;
+        };
+
+        let mut messages = messages.borrow_mut();
+
+        // Joining a channel that was already joined keeps its messages
+        if !messages.is_empty() {
+            return;
+        }
+
+        let count = self
+            .config
+            .chat_logs
+            .history_messages
+            .min(self.config.terminal.maximum_messages);
+
+        for entry in chat_logger.history(channel, count) {
+            let mut message_data = MessageData::from_twitch_message(entry.into(), &self.emotes);
+            message_data.history = true;
+
+            if !message_data.system {
+                self.chatters.insert(channel, &message_data.author);
+            }
+
+            messages.push_back(message_data);
+        }
+    }
+
+    /// Reloads the config and filters from disk, keeping the current ones if anything is invalid
+    fn reload_config(&mut self) {
+        let reloaded = self.config.reload().and_then(|config| {
+            let config = SharedCoreConfig::new(config);
+            let filters = Filters::new(&config)?;
+
+            Ok((config, filters))
+        });
+
+        match reloaded {
+            Ok((config, mut filters)) => {
+                filters.keep_toggles(&self.filters.borrow(), &self.config);
+                *self.filters.borrow_mut() = filters;
+                *self.highlights.borrow_mut() = Highlights::new(&config.highlights);
+
+                self.components.set_config(&config);
+                self.config = config;
+
+                if self.components.config_error.is_focused() {
+                    self.components.config_error.toggle_focus();
+                }
+            }
+            Err(err) => self.show_config_error(
+                "The config could not be reloaded, so the previous one is still in use.",
+                &err,
+            ),
+        }
+    }
+
+    fn show_config_error(&mut self, summary: &str, err: &color_eyre::Report) {
+        self.show_config_errors(summary, std::slice::from_ref(err));
+    }
+
+    fn show_config_errors(&mut self, summary: &str, errs: &[color_eyre::Report]) {
+        let mut message = vec![summary.to_string()];
+
+        for err in errs {
+            warn!("{summary} {err:?}");
+
+            message.push(String::new());
+            message.extend(err.chain().map(ToString::to_string));
+        }
+
+        self.components.config_error.set_message(message);
+
+        if !self.components.config_error.is_focused() {
+            self.components.config_error.toggle_focus();
         }
     }
 
@@ -156,33 +328,59 @@ impl App {
         self.emotes.unload();
     }
 
-    fn clear_messages(&mut self) {
-        self.messages.borrow_mut().clear();
+    fn clear_messages(&mut self, channel: Option<&str>) {
+        let channels = self.channels.borrow();
 
-        self.components.chat.scroll_offset.jump_to(0);
+        if let Some(messages) = channels.messages(channel) {
+            messages.borrow_mut().clear();
+
+            if Rc::ptr_eq(&messages, &channels.current_messages()) {
+                self.components.chat.scroll_offset.jump_to(0);
+                self.components.chat.clear_selection();
+            }
+        }
     }
 
-    fn purge_user_messages(&self, user_id: &str) {
-        let messages = self
-            .messages
+    fn purge_user_messages(&mut self, channel: Option<&str>, user_id: &str) {
+        let Some(channel_messages) = self.channels.borrow().messages(channel) else {
+            return;
+        };
+
+        if Rc::ptr_eq(
+            &channel_messages,
+            &self.channels.borrow().current_messages(),
+        ) {
+            self.components.chat.clear_selection();
+        }
+
+        let messages = channel_messages
             .borrow_mut()
             .iter()
             .filter(|&m| m.user_id.clone().is_none_or(|user| user != user_id))
             .cloned()
             .collect::<VecDeque<MessageData>>();
 
-        self.messages.replace(messages);
+        channel_messages.replace(messages);
     }
 
-    fn remove_message_with(&self, message_id: &str) {
-        let index = self
-            .messages
-            .borrow_mut()
-            .iter()
-            .position(|f| f.message_id.clone().is_some_and(|id| id == message_id));
+    fn remove_message_with(&mut self, message_id: &str) {
+        let channels = self.channels.borrow();
+        let current_messages = channels.current_messages();
+
+        // Message IDs are unique across all channels
+        for messages in channels.all_messages() {
+            let index = messages
+                .borrow_mut()
+                .iter()
+                .position(|f| f.message_id.clone().is_some_and(|id| id == message_id));
+
+            if let Some(i) = index {
+                messages.borrow_mut().remove(i).unwrap();
 
-        if let Some(i) = index {
-            self.messages.borrow_mut().remove(i).unwrap();
+                if Rc::ptr_eq(messages, &current_messages) {
+                    self.components.chat.message_removed(i);
+                }
+            }
         }
     }
 
@@ -217,12 +415,40 @@ impl App {
 
     fn handle_emote_event(&mut self) {
         // Check if we have received any emotes
-        if let Ok((user_emotes, global_emotes)) = self.emotes_rx.try_recv() {
+        if let Ok((user_emotes, global_emotes, badges)) = self.emotes_rx.try_recv() {
             *self.emotes.user_emotes.borrow_mut() = user_emotes;
             *self.emotes.global_emotes.borrow_mut() = global_emotes;
+            // Badges of opened channels may have arrived first
+            self.emotes.badges.borrow_mut().extend(badges);
 
-            for message in &mut *self.messages.borrow_mut() {
-                message.reparse_emotes(&self.emotes);
+            for messages in self.channels.borrow().all_messages() {
+                for message in &mut *messages.borrow_mut() {
+                    message.reparse_emotes(&self.emotes);
+                }
+            }
+        }
+
+        // Channels that are opened next to the current one have badges of their own
+        let mut received_badges = vec![];
+
+        self.badges_rx.retain_mut(|rx| match rx.try_recv() {
+            Ok(badges) => {
+                received_badges.push(badges);
+                false
+            }
+            Err(err) => err == TryRecvError::Empty,
+        });
+
+        if !received_badges.is_empty() {
+            self.emotes
+                .badges
+                .borrow_mut()
+                .extend(received_badges.into_iter().flatten());
+
+            for messages in self.channels.borrow().all_messages() {
+                for message in &mut *messages.borrow_mut() {
+                    message.reparse_emotes(&self.emotes);
+                }
             }
         }
 
@@ -260,7 +486,7 @@ impl App {
             }
             InternalEvent::SwitchState(state) => {
                 if self.state == State::Normal {
-                    self.clear_messages();
+                    self.clear_messages(None);
                 }
 
                 self.set_state(state.clone());
@@ -269,6 +495,7 @@ impl App {
                 self.open_stream(channel);
             }
             InternalEvent::SelectEmote(_) => {}
+            InternalEvent::ReloadConfig => self.reload_config(),
         }
     }
 
@@ -276,7 +503,9 @@ impl App {
         match twitch_action {
             TwitchAction::JoinChannel(channel) => {
                 let channel = clean_channel_name(channel);
-                self.clear_messages();
+                self.clear_messages(None);
+                self.channels.borrow_mut().replace_current(channel.clone());
+                self.load_chat_history(&channel);
                 self.emotes.unload();
 
                 self.twitch_tx
@@ -289,10 +518,55 @@ impl App {
                 self.emotes_rx = query_emotes(&self.config, self.twitch_oauth.clone(), channel);
                 self.set_state(State::Normal);
             }
-            TwitchAction::Message(message) => {
+            TwitchAction::OpenChannel(channel) => {
+                let channel = clean_channel_name(channel);
+                self.channels.borrow_mut().open(channel.clone());
+                self.load_chat_history(&channel);
+                self.components.chat.scroll_offset.jump_to(0);
+                self.components.chat.clear_selection();
+
+                self.badges_rx.push(query_channel_badges(
+                    &self.config,
+                    self.twitch_oauth.clone(),
+                    channel.clone(),
+                ));
+
                 self.twitch_tx
-                    .send(TwitchAction::Message(message.clone()))
+                    .send(TwitchAction::OpenChannel(channel))
                     .await?;
+
+                self.set_state(State::Normal);
+            }
+            TwitchAction::LeaveChannel(channel) => {
+                if self.channels.borrow_mut().leave(channel) {
+                    self.components.chat.scroll_offset.jump_to(0);
+                    self.components.chat.clear_selection();
+
+                    let current_channel = self.channels.borrow().current_name().to_string();
+
+                    self.twitch_tx
+                        .send(TwitchAction::LeaveChannel(channel.clone()))
+                        .await?;
+                    self.twitch_tx
+                        .send(TwitchAction::FocusChannel(current_channel))
+                        .await?;
+                }
+            }
+            TwitchAction::FocusChannel(channel) => {
+                if self.channels.borrow_mut().focus(channel) {
+                    self.components.chat.scroll_offset.jump_to(0);
+                    self.components.chat.clear_selection();
+
+                    self.twitch_tx
+                        .send(TwitchAction::FocusChannel(channel.clone()))
+                        .await?;
+                }
+            }
+            TwitchAction::Message(_)
+            | TwitchAction::Reply { .. }
+            | TwitchAction::DeleteMessage(_)
+            | TwitchAction::TimeoutUser { .. } => {
+                self.twitch_tx.send(twitch_action.clone()).await?;
             }
         }
 
@@ -302,7 +576,48 @@ impl App {
     fn handle_twitch_notification(&mut self, twitch_notification: &TwitchNotification) {
         match twitch_notification {
             TwitchNotification::Message(m) => {
-                let message_data = MessageData::from_twitch_message(m.clone(), &self.emotes);
+                let Some(messages) = self.channels.borrow().messages(m.channel.as_deref()) else {
+                    return;
+                };
+                let is_current_channel =
+                    Rc::ptr_eq(&messages, &self.channels.borrow().current_messages());
+
+                let first_seen = {
+                    let channels = self.channels.borrow();
+                    let channel = m
+                        .channel
+                        .as_deref()
+                        .unwrap_or_else(|| channels.current_name());
+
+                    if let Some(chat_logger) = &mut self.chat_logger {
+                        if let Err(err) = chat_logger.log(channel, m) {
+                            warn!("Failed to write message to chat log: {err}");
+                        }
+                    }
+
+                    // Highlight rules only ever apply to messages from other chatters
+                    let (notify, sound) = self
+                        .highlights
+                        .borrow()
+                        .find(&m.author, &m.payload)
+                        .filter(|_| !m.author.eq_ignore_ascii_case(&self.config.twitch.username))
+                        .map_or((false, false), |rule| (rule.notify, rule.sound));
+
+                    if let Some(notifier) = &self.notifier {
+                        notifier.notify(channel, m, notify);
+                    }
+
+                    if sound {
+                        if let Err(err) = ring_bell() {
+                            warn!("Failed to ring the terminal bell: {err}");
+                        }
+                    }
+
+                    !m.system && self.chatters.insert(channel, &m.author)
+                };
+
+                let mut message_data = MessageData::from_twitch_message(*m.clone(), &self.emotes);
+                message_data.first_seen = first_seen;
                 if !KNOWN_CHATTERS.contains(&message_data.author.as_str())
                     && self.config.twitch.username != message_data.author
                 {
@@ -310,23 +625,51 @@ impl App {
                         .borrow_mut()
                         .add("chatters", message_data.author.clone());
                 }
-                self.messages.borrow_mut().push_front(message_data);
+                self.channels.borrow().push(&messages, message_data);
 
-                // If scrolling is enabled, pad for more messages.
-                if self.components.chat.scroll_offset.get_offset() > 0 {
-                    self.components.chat.scroll_offset.up();
+                if is_current_channel {
+                    self.components.chat.shift_selection();
+
+                    // If scrolling is enabled, pad for more messages.
+                    if self.components.chat.scroll_offset.get_offset() > 0 {
+                        self.components.chat.scroll_offset.up();
+                    }
                 }
             }
-            TwitchNotification::ClearChat(user_id) => {
+            TwitchNotification::ClearChat { channel, user_id } => {
                 if let Some(user) = user_id {
-                    self.purge_user_messages(user.as_str());
+                    self.purge_user_messages(channel.as_deref(), user.as_str());
                 } else {
-                    self.clear_messages();
+                    self.clear_messages(channel.as_deref());
                 }
             }
             TwitchNotification::DeleteMessage(message_id) => {
                 self.remove_message_with(message_id.as_str());
             }
+            TwitchNotification::ConnectionState(state) => {
+                self.components.chat.connection_state = *state;
+            }
+            TwitchNotification::Whisper(whisper) => {
+                if let Some(notifier) = &self.notifier {
+                    notifier.notify_whisper(whisper);
+                }
+
+                self.whispers.borrow_mut().push(whisper.clone());
+            }
+            TwitchNotification::Poll(poll) => {
+                self.polls.borrow_mut().update(poll.clone());
+            }
+            TwitchNotification::StreamStatus { channel, status } => {
+                self.components
+                    .chat
+                    .set_stream_status(channel, status.clone());
+            }
+            TwitchNotification::HeldMessage(held_message) => {
+                self.automod_queue.borrow_mut().hold(held_message.clone());
+            }
+            TwitchNotification::HeldMessageResolved(message_id) => {
+                self.automod_queue.borrow_mut().remove(message_id);
+            }
         }
     }
 }
@@ -368,6 +711,12 @@ impl Component for App {
 
             self.components.debug.draw(f, Some(rect));
         }
+
+        if self.components.config_error.is_focused() {
+            self.components
+                .config_error
+                .draw(f, Some(popup_area(f.area(), 60, 30)));
+        }
     }
 
     async fn event(&mut self, event: &Event) -> Result<()> {
@@ -384,6 +733,21 @@ impl Component for App {
                 }
             },
             Event::Input(key) => {
+                // The error has to be dismissed before anything else can be done
+                if self.components.config_error.is_focused() {
+                    if self
+                        .config
+                        .keybinds
+                        .selection
+                        .back_to_previous_window
+                        .contains(key)
+                    {
+                        self.components.config_error.toggle_focus();
+                    }
+
+                    return Ok(());
+                }
+
                 if self.components.debug.is_focused() {
                     return self.components.debug.event(event).await;
                 }
@@ -392,7 +756,13 @@ impl Component for App {
                     self.components.debug.toggle_focus();
                 }
             }
-            Event::Tick => {}
+            Event::Tick => {
+                if let Some(chat_logger) = &mut self.chat_logger {
+                    if let Err(err) = chat_logger.flush() {
+                        warn!("Failed to write chat logs: {err}");
+                    }
+                }
+            }
         }
 
         match self.state {
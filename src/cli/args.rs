@@ -1,7 +1,9 @@
+use std::path::PathBuf;
+
 use clap::{Parser, ValueEnum, builder::PossibleValue};
 
 use crate::{
-    config::{CoreConfig, LogLevel, Palette, Theme},
+    config::{CoreConfig, FrontendConfig, LogLevel, Palette, Theme},
     handlers::state::State,
 };
 
@@ -85,6 +87,23 @@ pub struct Cli {
     /// Show a warning if the screen size is too small
     #[arg(short, long)]
     pub unsupported_screen_size: bool,
+    /// Replay messages recorded from Twitch in a file, instead of connecting to Twitch
+    #[arg(short, long)]
+    pub replay: Option<PathBuf>,
+    /// How many times faster than real time to replay messages, where 0 replays them all at once
+    #[arg(long, default_value_t = 1.0, value_parser = parse_replay_speed)]
+    pub replay_speed: f64,
+    /// Record every message received from Twitch to a file, with tokens and session IDs redacted
+    #[arg(long, conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+}
+
+fn parse_replay_speed(speed: &str) -> Result<f64, String> {
+    match speed.parse::<f64>() {
+        Ok(speed) if speed.is_finite() && speed >= 0.0 => Ok(speed),
+        Ok(_) => Err("the replay speed has to be a number that is 0 or more".to_string()),
+        Err(err) => Err(err.to_string()),
+    }
 }
 
 pub fn merge_args_into_config(config: &mut CoreConfig, args: Cli) {
@@ -112,20 +131,42 @@ pub fn merge_args_into_config(config: &mut CoreConfig, args: Cli) {
         config.twitch.channel = channel;
     }
 
-    // Frontend arguments
-    config.frontend.show_datetimes = config.frontend.show_datetimes || args.show_datetimes;
+    config.frontend_args = FrontendArgs {
+        show_datetimes: args.show_datetimes,
+        palette: args.palette,
+        badges: args.badges,
+        theme: args.theme,
+        unsupported_screen_size: args.unsupported_screen_size,
+    };
+    config.frontend_args.apply(&mut config.frontend);
+}
 
-    if let Some(palette) = args.palette {
-        config.frontend.palette = palette;
-    }
+/// The arguments that change the frontend, which are kept so that they still apply after the config is reloaded
+#[derive(Debug, Clone, Default)]
+pub struct FrontendArgs {
+    show_datetimes: bool,
+    palette: Option<Palette>,
+    badges: bool,
+    theme: Option<Theme>,
+    unsupported_screen_size: bool,
+}
 
-    config.frontend.badges = config.frontend.badges || args.badges;
+impl FrontendArgs {
+    pub fn apply(&self, frontend: &mut FrontendConfig) {
+        frontend.show_datetimes = frontend.show_datetimes || self.show_datetimes;
 
-    if let Some(theme) = args.theme {
-        config.frontend.theme = theme;
-    }
+        if let Some(palette) = &self.palette {
+            frontend.palette = palette.clone();
+        }
+
+        frontend.badges = frontend.badges || self.badges;
+
+        if let Some(theme) = &self.theme {
+            frontend.theme = theme.clone();
+        }
 
-    if args.unsupported_screen_size {
-        config.frontend.show_unsupported_screen_size = false;
+        if self.unsupported_screen_size {
+            frontend.show_unsupported_screen_size = false;
+        }
     }
 }
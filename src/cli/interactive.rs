@@ -58,6 +58,7 @@ pub fn interactive_config() -> Option<CoreConfig> {
             server,
             token: Some(token),
             keepalive_timeout_seconds,
+            ..Default::default()
         },
         ..Default::default()
     })
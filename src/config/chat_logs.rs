@@ -0,0 +1,69 @@
+use std::{fmt, vec};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatLogFormat {
+    /// One human-readable line per message
+    #[default]
+    Text,
+    /// One JSON object per line
+    Jsonl,
+}
+
+impl ChatLogFormat {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "log",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+impl fmt::Display for ChatLogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Text => "text",
+                Self::Jsonl => "jsonl",
+            }
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ChatLogsConfig {
+    /// If chat messages should be written to disk.
+    pub enabled: bool,
+    /// The format that messages are written in.
+    pub format: ChatLogFormat,
+    /// How many of the most recent logged messages to show when joining a channel.
+    pub history_messages: usize,
+}
+
+impl Default for ChatLogsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: ChatLogFormat::default(),
+            history_messages: 50,
+        }
+    }
+}
+
+impl From<ChatLogsConfig> for Vec<(String, String)> {
+    fn from(config: ChatLogsConfig) -> Self {
+        vec![
+            ("Enabled".to_string(), config.enabled.to_string()),
+            ("Format".to_string(), config.format.to_string()),
+            (
+                "History messages".to_string(),
+                config.history_messages.to_string(),
+            ),
+        ]
+    }
+}
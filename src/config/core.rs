@@ -4,18 +4,21 @@ use std::{
     sync::Arc,
 };
 
-use color_eyre::eyre::{Error, Result, bail};
+use color_eyre::eyre::{Context, Error, Result, bail};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     cli::{
-        args::{Cli, merge_args_into_config},
+        args::{Cli, FrontendArgs, merge_args_into_config},
         interactive::interactive_config,
     },
     config::{
+        chat_logs::ChatLogsConfig,
         filters::FiltersConfig,
         frontend::FrontendConfig,
+        highlights::HighlightConfig,
         keybinds::KeybindsConfig,
+        notifications::NotificationsConfig,
         persistence::{get_cache_dir, get_config_dir, persist_config, persist_default_config},
         storage::StorageConfig,
         terminal::TerminalConfig,
@@ -33,18 +36,30 @@ pub struct CoreConfig {
     pub terminal: TerminalConfig,
     /// If anything should be recorded for future use.
     pub storage: StorageConfig,
+    /// Writing chat messages to disk.
+    pub chat_logs: ChatLogsConfig,
+    /// Alerting the user about messages.
+    pub notifications: NotificationsConfig,
     /// Filtering out messages.
     pub filters: FiltersConfig,
+    /// Making messages stand out.
+    pub highlights: Vec<HighlightConfig>,
     /// How everything looks to the user.
     pub frontend: FrontendConfig,
     /// Keybinds for each state.
     pub keybinds: KeybindsConfig,
+    /// The frontend options given on the command line, which take the place of the ones in the file.
+    #[serde(skip)]
+    pub frontend_args: FrontendArgs,
 }
 
 pub type SharedCoreConfig = Arc<CoreConfig>;
 
 impl CoreConfig {
     pub fn new(cli: Cli) -> Result<Self, Error> {
+        // Replays don't connect to Twitch, so they don't need a token
+        let replaying = cli.replay.is_some();
+
         let cache_path = get_cache_dir();
         if !cache_path.exists() {
             create_dir_all(cache_path).unwrap();
@@ -83,7 +98,10 @@ impl CoreConfig {
 
                 let check_token = t.token.as_ref().map_or("", |t| t);
 
-                if t.username.is_empty() || t.channel.is_empty() || check_token.is_empty() {
+                if t.username.is_empty()
+                    || t.channel.is_empty()
+                    || (check_token.is_empty() && !replaying)
+                {
                     bail!(
                         "Twitch config section is missing one or more of the following: username, channel, token."
                     );
@@ -113,4 +131,114 @@ impl CoreConfig {
             )
         }
     }
+
+    /// Reads the config file again, taking the filters, highlights and frontend from it,
+    /// with the frontend arguments from the command line applied on top.
+    /// Everything else needs a restart, so it is kept from the running config.
+    pub fn reload(&self) -> Result<Self> {
+        let config_path = get_config_dir().join("config.toml");
+
+        let file_content = read_to_string(&config_path)
+            .with_context(|| format!("Config file {} could not be read", config_path.display()))?;
+
+        self.reloaded(&file_content)
+    }
+
+    fn reloaded(&self, file_content: &str) -> Result<Self> {
+        let file_config: Self = match toml::from_str(file_content) {
+            Ok(c) => c,
+            Err(err) => bail!("Config could not be processed. Error: {:?}", err.message()),
+        };
+
+        let mut config = self.clone();
+
+        // Emotes are set up when twt starts, so turning them on or off has to wait for a restart
+        config.frontend = FrontendConfig {
+            twitch_emotes: self.frontend.twitch_emotes,
+            betterttv_emotes: self.frontend.betterttv_emotes,
+            seventv_emotes: self.frontend.seventv_emotes,
+            frankerfacez_emotes: self.frontend.frankerfacez_emotes,
+            ..file_config.frontend
+        };
+        self.frontend_args.apply(&mut config.frontend);
+        config.filters = file_config.filters;
+        config.highlights = file_config.highlights;
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_keeps_sections_that_need_a_restart() {
+        let mut config = CoreConfig::default();
+        config.twitch.channel = "xithrius".to_string();
+        config.frontend.seventv_emotes = true;
+
+        let reloaded = config
+            .reloaded(
+                r#"
+                [twitch]
+                channel = "other"
+
+                [frontend]
+                seventv_emotes = false
+                show_datetimes = false
+
+                [filters.message]
+                filters = ["^!"]
+
+                [[highlights]]
+                regex = "twt"
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(reloaded.twitch.channel, "xithrius");
+        assert!(reloaded.frontend.seventv_emotes);
+        assert!(!reloaded.frontend.show_datetimes);
+        assert_eq!(
+            reloaded.filters.message.filters,
+            Some(vec!["^!".to_string()])
+        );
+        assert_eq!(reloaded.highlights.len(), 1);
+    }
+
+    #[test]
+    fn test_reload_keeps_command_line_arguments() {
+        use clap::Parser;
+
+        use crate::config::Theme;
+
+        let mut config = CoreConfig::default();
+        merge_args_into_config(
+            &mut config,
+            Cli::parse_from(["twt", "--theme", "light", "--show-datetimes"]),
+        );
+
+        let reloaded = config
+            .reloaded(
+                r#"
+                [frontend]
+                theme = "dark"
+                show_datetimes = false
+                "#,
+            )
+            .unwrap();
+
+        assert!(matches!(reloaded.frontend.theme, Theme::Light));
+        assert!(reloaded.frontend.show_datetimes);
+    }
+
+    #[test]
+    fn test_reload_invalid_config() {
+        assert!(
+            CoreConfig::default()
+                .reloaded("[frontend]\nshow_datetimes = 3")
+                .is_err()
+        );
+    }
 }
@@ -9,6 +9,8 @@ pub struct FiltersConfig {
     pub message: MessageFiltersConfig,
     /// Filters for chat usernames
     pub username: UsernameFiltersConfig,
+    /// Named filter expressions that can be toggled while twt is running
+    pub presets: Vec<FilterPresetConfig>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -29,6 +31,15 @@ pub struct UsernameFiltersConfig {
     pub reversed: bool,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct FilterPresetConfig {
+    pub name: String,
+    /// Messages that match this expression are hidden
+    pub expression: String,
+    pub enabled: bool,
+}
+
 impl From<MessageFiltersConfig> for Vec<(String, String)> {
     fn from(config: MessageFiltersConfig) -> Self {
         vec![
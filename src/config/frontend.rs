@@ -20,8 +20,12 @@ pub struct FrontendConfig {
     pub username_shown: bool,
     /// The color palette.
     pub palette: Palette,
+    /// If chatters' names should be in the colors they picked on Twitch, rather than ones from the palette.
+    pub twitch_colors: bool,
     /// Show Title with time and channel.
     pub title_shown: bool,
+    /// Show whether the channel is live, along with its uptime, viewers, title and category.
+    pub stream_status_shown: bool,
     /// The amount of space between the chat window and the terminal border.
     pub margin: u16,
     /// Show twitch badges next to usernames.
@@ -82,7 +86,9 @@ impl Default for FrontendConfig {
             datetime_format: "%a %b %e %T %Y".to_string(),
             username_shown: true,
             palette: Palette::default(),
+            twitch_colors: false,
             title_shown: true,
+            stream_status_shown: true,
             margin: 0,
             badges: false,
             theme: Theme::default(),
@@ -128,7 +134,15 @@ impl From<FrontendConfig> for Vec<(String, String)> {
                 config.username_shown.to_string(),
             ),
             // ("".to_string(), val.palette.to_string()),
+            (
+                "Twitch colors".to_string(),
+                config.twitch_colors.to_string(),
+            ),
             ("Title shown".to_string(), config.title_shown.to_string()),
+            (
+                "Stream status shown".to_string(),
+                config.stream_status_shown.to_string(),
+            ),
             ("Margin".to_string(), config.margin.to_string()),
             ("Badges".to_string(), config.badges.to_string()),
             // ("".to_string(), val.theme.to_string()),
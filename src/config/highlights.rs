@@ -0,0 +1,88 @@
+use std::{fmt, vec};
+
+use serde::{Deserialize, Serialize};
+use tui::style::{Color, Modifier};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HighlightModifier {
+    Bold,
+    Dim,
+    Italic,
+    Underlined,
+    Reversed,
+    CrossedOut,
+}
+
+impl fmt::Display for HighlightModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Bold => "bold",
+                Self::Dim => "dim",
+                Self::Italic => "italic",
+                Self::Underlined => "underlined",
+                Self::Reversed => "reversed",
+                Self::CrossedOut => "crossed_out",
+            }
+        )
+    }
+}
+
+impl From<HighlightModifier> for Modifier {
+    fn from(modifier: HighlightModifier) -> Self {
+        match modifier {
+            HighlightModifier::Bold => Self::BOLD,
+            HighlightModifier::Dim => Self::DIM,
+            HighlightModifier::Italic => Self::ITALIC,
+            HighlightModifier::Underlined => Self::UNDERLINED,
+            HighlightModifier::Reversed => Self::REVERSED,
+            HighlightModifier::CrossedOut => Self::CROSSED_OUT,
+        }
+    }
+}
+
+/// A rule that makes messages stand out, without hiding anything.
+/// Every field that is set has to match for the rule to apply.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct HighlightConfig {
+    /// Highlights the parts of messages that match this regex.
+    pub regex: Option<String>,
+    /// Highlights every message from this chatter, ignoring case.
+    pub username: Option<String>,
+    /// The color of the highlighted text.
+    pub fg: Option<Color>,
+    /// The color behind the highlighted text.
+    pub bg: Option<Color>,
+    /// How the highlighted text is emphasized.
+    pub modifiers: Vec<HighlightModifier>,
+    /// If messages that match the rule should be notified about.
+    pub notify: bool,
+    /// If the terminal bell should ring for messages that match the rule.
+    pub sound: bool,
+}
+
+impl From<HighlightConfig> for Vec<(String, String)> {
+    fn from(config: HighlightConfig) -> Self {
+        vec![
+            ("Regex".to_string(), format!("{:?}", config.regex)),
+            ("Username".to_string(), format!("{:?}", config.username)),
+            ("Foreground".to_string(), format!("{:?}", config.fg)),
+            ("Background".to_string(), format!("{:?}", config.bg)),
+            (
+                "Modifiers".to_string(),
+                config
+                    .modifiers
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            ("Notify".to_string(), config.notify.to_string()),
+            ("Sound".to_string(), config.sound.to_string()),
+        ]
+    }
+}
@@ -34,12 +34,29 @@ pub struct NormalKeybindsConfig {
     pub search_messages: Keybind,
     pub toggle_filters: Keybind,
     pub reverse_filters: Keybind,
+    pub filter_presets: Keybind,
+    pub filter_editor: Keybind,
+    pub whispers: Keybind,
+    pub redemption_queue: Keybind,
+    pub automod_queue: Keybind,
     pub back_to_previous_window: Keybind,
     pub scroll_down: Keybind,
     pub scroll_up: Keybind,
     pub scroll_to_end: Keybind,
     pub scroll_to_start: Keybind,
     pub open_in_player: Keybind,
+    pub select_message: Keybind,
+    pub reply_to_message: Keybind,
+    pub delete_message: Keybind,
+    pub timeout_user: Keybind,
+    pub ban_user: Keybind,
+    pub copy_message: Keybind,
+    pub open_profile: Keybind,
+    pub next_mention: Keybind,
+    pub open_channel: Keybind,
+    pub leave_channel: Keybind,
+    pub next_channel: Keybind,
+    pub previous_channel: Keybind,
     pub recent_channels_search: Keybind,
     pub followed_channels_search: Keybind,
     pub help: Keybind,
@@ -114,12 +131,29 @@ impl Default for NormalKeybindsConfig {
             search_messages: Box::new([Key::Ctrl('f')]),
             toggle_filters: Box::new([Key::Ctrl('t')]),
             reverse_filters: Box::new([Key::Ctrl('r')]),
+            filter_presets: Box::new([Key::Char('F')]),
+            filter_editor: Box::new([Key::Char('e')]),
+            whispers: Box::new([Key::Char('w')]),
+            redemption_queue: Box::new([Key::Char('R')]),
+            automod_queue: Box::new([Key::Char('A')]),
             back_to_previous_window: Box::new([Key::Esc]),
             scroll_up: Box::new([Key::ScrollUp, Key::Up, Key::Char('k')]),
             scroll_down: Box::new([Key::ScrollDown, Key::Down, Key::Char('j')]),
             scroll_to_end: Box::new([Key::Char('G')]),
             scroll_to_start: Box::new([Key::Char('g')]),
             open_in_player: Box::new([Key::Char('o')]),
+            select_message: Box::new([Key::Char('v')]),
+            reply_to_message: Box::new([Key::Char('r')]),
+            delete_message: Box::new([Key::Char('d')]),
+            timeout_user: Box::new([Key::Char('t')]),
+            ban_user: Box::new([Key::Char('b')]),
+            copy_message: Box::new([Key::Char('y')]),
+            open_profile: Box::new([Key::Char('p')]),
+            next_mention: Box::new([Key::Char('m')]),
+            open_channel: Box::new([Key::Char('n')]),
+            leave_channel: Box::new([Key::Char('x')]),
+            next_channel: Box::new([Key::Tab, Key::Char(']')]),
+            previous_channel: Box::new([Key::Char('[')]),
             recent_channels_search: Box::new([Key::Char('s')]),
             followed_channels_search: Box::new([Key::Char('f')]),
             help: Box::new([Key::Char('?'), Key::Char('h')]),
@@ -1,17 +1,23 @@
+mod chat_logs;
 mod core;
 mod filters;
 mod frontend;
+mod highlights;
 mod keybinds;
 mod logs;
+mod notifications;
 mod persistence;
 mod storage;
 mod terminal;
 mod twitch;
 
 pub use crate::config::{
+    chat_logs::{ChatLogFormat, ChatLogsConfig},
     core::{CoreConfig, SharedCoreConfig},
     frontend::{CursorType, FrontendConfig, Palette, Theme},
+    highlights::HighlightConfig,
     logs::LogLevel,
+    notifications::{NotificationMethod, NotificationsConfig},
     persistence::{get_cache_dir, get_config_dir, get_data_dir},
     twitch::TwitchConfig,
 };
@@ -0,0 +1,86 @@
+use std::{fmt, vec};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationMethod {
+    /// Rings the terminal bell
+    Bell,
+    /// A desktop notification through the OSC 9 escape sequence, as supported by iTerm2, kitty and others
+    Osc9,
+    /// A desktop notification through the OSC 777 escape sequence, as supported by urxvt, foot and others
+    Osc777,
+    /// Runs the configured command
+    Command,
+}
+
+impl fmt::Display for NotificationMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Bell => "bell",
+                Self::Osc9 => "osc9",
+                Self::Osc777 => "osc777",
+                Self::Command => "command",
+            }
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    /// If anything should alert the user about messages they might have missed.
+    pub enabled: bool,
+    /// How the user is alerted.
+    pub methods: Vec<NotificationMethod>,
+    /// The command and its args that are run for every notification, receiving the message as JSON on stdin.
+    pub command: Vec<String>,
+    /// If messages that mention the user should be notified about.
+    pub mentions: bool,
+    /// Words that cause a notification when a message contains any of them, ignoring case.
+    pub keywords: Vec<String>,
+    /// If raids on the channel should be notified about.
+    pub raids: bool,
+    /// If whispers sent to the user should be notified about.
+    pub whispers: bool,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            methods: vec![NotificationMethod::Bell],
+            command: vec![],
+            mentions: true,
+            keywords: vec![],
+            raids: true,
+            whispers: true,
+        }
+    }
+}
+
+impl From<NotificationsConfig> for Vec<(String, String)> {
+    fn from(config: NotificationsConfig) -> Self {
+        vec![
+            ("Enabled".to_string(), config.enabled.to_string()),
+            (
+                "Methods".to_string(),
+                config
+                    .methods
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            ("Command".to_string(), config.command.join(" ")),
+            ("Mentions".to_string(), config.mentions.to_string()),
+            ("Keywords".to_string(), config.keywords.join(", ")),
+            ("Raids".to_string(), config.raids.to_string()),
+            ("Whispers".to_string(), config.whispers.to_string()),
+        ]
+    }
+}
@@ -17,6 +17,8 @@ pub struct TerminalConfig {
     pub log_level: LogLevel,
     /// What state the application should start in.
     pub first_state: State,
+    /// How many seconds the author of a selected message is timed out for.
+    pub timeout_duration: usize,
 }
 
 impl Default for TerminalConfig {
@@ -27,6 +29,7 @@ impl Default for TerminalConfig {
             log_file: None,
             log_level: LogLevel::INFO,
             first_state: State::default(),
+            timeout_duration: 600,
         }
     }
 }
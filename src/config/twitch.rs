@@ -15,6 +15,10 @@ pub struct TwitchConfig {
     pub token: Option<String>,
     /// Keepalive timeout
     pub keepalive_timeout_seconds: usize,
+    /// The Helix API that requests are sent to.
+    pub api_url: String,
+    /// Where the authentication token is validated.
+    pub oauth_validate_url: String,
 }
 
 impl TwitchConfig {
@@ -35,6 +39,8 @@ impl Default for TwitchConfig {
             server: "wss://eventsub.wss.twitch.tv/ws".to_string(),
             token: None,
             keepalive_timeout_seconds: 30,
+            api_url: "https://api.twitch.tv/helix".to_string(),
+            oauth_validate_url: "https://id.twitch.tv/oauth2/validate".to_string(),
         }
     }
 }
@@ -45,6 +51,7 @@ impl From<TwitchConfig> for Vec<(String, String)> {
             ("Username".to_string(), config.username.clone()),
             ("Channel".to_string(), config.channel.clone()),
             ("Server".to_string(), config.server),
+            ("API".to_string(), config.api_url),
         ]
     }
 }
@@ -4,11 +4,16 @@ use color_eyre::{Result, eyre::ContextCompat};
 use futures::StreamExt;
 use reqwest::{Client, Response};
 use tokio::io::AsyncWriteExt;
+use tracing::warn;
 
 use crate::{
     config::{CoreConfig, FrontendConfig, get_cache_dir},
     emotes::DownloadedEmotes,
-    twitch::{api::channels::get_channel_id, oauth::TwitchOauth},
+    twitch::{
+        api::{channels::get_channel_id, cheermotes::get_cheermotes},
+        cheers::cheermote_filename,
+        oauth::TwitchOauth,
+    },
 };
 
 // HashMap of emote name, emote filename, emote url, and if the emote is an overlay
@@ -20,7 +25,16 @@ mod twitch {
     use serde::Deserialize;
     use tracing::warn;
 
-    use crate::{emotes::downloader::EmoteMap, twitch::api::TWITCH_API_BASE_URL};
+    use crate::{
+        emotes::downloader::EmoteMap,
+        twitch::{
+            api::{
+                TWITCH_API_BASE_URL,
+                chat_badges::{ChatBadgeSet, get_channel_chat_badges, get_global_chat_badges},
+            },
+            badges::{badge_key, channel_badge_key},
+        },
+    };
 
     #[derive(Deserialize, Debug)]
     struct Emote {
@@ -111,6 +125,46 @@ mod twitch {
 
         Ok(parse_emote_list(user_emotes))
     }
+
+    /// Images of the badges in the sets, keyed by [`channel_badge_key`] if they are the ones that a channel made for itself
+    fn parse_badge_sets(badge_sets: Vec<ChatBadgeSet>, channel_id: Option<&str>) -> EmoteMap {
+        badge_sets
+            .into_iter()
+            .flat_map(|badge_set| {
+                let set_id = badge_set.set_id;
+
+                badge_set.versions.into_iter().map(move |version| {
+                    let filename = version.image_id().map_or_else(
+                        || format!("badge_{set_id}_{}", version.id),
+                        |image_id| format!("badge_{image_id}"),
+                    );
+
+                    let key = channel_id.map_or_else(
+                        || badge_key(&set_id, &version.id),
+                        |channel_id| channel_badge_key(channel_id, &set_id, &version.id),
+                    );
+
+                    (key, (filename, version.image_url_1x, false))
+                })
+            })
+            .collect()
+    }
+
+    /// The global badges, and the ones that the channel made for itself which are shown in their place
+    pub async fn get_badges(client: &Client, channel_id: &str) -> Result<EmoteMap> {
+        let mut badges = parse_badge_sets(get_global_chat_badges(client).await?, None);
+
+        badges.extend(get_channel_badges(client, channel_id).await?);
+
+        Ok(badges)
+    }
+
+    pub async fn get_channel_badges(client: &Client, channel_id: &str) -> Result<EmoteMap> {
+        Ok(parse_badge_sets(
+            get_channel_chat_badges(client, channel_id).await?,
+            Some(channel_id),
+        ))
+    }
 }
 
 mod betterttv {
@@ -422,7 +476,7 @@ pub async fn get_emotes(
     config: &CoreConfig,
     twitch_oauth: TwitchOauth,
     channel: &str,
-) -> Result<(DownloadedEmotes, DownloadedEmotes)> {
+) -> Result<(DownloadedEmotes, DownloadedEmotes, DownloadedEmotes)> {
     let user_id = twitch_oauth
         .user_id()
         .context("Unable to get user ID from Twitch OAuth")?;
@@ -442,6 +496,15 @@ pub async fn get_emotes(
         HashMap::default()
     };
 
+    let badges = if config.frontend.badges {
+        twitch::get_badges(&twitch_client, &channel_id)
+            .await
+            .inspect_err(|err| warn!("Unable to get chat badges: {err}"))
+            .unwrap_or_default()
+    } else {
+        HashMap::default()
+    };
+
     // Concurrently get the list of emotes for each provider
     let global_emotes = futures::stream::iter(enabled_emotes.into_iter().map(|emote_provider| {
         let channel_id = channel_id.clone();
@@ -466,9 +529,31 @@ pub async fn get_emotes(
     Ok((
         download_emotes(user_emotes).await,
         download_emotes(global_emotes).await,
+        download_emotes(badges).await,
     ))
 }
 
+/// The badges that a channel made for itself, for when it is opened next to the current one
+pub async fn get_channel_badges(
+    config: &CoreConfig,
+    twitch_oauth: TwitchOauth,
+    channel: &str,
+) -> Result<DownloadedEmotes> {
+    if !config.frontend.badges {
+        return Ok(DownloadedEmotes::default());
+    }
+
+    let twitch_client = twitch_oauth
+        .client()
+        .context("Unable to get client from Twitch OAuth")?;
+
+    let channel_id = get_channel_id(&twitch_client, channel).await?;
+
+    let badges = twitch::get_channel_badges(&twitch_client, &channel_id).await?;
+
+    Ok(download_emotes(badges).await)
+}
+
 pub async fn get_twitch_emote(emote_id: &str) -> Result<()> {
     // Checks if emote is already downloaded.
     let path = get_cache_dir().join(emote_id);
@@ -494,3 +579,42 @@ pub async fn get_twitch_emote(emote_id: &str) -> Result<()> {
 
     save_emote(&path, res).await
 }
+
+/// Downloads the image of a cheermote if it isn't cached yet, returning its filename.
+pub async fn get_twitch_cheermote(
+    client: &Client,
+    broadcaster_id: &str,
+    prefix: &str,
+    tier: usize,
+) -> Result<String> {
+    let filename = cheermote_filename(prefix, tier);
+    let path = get_cache_dir().join(&filename);
+
+    if tokio::fs::metadata(&path).await.is_ok() {
+        return Ok(filename);
+    }
+
+    // Cheermotes can be made by the channel, so their images have to be looked up.
+    let cheermotes = get_cheermotes(client, broadcaster_id).await?;
+
+    let images = &cheermotes
+        .iter()
+        .find(|cheermote| cheermote.prefix.eq_ignore_ascii_case(prefix))
+        .and_then(|cheermote| cheermote.tier(tier))
+        .with_context(|| format!("Could not find tier {tier} of cheermote {prefix}"))?
+        .images
+        .dark;
+
+    // Try the animated version first.
+    let url = images
+        .animated
+        .get("1")
+        .or_else(|| images.static_.get("1"))
+        .with_context(|| format!("Could not find an image for cheermote {prefix}{tier}"))?;
+
+    let res = Client::new().get(url).send().await?.error_for_status()?;
+
+    save_emote(&path, res).await?;
+
+    Ok(filename)
+}
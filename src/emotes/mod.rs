@@ -17,7 +17,10 @@ use tracing::{error, info, warn};
 
 use crate::{
     config::{CoreConfig, get_cache_dir},
-    emotes::{downloader::get_emotes, graphics_protocol::Image},
+    emotes::{
+        downloader::{get_channel_badges, get_emotes},
+        graphics_protocol::Image,
+    },
     twitch::oauth::TwitchOauth,
     utils::emotes::get_emote_offset,
 };
@@ -25,7 +28,7 @@ use crate::{
 mod downloader;
 mod graphics_protocol;
 
-pub use downloader::get_twitch_emote;
+pub use downloader::{get_twitch_cheermote, get_twitch_emote};
 pub use graphics_protocol::{ApplyCommand, DecodedEmote, support_graphics_protocol};
 
 // HashMap of emote name, emote filename, and if the emote is an overlay
@@ -58,6 +61,9 @@ pub struct Emotes {
     /// `global_emotes` is used everywhere.
     pub user_emotes: RefCell<DownloadedEmotes>,
     pub global_emotes: RefCell<DownloadedEmotes>,
+    /// Map of badge key (see [`badge_key`](crate::twitch::badges::badge_key) and
+    /// [`channel_badge_key`](crate::twitch::badges::channel_badge_key)) and the filename of its image.
+    pub badges: RefCell<DownloadedEmotes>,
     /// Info about loaded emotes
     pub info: RefCell<HashMap<String, LoadedEmote>>,
     /// Terminal cell size in pixels: (width, height)
@@ -81,6 +87,7 @@ impl Emotes {
         Self {
             user_emotes: RefCell::default(),
             global_emotes: RefCell::default(),
+            badges: RefCell::default(),
             info: RefCell::default(),
             cell_size: OnceCell::default(),
             enabled,
@@ -96,6 +103,7 @@ impl Emotes {
             });
         self.user_emotes.borrow_mut().clear();
         self.global_emotes.borrow_mut().clear();
+        self.badges.borrow_mut().clear();
         self.info.borrow_mut().clear();
     }
 }
@@ -114,7 +122,7 @@ pub fn query_emotes(
     config: &CoreConfig,
     twitch_oauth: TwitchOauth,
     channel: String,
-) -> OSReceiver<(DownloadedEmotes, DownloadedEmotes)> {
+) -> OSReceiver<(DownloadedEmotes, DownloadedEmotes, DownloadedEmotes)> {
     let (tx, mut rx) = tokio::sync::oneshot::channel();
 
     if config.frontend.is_emotes_enabled() {
@@ -130,7 +138,7 @@ pub fn query_emotes(
 pub async fn send_emotes(
     config: &CoreConfig,
     twitch_oauth: TwitchOauth,
-    tx: OSSender<(DownloadedEmotes, DownloadedEmotes)>,
+    tx: OSSender<(DownloadedEmotes, DownloadedEmotes, DownloadedEmotes)>,
     channel: String,
 ) {
     info!("Starting emotes download.");
@@ -147,6 +155,34 @@ pub async fn send_emotes(
     }
 }
 
+pub fn query_channel_badges(
+    config: &CoreConfig,
+    twitch_oauth: TwitchOauth,
+    channel: String,
+) -> OSReceiver<DownloadedEmotes> {
+    let (tx, mut rx) = tokio::sync::oneshot::channel();
+
+    if config.frontend.is_emotes_enabled() {
+        let config = config.clone();
+        tokio::spawn(async move {
+            match get_channel_badges(&config, twitch_oauth, &channel).await {
+                Ok(badges) => {
+                    if tx.send(badges).is_err() {
+                        warn!("Unable to send badges to main thread.");
+                    }
+                }
+                Err(e) => {
+                    warn!("Unable to download badges of {channel}: {e}");
+                }
+            }
+        });
+    } else {
+        rx.close();
+    }
+
+    rx
+}
+
 pub static DECODE_EMOTE_SENDER: OnceLock<Sender<Image>> = OnceLock::new();
 
 pub fn decoder(mut rx: Receiver<Image>, tx: &Sender<Result<DecodedEmote, String>>) {
@@ -1,6 +1,9 @@
 use crate::{
     events::key::Key,
-    handlers::{data::RawMessageData, state::State},
+    handlers::{
+        automod::HeldMessage, data::RawMessageData, polls::LivePoll, state::State,
+        stream_status::StreamStatus, whispers::Whisper,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,6 +29,8 @@ pub enum InternalEvent {
     SwitchState(State),
     OpenStream(String),
     SelectEmote(String),
+    /// The config or filter files changed on disk
+    ReloadConfig,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,12 +42,68 @@ pub enum TwitchEvent {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TwitchAction {
     Message(String),
+    /// Reply to a message in the current channel
+    Reply {
+        message: String,
+        parent_message_id: String,
+    },
+    /// Delete a message in the current channel by its ID
+    DeleteMessage(String),
+    /// Timeout a user in the current channel by their ID, banning them if there is no duration
+    TimeoutUser {
+        user_id: String,
+        username: String,
+        duration: Option<usize>,
+    },
+    /// Join a channel in place of the current one
     JoinChannel(String),
+    /// Join a channel alongside the ones that have already been joined
+    OpenChannel(String),
+    /// Leave one of the joined channels
+    LeaveChannel(String),
+    /// Send messages and commands to one of the joined channels
+    FocusChannel(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TwitchNotification {
-    Message(RawMessageData),
-    ClearChat(Option<String>),
+    Message(Box<RawMessageData>),
+    ClearChat {
+        channel: Option<String>,
+        user_id: Option<String>,
+    },
     DeleteMessage(String),
+    ConnectionState(ConnectionState),
+    Whisper(Whisper),
+    Poll(LivePoll),
+    StreamStatus {
+        channel: String,
+        status: StreamStatus,
+    },
+    /// `AutoMod` held a message in a channel that the user moderates
+    HeldMessage(HeldMessage),
+    /// A held message was approved, denied or expired, by its ID
+    HeldMessageResolved(String),
+}
+
+/// The state of the connection to Twitch's websocket server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    #[default]
+    Connecting,
+    Connected,
+    Reconnecting,
+    /// Reconnecting was given up on, because the token was rejected or Twitch could not be reached
+    Disconnected,
+}
+
+impl ConnectionState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Connecting => "Connecting",
+            Self::Connected => "Connected",
+            Self::Reconnecting => "Reconnecting",
+            Self::Disconnected => "Disconnected",
+        }
+    }
 }
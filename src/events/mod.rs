@@ -2,6 +2,8 @@ mod event;
 mod event_loop;
 mod key;
 
-pub use event::{Event, InternalEvent, TwitchAction, TwitchEvent, TwitchNotification};
+pub use event::{
+    ConnectionState, Event, InternalEvent, TwitchAction, TwitchEvent, TwitchNotification,
+};
 pub use event_loop::Events;
 pub use key::*;
@@ -0,0 +1,120 @@
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use chrono::{DateTime, Utc};
+
+pub type SharedAutoModQueue = Rc<RefCell<AutoModQueue>>;
+
+/// A message that `AutoMod` is holding back until a moderator approves or denies it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeldMessage {
+    pub id: String,
+    pub channel: String,
+    pub user_name: String,
+    pub text: String,
+    /// Why the message was held, such as the `AutoMod` category that caught it
+    pub reason: String,
+    pub held_at: DateTime<Utc>,
+}
+
+impl fmt::Display for HeldMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<25.25} {:<30.30} {}",
+            self.user_name, self.reason, self.text
+        )
+    }
+}
+
+/// Messages held by `AutoMod` in every joined channel, which Twitch has no way of listing
+/// so they're collected from events as they come in
+#[derive(Debug, Default)]
+pub struct AutoModQueue {
+    messages: Vec<HeldMessage>,
+}
+
+impl AutoModQueue {
+    pub fn hold(&mut self, message: HeldMessage) {
+        if self.messages.iter().any(|held| held.id == message.id) {
+            return;
+        }
+
+        self.messages.push(message);
+    }
+
+    /// Takes a message out of the queue once it has been approved, denied or has expired
+    pub fn remove(&mut self, id: &str) {
+        self.messages.retain(|held| held.id != id);
+    }
+
+    /// The messages held in a channel, the ones that have waited the longest first
+    pub fn channel_messages(&self, channel: &str) -> Vec<HeldMessage> {
+        let mut messages = self
+            .messages
+            .iter()
+            .filter(|held| held.channel.eq_ignore_ascii_case(channel))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        messages.sort_by_key(|held| held.held_at);
+
+        messages
+    }
+
+    pub fn count(&self, channel: &str) -> usize {
+        self.messages
+            .iter()
+            .filter(|held| held.channel.eq_ignore_ascii_case(channel))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn held_message(id: &str, channel: &str, held_at: DateTime<Utc>) -> HeldMessage {
+        HeldMessage {
+            id: id.to_string(),
+            channel: channel.to_string(),
+            user_name: "viewer32".to_string(),
+            text: "some text".to_string(),
+            reason: "Swearing, level 2".to_string(),
+            held_at,
+        }
+    }
+
+    #[test]
+    fn test_channel_messages_oldest_first() {
+        let mut queue = AutoModQueue::default();
+        let now = Utc::now();
+
+        queue.hold(held_message("1", "first", now));
+        queue.hold(held_message("2", "first", now - TimeDelta::minutes(1)));
+        queue.hold(held_message("3", "second", now));
+
+        let ids = queue
+            .channel_messages("FIRST")
+            .into_iter()
+            .map(|held| held.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec!["2", "1"]);
+        assert_eq!(queue.count("second"), 1);
+    }
+
+    #[test]
+    fn test_held_message_is_only_queued_once() {
+        let mut queue = AutoModQueue::default();
+        let now = Utc::now();
+
+        queue.hold(held_message("1", "first", now));
+        queue.hold(held_message("1", "first", now));
+        assert_eq!(queue.count("first"), 1);
+
+        queue.remove("1");
+        assert_eq!(queue.count("first"), 0);
+    }
+}
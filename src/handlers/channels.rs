@@ -0,0 +1,246 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use crate::{app::SharedMessages, handlers::data::MessageData};
+
+pub type SharedChannels = Rc<RefCell<Channels>>;
+
+/// Every channel that has been joined, each with its own buffer of messages
+#[derive(Debug)]
+pub struct Channels {
+    /// Channel names along with their messages, in the order that they were joined
+    channels: Vec<(String, SharedMessages)>,
+    /// The index of the channel that is currently being viewed
+    current: usize,
+    maximum_messages: usize,
+}
+
+impl Channels {
+    pub fn new(channel: String, maximum_messages: usize) -> Self {
+        let messages = Rc::new(RefCell::new(VecDeque::with_capacity(maximum_messages)));
+
+        Self {
+            channels: vec![(channel, messages)],
+            current: 0,
+            maximum_messages,
+        }
+    }
+
+    fn position(&self, channel: &str) -> Option<usize> {
+        self.channels.iter().position(|(name, _)| name == channel)
+    }
+
+    fn new_messages(&self) -> SharedMessages {
+        Rc::new(RefCell::new(VecDeque::with_capacity(self.maximum_messages)))
+    }
+
+    pub fn current_name(&self) -> &str {
+        &self.channels[self.current].0
+    }
+
+    pub fn current_messages(&self) -> SharedMessages {
+        self.channels[self.current].1.clone()
+    }
+
+    pub const fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.channels.iter().map(|(name, _)| name)
+    }
+
+    /// The messages of a specific channel, or the current one if no channel is given
+    pub fn messages(&self, channel: Option<&str>) -> Option<SharedMessages> {
+        channel.map_or_else(
+            || Some(self.current_messages()),
+            |channel| {
+                self.position(channel)
+                    .map(|index| self.channels[index].1.clone())
+            },
+        )
+    }
+
+    /// Adds a new message to a channel, forgetting its oldest messages once there are too many,
+    /// even if the channel isn't the one being viewed
+    pub fn push(&self, messages: &SharedMessages, message: MessageData) {
+        let mut messages = messages.borrow_mut();
+
+        messages.push_front(message);
+        messages.truncate(self.maximum_messages);
+    }
+
+    pub fn all_messages(&self) -> impl Iterator<Item = &SharedMessages> {
+        self.channels.iter().map(|(_, messages)| messages)
+    }
+
+    /// Joins a channel in place of the current one.
+    /// If the channel has already been joined, the current one is left and the other is switched to instead.
+    pub fn replace_current(&mut self, channel: String) {
+        if let Some(index) = self.position(&channel) {
+            if index != self.current {
+                self.channels.remove(self.current);
+                self.current = self.position(&channel).unwrap_or_default();
+            }
+
+            return;
+        }
+
+        self.channels[self.current] = (channel, self.new_messages());
+    }
+
+    /// Joins a channel alongside the others, switching to it
+    pub fn open(&mut self, channel: String) {
+        if let Some(index) = self.position(&channel) {
+            self.current = index;
+        } else {
+            let messages = self.new_messages();
+            self.channels.push((channel, messages));
+            self.current = self.channels.len() - 1;
+        }
+    }
+
+    /// Switches to a joined channel, returning if the channel could be found
+    pub fn focus(&mut self, channel: &str) -> bool {
+        self.position(channel)
+            .map(|index| self.current = index)
+            .is_some()
+    }
+
+    /// Leaves a joined channel, returning if it was left.
+    /// The last remaining channel can never be left.
+    pub fn leave(&mut self, channel: &str) -> bool {
+        if self.channels.len() == 1 {
+            return false;
+        }
+
+        let Some(index) = self.position(channel) else {
+            return false;
+        };
+
+        self.channels.remove(index);
+
+        if index < self.current || self.current == self.channels.len() {
+            self.current -= 1;
+        }
+
+        true
+    }
+
+    pub fn next_name(&self) -> &str {
+        &self.channels[(self.current + 1) % self.channels.len()].0
+    }
+
+    pub fn previous_name(&self) -> &str {
+        let index = self
+            .current
+            .checked_sub(1)
+            .unwrap_or(self.channels.len() - 1);
+
+        &self.channels[index].0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{emotes::Emotes, handlers::data::RawMessageData};
+
+    fn setup_channels() -> Channels {
+        let mut channels = Channels::new("first".to_string(), 10);
+        channels.open("second".to_string());
+        channels.open("third".to_string());
+
+        channels
+    }
+
+    #[test]
+    fn test_open_switches_to_channel() {
+        let mut channels = setup_channels();
+
+        assert_eq!(channels.current_name(), "third");
+
+        channels.open("first".to_string());
+
+        assert_eq!(channels.len(), 3);
+        assert_eq!(channels.current_name(), "first");
+    }
+
+    #[test]
+    fn test_cycle_channels() {
+        let mut channels = setup_channels();
+
+        assert_eq!(channels.next_name(), "first");
+        assert_eq!(channels.previous_name(), "second");
+
+        assert!(channels.focus("first"));
+        assert_eq!(channels.previous_name(), "third");
+    }
+
+    #[test]
+    fn test_leave_current_channel() {
+        let mut channels = setup_channels();
+
+        assert!(channels.leave("third"));
+        assert_eq!(channels.current_name(), "second");
+
+        assert!(channels.leave("first"));
+        assert_eq!(channels.current_name(), "second");
+
+        assert!(!channels.leave("second"));
+    }
+
+    #[test]
+    fn test_replace_current_with_joined_channel() {
+        let mut channels = setup_channels();
+
+        channels.replace_current("first".to_string());
+
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels.current_name(), "first");
+    }
+
+    #[test]
+    fn test_messages_are_per_channel() {
+        let channels = setup_channels();
+
+        let first = channels.messages(Some("first")).unwrap();
+        let current = channels.messages(None).unwrap();
+
+        assert!(!Rc::ptr_eq(&first, &current));
+        assert!(channels.messages(Some("unknown")).is_none());
+    }
+
+    #[test]
+    fn test_push_limits_background_channels() {
+        let channels = setup_channels();
+        let emotes = Rc::new(Emotes::new(false));
+        let first = channels.messages(Some("first")).unwrap();
+
+        for i in 0..15 {
+            let raw_message = RawMessageData::builder()
+                .author("viewer32".to_string())
+                .system(false)
+                .payload(i.to_string())
+                .emotes(BTreeMap::new())
+                .highlight(false)
+                .build();
+
+            channels.push(
+                &first,
+                MessageData::from_twitch_message(raw_message, &emotes),
+            );
+        }
+
+        let first = first.borrow();
+
+        assert_eq!(first.len(), 10);
+        assert_eq!(first.front().unwrap().payload, "14");
+        assert_eq!(first.back().unwrap().payload, "5");
+    }
+}
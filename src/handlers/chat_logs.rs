@@ -0,0 +1,404 @@
+use std::{
+    collections::{BTreeMap, HashMap, hash_map::Entry},
+    fs::{File, OpenOptions, create_dir_all, read_dir, read_to_string},
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::{
+    config::{ChatLogFormat, ChatLogsConfig, get_data_dir},
+    handlers::data::{KNOWN_CHATTERS, RawMessageData, ReplyParent},
+    twitch::{badges::ChatBadge, notices::ChatNotice},
+};
+
+const CHAT_LOGS_DIRECTORY_NAME: &str = "logs";
+const TEXT_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// A single message as it is written to a chat log
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChatLogEntry {
+    pub time_sent: DateTime<Local>,
+    pub channel: String,
+    pub author: String,
+    pub user_id: Option<String>,
+    pub system: bool,
+    pub payload: String,
+    pub message_id: Option<String>,
+    pub highlight: bool,
+    #[serde(default)]
+    pub badges: Vec<ChatBadge>,
+    pub color: Option<String>,
+    pub bits: Option<u64>,
+    pub notice: Option<ChatNotice>,
+    #[serde(default)]
+    pub mentions: Vec<String>,
+    #[serde(default)]
+    pub ping: bool,
+    pub reply: Option<ReplyParent>,
+}
+
+impl ChatLogEntry {
+    pub fn new(channel: &str, message: &RawMessageData) -> Self {
+        Self {
+            time_sent: message.time_sent,
+            channel: channel.to_string(),
+            author: message.author.clone(),
+            user_id: message.user_id.clone(),
+            system: message.system,
+            payload: message.payload.clone(),
+            message_id: message.message_id.clone(),
+            highlight: message.highlight,
+            badges: message.badges.clone(),
+            color: message.color.clone(),
+            bits: message.bits,
+            notice: message.notice,
+            mentions: message.mentions.clone(),
+            ping: message.ping,
+            reply: message.reply.as_deref().cloned(),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let time_sent = self.time_sent.format(TEXT_DATETIME_FORMAT);
+
+        let replying_to = self
+            .reply
+            .as_ref()
+            .map(|reply| format!(" (replying to @{})", reply.author))
+            .unwrap_or_default();
+
+        format!(
+            "[{time_sent}] {}{replying_to}: {}",
+            self.author, self.payload
+        )
+    }
+
+    /// Reads back a line written with [`Self::to_text`].
+    /// Only what is written to the line can be recovered, so IDs and reply contents are lost.
+    fn from_text(channel: &str, line: &str) -> Option<Self> {
+        let (time_sent, rest) = line.strip_prefix('[')?.split_once("] ")?;
+        let time_sent = NaiveDateTime::parse_from_str(time_sent, TEXT_DATETIME_FORMAT)
+            .ok()?
+            .and_local_timezone(Local)
+            .earliest()?;
+
+        let (author, payload) = rest.split_once(": ")?;
+        let author = author
+            .split_once(" (replying to @")
+            .map_or(author, |(author, _)| author);
+
+        Some(Self {
+            time_sent,
+            channel: channel.to_string(),
+            author: author.to_string(),
+            user_id: None,
+            system: KNOWN_CHATTERS.contains(&author),
+            payload: payload.to_string(),
+            message_id: None,
+            highlight: false,
+            badges: vec![],
+            color: None,
+            bits: None,
+            notice: None,
+            mentions: vec![],
+            ping: false,
+            reply: None,
+        })
+    }
+
+    pub fn to_line(&self, format: ChatLogFormat) -> Result<String> {
+        Ok(match format {
+            ChatLogFormat::Text => self.to_text(),
+            ChatLogFormat::Jsonl => serde_json::to_string(self)?,
+        })
+    }
+
+    pub fn from_line(channel: &str, line: &str, format: ChatLogFormat) -> Option<Self> {
+        match format {
+            ChatLogFormat::Text => Self::from_text(channel, line),
+            ChatLogFormat::Jsonl => serde_json::from_str(line).ok(),
+        }
+    }
+}
+
+impl From<ChatLogEntry> for RawMessageData {
+    fn from(entry: ChatLogEntry) -> Self {
+        let mut message = Self::builder()
+            .author(entry.author)
+            .maybe_user_id(entry.user_id)
+            .system(entry.system)
+            .payload(entry.payload)
+            .emotes(BTreeMap::new())
+            .maybe_message_id(entry.message_id)
+            .highlight(entry.highlight)
+            .badges(entry.badges)
+            .maybe_color(entry.color)
+            .maybe_bits(entry.bits)
+            .maybe_notice(entry.notice)
+            .mentions(entry.mentions)
+            .ping(entry.ping)
+            .maybe_reply(entry.reply.map(Box::new))
+            .channel(entry.channel)
+            .build();
+
+        message.time_sent = entry.time_sent;
+
+        message
+    }
+}
+
+/// A log file that is kept open while messages are being written to it
+#[derive(Debug)]
+struct OpenChatLog {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl OpenChatLog {
+    fn open(path: PathBuf) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+/// Writes messages to disk, with a file for every channel and day
+#[derive(Debug)]
+pub struct ChatLogger {
+    format: ChatLogFormat,
+    directory: PathBuf,
+    /// The file of the current day for every channel that has been logged to
+    files: HashMap<String, OpenChatLog>,
+}
+
+impl ChatLogger {
+    pub fn new(config: &ChatLogsConfig) -> Self {
+        Self {
+            format: config.format,
+            directory: get_data_dir().join(CHAT_LOGS_DIRECTORY_NAME),
+            files: HashMap::new(),
+        }
+    }
+
+    /// The file that messages sent in a channel on a certain day are written to
+    pub fn file_path(&self, channel: &str, time_sent: &DateTime<Local>) -> PathBuf {
+        self.directory.join(channel).join(format!(
+            "{}.{}",
+            time_sent.format("%Y-%m-%d"),
+            self.format.extension()
+        ))
+    }
+
+    /// Buffers a message to be written to its channel's file of the day.
+    /// Files are only opened again once the day changes.
+    pub fn log(&mut self, channel: &str, message: &RawMessageData) -> Result<()> {
+        let entry = ChatLogEntry::new(channel, message);
+        let path = self.file_path(channel, &entry.time_sent);
+
+        let file = match self.files.entry(channel.to_string()) {
+            Entry::Occupied(file) if file.get().path == path => file.into_mut(),
+            // The file of the previous day is flushed as it is dropped
+            file => file.insert_entry(OpenChatLog::open(path)?).into_mut(),
+        };
+
+        writeln!(file.writer, "{}", entry.to_line(self.format)?)?;
+
+        Ok(())
+    }
+
+    /// Writes every buffered message to disk
+    pub fn flush(&mut self) -> Result<()> {
+        for file in self.files.values_mut() {
+            file.writer.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// The most recent messages that were logged for a channel, from newest to oldest
+    pub fn history(&mut self, channel: &str, count: usize) -> Vec<ChatLogEntry> {
+        // Messages that are still buffered would otherwise be missing
+        if let Some(file) = self.files.get_mut(channel) {
+            if let Err(err) = file.writer.flush() {
+                warn!("Failed to write chat log of {channel}: {err}");
+            }
+        }
+
+        let Ok(directory) = read_dir(self.directory.join(channel)) else {
+            return vec![];
+        };
+
+        let mut files = directory
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == self.format.extension())
+            })
+            .collect::<Vec<PathBuf>>();
+
+        // Files are named by date, so the newest ones sort last
+        files.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut entries = vec![];
+
+        for file in files {
+            if entries.len() >= count {
+                break;
+            }
+
+            let Ok(contents) = read_to_string(&file) else {
+                continue;
+            };
+
+            let remaining = count - entries.len();
+
+            entries.extend(
+                contents
+                    .lines()
+                    .rev()
+                    .filter_map(|line| ChatLogEntry::from_line(channel, line, self.format))
+                    .take(remaining),
+            );
+        }
+
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+
+    fn setup_entry() -> ChatLogEntry {
+        let mut message = RawMessageData::builder()
+            .author("foo".to_string())
+            .user_id("1234".to_string())
+            .system(false)
+            .payload("hello world".to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .build();
+        message.time_sent = Local.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap();
+
+        ChatLogEntry::new("bar", &message)
+    }
+
+    #[test]
+    fn test_text_line() {
+        let entry = setup_entry();
+
+        assert_eq!(
+            entry.to_line(ChatLogFormat::Text).unwrap(),
+            "[2025-01-02 03:04:05] foo: hello world"
+        );
+    }
+
+    #[test]
+    fn test_jsonl_line_round_trip() {
+        let entry = setup_entry();
+
+        let line = entry.to_line(ChatLogFormat::Jsonl).unwrap();
+
+        assert!(!line.contains('\n'));
+        assert_eq!(serde_json::from_str::<ChatLogEntry>(&line).unwrap(), entry);
+    }
+
+    #[test]
+    fn test_text_line_round_trip() {
+        let entry = setup_entry();
+
+        let line = entry.to_line(ChatLogFormat::Text).unwrap();
+        let parsed = ChatLogEntry::from_line("bar", &line, ChatLogFormat::Text).unwrap();
+
+        assert_eq!(parsed.time_sent, entry.time_sent);
+        assert_eq!(parsed.author, entry.author);
+        assert_eq!(parsed.payload, entry.payload);
+    }
+
+    #[test]
+    fn test_history_spans_days() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut logger = ChatLogger {
+            format: ChatLogFormat::Jsonl,
+            directory: directory.path().to_path_buf(),
+            files: HashMap::new(),
+        };
+
+        let mut message = RawMessageData::from(setup_entry());
+
+        for i in 0..3 {
+            message.payload = format!("message {i}");
+            message.time_sent += Duration::hours(12);
+            logger.log("bar", &message).unwrap();
+        }
+
+        // The last two messages are logged on the same day, after the first one
+        let history = logger.history("bar", 3);
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].payload, "message 2");
+        assert_eq!(history[2].payload, "message 0");
+        assert_eq!(logger.history("bar", 1).len(), 1);
+        assert!(logger.history("unknown", 2).is_empty());
+    }
+
+    #[test]
+    fn test_file_path_per_channel_and_day() {
+        let logger = ChatLogger {
+            format: ChatLogFormat::Jsonl,
+            directory: PathBuf::from("logs"),
+            files: HashMap::new(),
+        };
+        let entry = setup_entry();
+
+        assert_eq!(
+            logger.file_path(&entry.channel, &entry.time_sent),
+            PathBuf::from("logs").join("bar").join("2025-01-02.jsonl")
+        );
+    }
+
+    #[test]
+    fn test_log_reopens_file_on_new_day() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut logger = ChatLogger {
+            format: ChatLogFormat::Text,
+            directory: directory.path().to_path_buf(),
+            files: HashMap::new(),
+        };
+
+        let mut message = RawMessageData::from(setup_entry());
+        let first_day = message.time_sent;
+        let second_day = first_day + Duration::days(1);
+
+        for time_sent in [first_day, first_day, second_day] {
+            message.time_sent = time_sent;
+            logger.log("bar", &message).unwrap();
+        }
+
+        logger.flush().unwrap();
+
+        let lines = |time_sent| {
+            read_to_string(logger.file_path("bar", &time_sent))
+                .unwrap()
+                .lines()
+                .count()
+        };
+
+        assert_eq!(lines(first_day), 2);
+        assert_eq!(lines(second_day), 1);
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::{HashSet, VecDeque};
+
+/// How many chatters are remembered before the ones seen the longest ago are forgotten
+const MAXIMUM_SEEN_CHATTERS: usize = 10_000;
+
+/// The chatters seen in each channel since twt started, which is only kept for the session.
+/// Once there are too many, the ones seen the longest ago are forgotten,
+/// so their next message counts as their first one again.
+#[derive(Debug, Default)]
+pub struct SeenChatters {
+    seen: HashSet<(String, String)>,
+    /// The same chatters as [`Self::seen`], in the order that they were first seen in
+    order: VecDeque<(String, String)>,
+}
+
+impl SeenChatters {
+    /// Remembers a chatter, returning if they had not been seen in the channel before
+    pub fn insert(&mut self, channel: &str, name: &str) -> bool {
+        let chatter = (channel.to_lowercase(), name.to_lowercase());
+
+        if !self.seen.insert(chatter.clone()) {
+            return false;
+        }
+
+        self.order.push_back(chatter);
+
+        if self.order.len() > MAXIMUM_SEEN_CHATTERS {
+            if let Some(forgotten) = self.order.pop_front() {
+                self.seen.remove(&forgotten);
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chatter_is_seen_once_per_channel() {
+        let mut chatters = SeenChatters::default();
+
+        assert!(chatters.insert("first", "Viewer32"));
+        assert!(!chatters.insert("FIRST", "viewer32"));
+        assert!(chatters.insert("second", "viewer32"));
+    }
+
+    #[test]
+    fn test_oldest_chatter_is_forgotten() {
+        let mut chatters = SeenChatters::default();
+
+        for i in 0..=MAXIMUM_SEEN_CHATTERS {
+            chatters.insert("channel", &format!("viewer{i}"));
+        }
+
+        assert!(chatters.insert("channel", "viewer0"));
+        assert!(!chatters.insert("channel", &format!("viewer{MAXIMUM_SEEN_CHATTERS}")));
+    }
+}
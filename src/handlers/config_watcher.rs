@@ -0,0 +1,53 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use color_eyre::Result;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    config::{SharedCoreConfig, get_config_dir},
+    events::{Event, InternalEvent},
+    handlers::filters::Filters,
+};
+
+/// Asks for the config to be reloaded whenever it, or any of the filter files, change on disk
+pub struct ConfigWatcher {
+    /// Stops watching once dropped
+    _watcher: RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+    pub fn new(config: &SharedCoreConfig, event_tx: Sender<Event>) -> Result<Self> {
+        let mut files = Filters::files(config);
+        files.push(get_config_dir().join("config.toml"));
+
+        // Editors often replace files rather than writing to them,
+        // so the directories are watched instead of the files themselves
+        let directories = files
+            .iter()
+            .filter_map(|file| file.parent().map(PathBuf::from))
+            .collect::<HashSet<_>>();
+
+        let files = files.into_iter().collect::<HashSet<_>>();
+
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let Ok(event) = res else {
+                    return;
+                };
+
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                    && event.paths.iter().any(|path| files.contains(path))
+                {
+                    // Events arrive on the watcher's own thread, outside of the async runtime
+                    let _ = event_tx.blocking_send(Event::Internal(InternalEvent::ReloadConfig));
+                }
+            })?;
+
+        for directory in directories {
+            watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+        }
+
+        Ok(Self { _watcher: watcher })
+    }
+}
@@ -4,6 +4,7 @@ use bon::bon;
 use chrono::{DateTime, offset::Local};
 use fuzzy_matcher::FuzzyMatcher;
 use memchr::{memchr_iter, memmem};
+use serde::{Deserialize, Serialize};
 use tracing::{error, warn};
 use tui::{
     style::{Color, Color::Rgb, Modifier, Style},
@@ -15,9 +16,11 @@ use crate::{
     config::{FrontendConfig, Palette, Theme},
     emotes::{DownloadedEmotes, EmoteData, SharedEmotes, display_emote, load_emote, overlay_emote},
     events::{Event, TwitchEvent, TwitchNotification},
+    handlers::highlights::Highlights,
+    twitch::{badges::ChatBadge, cheers::bits_tier_color, notices::ChatNotice},
     ui::statics::NAME_MAX_CHARACTERS,
     utils::{
-        colors::{hsl_to_rgb, u32_to_color},
+        colors::{hex_to_color, hsl_to_rgb, readable_color, u32_to_color},
         emotes::{
             PRIVATE_USE_UNICODE, UnicodePlaceholder, ZERO_WIDTH_SPACE, ZERO_WIDTH_SPACE_STR,
             get_emote_offset,
@@ -25,17 +28,30 @@ use crate::{
         search::FUZZY_FINDER,
         styles::{
             DATETIME_DARK_STYLE, DATETIME_LIGHT_STYLE, HIGHLIGHT_NAME_DARK_STYLE,
-            HIGHLIGHT_NAME_LIGHT_STYLE, SEARCH_STYLE, SYSTEM_CHAT_STYLE,
+            HIGHLIGHT_NAME_LIGHT_STYLE, HISTORY_STYLE, PING_DARK_STYLE, PING_LIGHT_STYLE,
+            REPLY_STYLE, SEARCH_STYLE, SYSTEM_CHAT_STYLE, bits_style,
         },
         text::split_cow_in_place,
     },
 };
 
+/// What the background of the terminal is assumed to be for each theme
+const DARK_THEME_BACKGROUND: [u8; 3] = [0, 0, 0];
+const LIGHT_THEME_BACKGROUND: [u8; 3] = [255, 255, 255];
+
 enum Word {
     Emote(Vec<EmoteData>),
     Text(String),
 }
 
+/// The message that another message is replying to
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReplyParent {
+    pub message_id: String,
+    pub author: String,
+    pub payload: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MessageData {
     pub time_sent: DateTime<Local>,
@@ -46,7 +62,28 @@ pub struct MessageData {
     pub emotes: Vec<(Color, Color)>,
     pub message_id: Option<String>,
     pub highlight: bool,
-    pub badges: Option<String>,
+    pub badges: Vec<ChatBadge>,
+    /// What is shown for the badges before the author's name, with placeholders for the ones that have images
+    pub badge_prefix: String,
+    /// The images of the badges in [`Self::badge_prefix`], in the same format as [`Self::emotes`]
+    pub badge_emotes: Vec<(Color, Color)>,
+    /// The color that the author picked for their name on Twitch
+    pub color: Option<Color>,
+    /// How many bits were cheered with the message
+    pub bits: Option<u64>,
+    /// What the message is about, if it came from a chat notification
+    pub notice: Option<ChatNotice>,
+    /// How each mention of another chatter was written in the payload, such as `@viewer32`
+    pub mentions: Vec<String>,
+    /// If the message mentions the current user
+    pub ping: bool,
+    /// If the message has nothing but emotes in it
+    pub emote_only: bool,
+    /// If the author had not been seen chatting in the channel since twt started, before this message
+    pub first_seen: bool,
+    pub reply: Option<Box<ReplyParent>>,
+    /// If the message was loaded from the chat logs rather than received live
+    pub history: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -59,7 +96,23 @@ pub struct RawMessageData {
     pub emotes: DownloadedEmotes,
     pub message_id: Option<String>,
     pub highlight: bool,
-    pub badges: Option<String>,
+    pub badges: Vec<ChatBadge>,
+    /// The color of the author's name as a hex string, such as `#00FF7F`
+    pub color: Option<String>,
+    /// How many bits were cheered with the message
+    pub bits: Option<u64>,
+    /// What the message is about, if it came from a chat notification
+    pub notice: Option<ChatNotice>,
+    /// How each mention of another chatter was written in the payload, such as `@viewer32`
+    pub mentions: Vec<String>,
+    /// If the message mentions the current user
+    pub ping: bool,
+    /// If the message has nothing but emotes in it
+    pub emote_only: bool,
+    pub reply: Option<Box<ReplyParent>>,
+    /// The channel that the message was sent in, or nothing for messages from twt itself,
+    /// which are shown in the current channel
+    pub channel: Option<String>,
 }
 
 #[bon]
@@ -73,7 +126,15 @@ impl RawMessageData {
         emotes: DownloadedEmotes,
         message_id: Option<String>,
         highlight: bool,
-        badges: Option<String>,
+        #[builder(default)] badges: Vec<ChatBadge>,
+        color: Option<String>,
+        bits: Option<u64>,
+        notice: Option<ChatNotice>,
+        #[builder(default)] mentions: Vec<String>,
+        #[builder(default)] ping: bool,
+        #[builder(default)] emote_only: bool,
+        reply: Option<Box<ReplyParent>>,
+        channel: Option<String>,
     ) -> Self {
         Self {
             time_sent: Local::now(),
@@ -85,6 +146,14 @@ impl RawMessageData {
             message_id,
             highlight,
             badges,
+            color,
+            bits,
+            notice,
+            mentions,
+            ping,
+            emote_only,
+            reply,
+            channel,
         }
     }
 }
@@ -94,6 +163,8 @@ type Highlight<'a> = (&'a [usize], Style);
 impl MessageData {
     /// Create a message and parse its emotes using global emotes, and twitch emotes provided through [`RawMessageData`]
     pub fn from_twitch_message(msg: RawMessageData, emotes: &SharedEmotes) -> Self {
+        let (badge_prefix, badge_emotes) = Self::parse_badges(&msg.badges, emotes);
+
         let (payload, emotes) = Self::parse_emotes(
             msg.payload,
             emotes,
@@ -111,10 +182,32 @@ impl MessageData {
             message_id: msg.message_id,
             highlight: msg.highlight,
             badges: msg.badges,
+            badge_prefix,
+            badge_emotes,
+            color: msg.color.as_deref().and_then(hex_to_color),
+            bits: msg.bits,
+            notice: msg.notice,
+            mentions: msg.mentions,
+            ping: msg.ping,
+            emote_only: msg.emote_only,
+            first_seen: false,
+            reply: msg.reply,
+            history: false,
         }
     }
 
+    /// The words of the payload without the placeholders of its emotes
+    pub fn text(&self) -> String {
+        self.payload
+            .split([' ', ZERO_WIDTH_SPACE])
+            .filter(|word| !word.is_empty() && !word.starts_with(PRIVATE_USE_UNICODE))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     pub fn reparse_emotes(&mut self, emotes: &SharedEmotes) {
+        (self.badge_prefix, self.badge_emotes) = Self::parse_badges(&self.badges, emotes);
+
         // Small hack to avoid cloning `self.payload`
         let mut payload = String::new();
         swap(&mut payload, &mut self.payload);
@@ -130,6 +223,68 @@ impl MessageData {
         self.emotes.extend(emotes);
     }
 
+    /// Builds what is shown for the author's badges, using their images if they have been downloaded,
+    /// and falling back to text for the rest.
+    /// Images are placed between zero-width spaces, so that they are found the same way as the emotes in the payload.
+    fn parse_badges(badges: &[ChatBadge], emotes: &SharedEmotes) -> (String, Vec<(Color, Color)>) {
+        let cell_size = if emotes.enabled {
+            emotes.cell_size.get().copied()
+        } else {
+            None
+        };
+
+        let mut prefix = String::new();
+        let mut badge_emotes = vec![];
+
+        for badge in badges {
+            let loaded_badge = cell_size.and_then(|cell_size| {
+                let (filename, _) = {
+                    let badge_images = emotes.badges.borrow();
+
+                    badge
+                        .keys()
+                        .iter()
+                        .find_map(|key| badge_images.get(key))?
+                        .clone()
+                };
+
+                let loaded_badge = load_emote(
+                    &filename,
+                    &filename,
+                    false,
+                    &mut emotes.info.borrow_mut(),
+                    cell_size,
+                )
+                .map_err(|e| warn!("Unable to load badge {} ({filename}): {e}", badge.key()))
+                .ok()?;
+
+                let cols = (loaded_badge.width as f32 / cell_size.0).ceil() as u16;
+
+                display_emote(loaded_badge.hash, loaded_badge.n, cols)
+                    .map_err(|e| warn!("Unable to display badge: {e}"))
+                    .ok()?;
+
+                Some((loaded_badge, cols))
+            });
+
+            match loaded_badge {
+                Some((loaded_badge, cols)) => {
+                    badge_emotes.push((
+                        u32_to_color(loaded_badge.hash),
+                        u32_to_color(loaded_badge.n),
+                    ));
+
+                    prefix.push(ZERO_WIDTH_SPACE);
+                    prefix.extend(UnicodePlaceholder::new(cols as usize).iter());
+                    prefix.push(ZERO_WIDTH_SPACE);
+                }
+                None => prefix.extend(badge.text()),
+            }
+        }
+
+        (prefix, badge_emotes)
+    }
+
     fn is_emote<'a>(
         word: &str,
         set1: &'a DownloadedEmotes,
@@ -262,6 +417,22 @@ impl MessageData {
         (payload, emotes)
     }
 
+    /// The color of the author's name, being the one they picked on Twitch if that is enabled,
+    /// made readable against the background of the theme
+    fn username_color(&self, frontend_config: &FrontendConfig) -> Color {
+        match self.color {
+            Some(color) if frontend_config.twitch_colors => {
+                let background = match frontend_config.theme {
+                    Theme::Dark => DARK_THEME_BACKGROUND,
+                    _ => LIGHT_THEME_BACKGROUND,
+                };
+
+                readable_color(color, background)
+            }
+            _ => self.hash_username(&frontend_config.palette),
+        }
+    }
+
     fn hash_username(&self, palette: &Palette) -> Color {
         let hash = f64::from(
             self.author
@@ -328,6 +499,7 @@ impl MessageData {
         default_style: Style,
         (search_highlight, search_theme): Highlight,
         (username_highlight, username_theme): Highlight,
+        rule_highlight: &[(usize, Style)],
     ) -> Vec<Span<'s>> {
         const HAS_NO_HIGHLIGHTS: fn(&[usize], &usize, &usize) -> bool =
             |highlight: &[usize], start: &usize, end: &usize| {
@@ -341,6 +513,9 @@ impl MessageData {
 
         if HAS_NO_HIGHLIGHTS(search_highlight, &offset, start_index)
             && HAS_NO_HIGHLIGHTS(username_highlight, &offset, start_index)
+            && (rule_highlight.is_empty()
+                || offset > rule_highlight.last().unwrap().0
+                || *start_index < rule_highlight.first().unwrap().0)
         {
             return vec![Span::styled(line, default_style)];
         }
@@ -352,6 +527,10 @@ impl MessageData {
                     Span::styled(c.to_string(), search_theme)
                 } else if username_highlight.binary_search(&i).is_ok() {
                     Span::styled(c.to_string(), username_theme)
+                } else if let Ok(rule) =
+                    rule_highlight.binary_search_by_key(&i, |(index, _)| *index)
+                {
+                    Span::styled(c.to_string(), default_style.patch(rule_highlight[rule].1))
                 } else {
                     Span::styled(c.to_string(), default_style)
                 }
@@ -365,6 +544,7 @@ impl MessageData {
         default_style: Style,
         search_highlight: Highlight,
         username_highlight: Highlight,
+        rule_highlight: &[(usize, Style)],
         emotes: &mut &[(Color, Color)],
     ) -> Vec<Span<'s>> {
         static EMOTE_FINDER: LazyLock<memmem::Finder> =
@@ -381,6 +561,7 @@ impl MessageData {
                 default_style,
                 search_highlight,
                 username_highlight,
+                rule_highlight,
             )
         } else {
             let mut spans: Vec<Span<'s>> = vec![];
@@ -403,6 +584,7 @@ impl MessageData {
                         default_style,
                         search_highlight,
                         username_highlight,
+                        rule_highlight,
                     ));
                 }
                 *start_index += ZERO_WIDTH_SPACE_STR.len();
@@ -419,15 +601,24 @@ impl MessageData {
         width: usize,
         search_highlight: Option<&str>,
         username_highlight: Option<&str>,
+        highlights: &Highlights,
     ) -> Vec<Line<'_>> {
         // Theme styles
-        let fg = self.hash_username(&frontend_config.palette);
+        let fg = self.username_color(frontend_config);
 
         let message_theme = if self.highlight {
             Style::default().fg(fg).add_modifier(Modifier::ITALIC)
         } else {
             Style::default()
         };
+        // Cheers and notices stand out from the rest of the chat
+        let message_theme = match self.notice {
+            Some(notice) => message_theme.patch(notice.style(fg)),
+            None if self.bits.is_some() => message_theme.add_modifier(Modifier::BOLD),
+            None => message_theme,
+        };
+        // Highlight rules for the author apply to the whole message
+        let message_theme = message_theme.patch(highlights.message_style(&self.author));
         let username_theme = match frontend_config.theme {
             Theme::Dark => *HIGHLIGHT_NAME_DARK_STYLE,
             _ => *HIGHLIGHT_NAME_LIGHT_STYLE,
@@ -461,6 +652,17 @@ impl MessageData {
             }
         }
 
+        // Mentions of any chatter are highlighted the same way as the current user's name
+        if !self.mentions.is_empty() {
+            username_highlight.extend(self.mentions.iter().flat_map(|mention| {
+                self.payload
+                    .match_indices(mention.as_str())
+                    .flat_map(|(index, _)| index..(index + mention.len()))
+            }));
+            username_highlight.sort_unstable();
+            username_highlight.dedup();
+        }
+
         // All indices to highlight like a search result
         let search_highlight = search_highlight
             .and_then(|query| {
@@ -474,6 +676,9 @@ impl MessageData {
             })
             .unwrap_or_default();
 
+        // All indices to highlight with the style of a highlight rule
+        let rule_highlight = highlights.payload_highlights(&self.author, &self.payload);
+
         let search = (&search_highlight as &[usize], *SEARCH_STYLE);
         let username = (&username_highlight as &[usize], username_theme);
 
@@ -488,17 +693,24 @@ impl MessageData {
             None
         };
 
-        let author = self.badges.as_ref().map_or_else(
-            || self.author.clone(),
-            |badges| badges.clone() + &self.author,
-        );
+        let author = if frontend_config.badges {
+            format!("{}{}", self.badge_prefix, self.author)
+        } else {
+            self.author.clone()
+        };
+
+        // The amount of bits cheered, shown after the author in the color of its tier
+        let bits = self
+            .bits
+            .map(|bits| (format!(" {bits} bits"), bits_tier_color(bits)));
+        let author_width = author.width() + bits.as_ref().map_or(0, |(label, _)| label.len());
 
         // Add 1 for the space after the timestamp
         let time_sent_len = time_sent.as_ref().map_or(0, |t| t.len() + 1);
 
         let prefix_len = if frontend_config.username_shown {
             // Add 2 for the ": "
-            time_sent_len + author.len() + 2
+            time_sent_len + author_width + 2
         } else {
             time_sent_len
         };
@@ -520,7 +732,7 @@ impl MessageData {
 
         let username_alignment = if frontend_config.username_shown {
             if frontend_config.right_align_usernames {
-                NAME_MAX_CHARACTERS.saturating_sub(author.width()) + 1
+                NAME_MAX_CHARACTERS.saturating_sub(author_width) + 1
             } else {
                 1
             }
@@ -538,7 +750,25 @@ impl MessageData {
         }
 
         if frontend_config.username_shown {
-            first_row.extend(vec![Span::styled(author, author_theme), Span::raw(": ")]);
+            if self.badge_emotes.is_empty() || !frontend_config.badges {
+                first_row.push(Span::styled(author, author_theme));
+            } else {
+                first_row.extend(Self::build_line(
+                    Cow::Owned(author),
+                    &mut 0,
+                    author_theme,
+                    (&[], author_theme),
+                    (&[], author_theme),
+                    &[],
+                    &mut &self.badge_emotes[..],
+                ));
+            }
+
+            if let Some((label, color)) = bits {
+                first_row.push(Span::styled(label, bits_style(color)));
+            }
+
+            first_row.push(Span::raw(": "));
         }
 
         let mut next_index = 0;
@@ -559,12 +789,38 @@ impl MessageData {
             message_theme,
             search,
             username,
+            &rule_highlight,
             &mut emotes,
         ));
 
         let mut space_idx = space_iter.next();
 
-        let mut rows = vec![Line::from(first_row)];
+        let mut rows = vec![];
+
+        if let Some(ChatNotice::Announcement(_)) = self.notice {
+            rows.push(Line::from(Span::styled("┌ Announcement", message_theme)));
+        }
+
+        // Quote the message being replied to above the reply itself
+        if let Some(reply) = &self.reply {
+            let quote = format!("┌ @{}: {}", reply.author, reply.payload);
+            let mut quote_lines = textwrap::wrap(&quote, wrap_limit).into_iter();
+
+            if let Some(first_quote_line) = quote_lines.next() {
+                let ellipsis = if quote_lines.next().is_some() {
+                    "…"
+                } else {
+                    ""
+                };
+
+                rows.push(Line::from(Span::styled(
+                    format!("{first_quote_line}{ellipsis}"),
+                    *REPLY_STYLE,
+                )));
+            }
+        }
+
+        rows.push(Line::from(first_row));
 
         rows.extend(lines.map(|line| {
             while space_idx.is_some_and(|x| x < next_index) {
@@ -582,10 +838,30 @@ impl MessageData {
                 message_theme,
                 search,
                 username,
+                &rule_highlight,
                 &mut emotes,
             ))
         }));
 
+        if self.ping {
+            let ping_style = match frontend_config.theme {
+                Theme::Dark => *PING_DARK_STYLE,
+                _ => *PING_LIGHT_STYLE,
+            };
+
+            rows = rows
+                .into_iter()
+                .map(|row| row.patch_style(ping_style))
+                .collect();
+        }
+
+        if self.history {
+            return rows
+                .into_iter()
+                .map(|row| row.patch_style(*HISTORY_STYLE))
+                .collect();
+        }
+
         rows
     }
 }
@@ -596,54 +872,63 @@ pub struct DataBuilder;
 pub static KNOWN_CHATTERS: &[&str] = &["System", "Twitch"];
 
 impl DataBuilder {
-    // User messages that come from either twitch or the terminal
-    pub fn user(
-        user: String,
-        user_id: Option<String>,
-        payload: String,
-        emotes: DownloadedEmotes,
-        message_id: Option<String>,
-        highlight: bool,
-        badges: Option<String>,
-    ) -> TwitchNotification {
+    /// Notification messages from the terminal
+    pub fn system(payload: String) -> TwitchNotification {
         let message = RawMessageData::builder()
-            .author(user)
-            .maybe_user_id(user_id)
-            .system(false)
+            .author("System".to_string())
+            .system(true)
             .payload(payload)
-            .emotes(emotes)
-            .maybe_message_id(message_id)
-            .highlight(highlight)
-            .maybe_badges(badges)
+            .emotes(DownloadedEmotes::default())
+            .highlight(false)
             .build();
 
-        TwitchNotification::Message(message)
+        TwitchNotification::Message(Box::new(message))
     }
 
-    /// Notification messages from the terminal
-    pub fn system(payload: String) -> TwitchNotification {
+    /// Notification messages from Twitch
+    pub fn twitch(payload: String) -> TwitchNotification {
         let message = RawMessageData::builder()
-            .author("System".to_string())
+            .author("Twitch".to_string())
             .system(true)
             .payload(payload)
             .emotes(DownloadedEmotes::default())
             .highlight(false)
             .build();
 
-        TwitchNotification::Message(message)
+        TwitchNotification::Message(Box::new(message))
     }
 
-    /// Notification messages from Twitch
-    pub fn twitch(payload: String) -> TwitchNotification {
+    /// Notification messages from Twitch about a specific channel
+    pub fn channel_twitch(channel: Option<String>, payload: String) -> TwitchNotification {
+        let message = RawMessageData::builder()
+            .author("Twitch".to_string())
+            .system(true)
+            .payload(payload)
+            .emotes(DownloadedEmotes::default())
+            .highlight(false)
+            .maybe_channel(channel)
+            .build();
+
+        TwitchNotification::Message(Box::new(message))
+    }
+
+    /// Chat notifications from Twitch, such as subscriptions and raids
+    pub fn notice(
+        channel: Option<String>,
+        notice: ChatNotice,
+        payload: String,
+    ) -> TwitchNotification {
         let message = RawMessageData::builder()
             .author("Twitch".to_string())
             .system(true)
             .payload(payload)
             .emotes(DownloadedEmotes::default())
             .highlight(false)
+            .notice(notice)
+            .maybe_channel(channel)
             .build();
 
-        TwitchNotification::Message(message)
+        TwitchNotification::Message(Box::new(message))
     }
 }
 
@@ -659,7 +944,7 @@ mod tests {
     use std::{collections::BTreeMap, rc::Rc};
 
     use super::*;
-    use crate::emotes::Emotes;
+    use crate::{config::HighlightConfig, emotes::Emotes, twitch::notices::AnnouncementColor};
 
     #[test]
     fn test_username_hash() {
@@ -673,13 +958,78 @@ mod tests {
                 emotes: vec![],
                 message_id: None,
                 highlight: false,
-                badges: None,
+                badges: vec![],
+                badge_prefix: String::new(),
+                badge_emotes: vec![],
+                color: None,
+                bits: None,
+                notice: None,
+                mentions: vec![],
+                ping: false,
+                emote_only: false,
+                first_seen: false,
+                reply: None,
+                history: false,
             }
             .hash_username(&Palette::Pastel),
             Rgb(159, 223, 221)
         );
     }
 
+    #[test]
+    fn test_username_twitch_color() {
+        let raw_message = RawMessageData::builder()
+            .author("human".to_string())
+            .system(false)
+            .payload("beep boop".to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .color("#00FF7F".to_string())
+            .build();
+
+        let emotes = Rc::new(Emotes::new(false));
+        let data = MessageData::from_twitch_message(raw_message, &emotes);
+
+        let mut frontend_config = FrontendConfig::default();
+
+        assert_eq!(data.username_color(&frontend_config), Rgb(159, 223, 221));
+
+        frontend_config.twitch_colors = true;
+
+        assert_eq!(data.username_color(&frontend_config), Rgb(0, 255, 127));
+
+        // Too bright to be read on a white background
+        frontend_config.theme = Theme::Light;
+
+        assert_ne!(data.username_color(&frontend_config), Rgb(0, 255, 127));
+    }
+
+    #[test]
+    fn test_username_color_falls_back_to_palette() {
+        let raw_message = RawMessageData::builder()
+            .author("human".to_string())
+            .system(false)
+            .payload("beep boop".to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .color("not a color".to_string())
+            .build();
+
+        let emotes = Rc::new(Emotes::new(false));
+        let data = MessageData::from_twitch_message(raw_message, &emotes);
+
+        let frontend_config = FrontendConfig {
+            twitch_colors: true,
+            ..FrontendConfig::default()
+        };
+
+        assert_eq!(data.color, None);
+        assert_eq!(
+            data.username_color(&frontend_config),
+            data.hash_username(&Palette::Pastel)
+        );
+    }
+
     const EMOTES_ID_PID: [(Color, Color); 3] = [
         (Color::Red, Color::Green),
         (Color::Black, Color::Cyan),
@@ -733,6 +1083,7 @@ mod tests {
             Style::default(),
             (search_highlight, STYLES[0]),
             (username_highlight, STYLES[1]),
+            &[],
         );
 
         assert_eq!(start_index, line_len + 1);
@@ -772,6 +1123,7 @@ mod tests {
                 Style::default(),
                 search_highlight,
                 username_highlight,
+                &[],
                 &mut emotes,
             );
 
@@ -788,6 +1140,7 @@ mod tests {
                 Style::default(),
                 search_highlight,
                 username_highlight,
+                &[],
                 &mut emotes,
             );
 
@@ -952,7 +1305,6 @@ mod tests {
             .emotes(BTreeMap::new())
             .maybe_message_id(None)
             .highlight(false)
-            .maybe_badges(None)
             .build();
 
         let emotes = Rc::new(Emotes::new(false));
@@ -963,7 +1315,13 @@ mod tests {
             ..FrontendConfig::default()
         };
 
-        let lines = data.to_vec(&frontendconfig, 13, Some("bar"), None);
+        let lines = data.to_vec(
+            &frontendconfig,
+            13,
+            Some("bar"),
+            None,
+            &Highlights::default(),
+        );
 
         assert_eq!(
             lines,
@@ -994,4 +1352,245 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn build_vec_with_text_badges() {
+        let raw_message = RawMessageData::builder()
+            .author("foo".to_string())
+            .system(false)
+            .payload("hi".to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .badges(vec![
+                ChatBadge {
+                    set_id: "broadcaster".to_string(),
+                    id: "1".to_string(),
+                    info: String::new(),
+                    channel_id: None,
+                },
+                ChatBadge {
+                    set_id: "unknown".to_string(),
+                    id: "1".to_string(),
+                    info: String::new(),
+                    channel_id: None,
+                },
+            ])
+            .build();
+
+        let emotes = Rc::new(Emotes::new(false));
+        let data = MessageData::from_twitch_message(raw_message, &emotes);
+
+        let mut frontendconfig = FrontendConfig {
+            show_datetimes: false,
+            badges: true,
+            ..FrontendConfig::default()
+        };
+
+        let author_style = Style::default().fg(data.hash_username(&Palette::Pastel));
+
+        assert_eq!(
+            data.to_vec(&frontendconfig, 30, None, None, &Highlights::default())[0].spans[0],
+            Span::styled("\u{1F3A5}foo", author_style)
+        );
+
+        frontendconfig.badges = false;
+
+        assert_eq!(
+            data.to_vec(&frontendconfig, 30, None, None, &Highlights::default())[0].spans[0],
+            Span::styled("foo", author_style)
+        );
+    }
+
+    #[test]
+    fn build_vec_with_bits() {
+        let raw_message = RawMessageData::builder()
+            .author("foo".to_string())
+            .system(false)
+            .payload("Cheer100".to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .bits(100)
+            .build();
+
+        let emotes = Rc::new(Emotes::new(false));
+        let data = MessageData::from_twitch_message(raw_message, &emotes);
+
+        let frontendconfig = FrontendConfig {
+            show_datetimes: false,
+            ..FrontendConfig::default()
+        };
+
+        let author_style = Style::default().fg(data.hash_username(&Palette::Pastel));
+
+        assert_eq!(
+            data.to_vec(&frontendconfig, 30, None, None, &Highlights::default()),
+            vec![Line::from(vec![
+                Span::styled("foo", author_style),
+                Span::styled(
+                    " 100 bits",
+                    Style::default()
+                        .fg(bits_tier_color(100))
+                        .add_modifier(Modifier::BOLD)
+                ),
+                Span::raw(": "),
+                Span::styled("Cheer100", Style::default().add_modifier(Modifier::BOLD)),
+            ])]
+        );
+    }
+
+    #[test]
+    fn build_vec_with_announcement() {
+        let raw_message = RawMessageData::builder()
+            .author("foo".to_string())
+            .system(false)
+            .payload("hi".to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .notice(ChatNotice::new("announcement", Some("GREEN")))
+            .build();
+
+        let emotes = Rc::new(Emotes::new(false));
+        let data = MessageData::from_twitch_message(raw_message, &emotes);
+
+        let frontendconfig = FrontendConfig {
+            show_datetimes: false,
+            ..FrontendConfig::default()
+        };
+
+        let fg = data.hash_username(&Palette::Pastel);
+        let announcement_style = ChatNotice::Announcement(AnnouncementColor::Green).style(fg);
+
+        assert_eq!(
+            data.to_vec(&frontendconfig, 30, None, None, &Highlights::default()),
+            vec![
+                Line::from(Span::styled("┌ Announcement", announcement_style)),
+                Line::from(vec![
+                    Span::styled("foo", Style::default().fg(fg)),
+                    Span::raw(": "),
+                    Span::styled("hi", announcement_style),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_vec_with_ping() {
+        let raw_message = RawMessageData::builder()
+            .author("foo".to_string())
+            .system(false)
+            .payload("@bar hi".to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .mentions(vec!["@bar".to_string()])
+            .ping(true)
+            .build();
+
+        let emotes = Rc::new(Emotes::new(false));
+        let data = MessageData::from_twitch_message(raw_message, &emotes);
+
+        let frontendconfig = FrontendConfig {
+            show_datetimes: false,
+            ..FrontendConfig::default()
+        };
+
+        let author_style = Style::default().fg(data.hash_username(&Palette::Pastel));
+        let mention_style = *HIGHLIGHT_NAME_DARK_STYLE;
+
+        assert_eq!(
+            data.to_vec(&frontendconfig, 30, None, None, &Highlights::default()),
+            vec![
+                Line::from(vec![
+                    Span::styled("foo", author_style),
+                    Span::raw(": "),
+                    Span::styled("@", mention_style),
+                    Span::styled("b", mention_style),
+                    Span::styled("a", mention_style),
+                    Span::styled("r", mention_style),
+                    Span::styled(" ", Style::default()),
+                    Span::styled("h", Style::default()),
+                    Span::styled("i", Style::default()),
+                ])
+                .style(*PING_DARK_STYLE)
+            ]
+        );
+    }
+
+    #[test]
+    fn build_vec_with_highlight_rules() {
+        let raw_message = RawMessageData::builder()
+            .author("foo".to_string())
+            .system(false)
+            .payload("ban evasion".to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .build();
+
+        let emotes = Rc::new(Emotes::new(false));
+        let data = MessageData::from_twitch_message(raw_message, &emotes);
+
+        let frontendconfig = FrontendConfig {
+            show_datetimes: false,
+            ..FrontendConfig::default()
+        };
+
+        let highlights = Highlights::new(&[
+            toml::from_str::<HighlightConfig>(
+                r#"
+                regex = "evasion"
+                modifiers = ["underlined"]
+                "#,
+            )
+            .unwrap(),
+            toml::from_str::<HighlightConfig>(
+                r#"
+                username = "FOO"
+                modifiers = ["bold"]
+                "#,
+            )
+            .unwrap(),
+        ]);
+
+        let author_style = Style::default().fg(data.hash_username(&Palette::Pastel));
+        let message_style = Style::default().add_modifier(Modifier::BOLD);
+        let rule_style = message_style.add_modifier(Modifier::UNDERLINED);
+
+        let line = data.to_vec(&frontendconfig, 30, None, None, &highlights);
+
+        assert_eq!(line[0].spans[0], Span::styled("foo", author_style));
+        assert_eq!(line[0].spans[2], Span::styled("b", message_style));
+        assert_eq!(line[0].spans[6], Span::styled("e", rule_style));
+        assert_eq!(line[0].spans.len(), 13);
+    }
+
+    #[test]
+    fn build_vec_with_reply_quote() {
+        let raw_message = RawMessageData::builder()
+            .author("foo".to_string())
+            .system(false)
+            .payload("@bar hi".to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .reply(Box::new(ReplyParent {
+                message_id: "parent".to_string(),
+                author: "bar".to_string(),
+                payload: "hello there".to_string(),
+            }))
+            .build();
+
+        let emotes = Rc::new(Emotes::new(false));
+        let data = MessageData::from_twitch_message(raw_message, &emotes);
+
+        let frontendconfig = FrontendConfig {
+            show_datetimes: false,
+            ..FrontendConfig::default()
+        };
+
+        let lines = data.to_vec(&frontendconfig, 30, None, None, &Highlights::default());
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            Line::from(Span::styled("┌ @bar: hello there", *REPLY_STYLE))
+        );
+    }
 }
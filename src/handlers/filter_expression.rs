@@ -0,0 +1,508 @@
+use std::{cmp::Ordering, fmt, iter::Peekable, str::FromStr, vec::IntoIter};
+
+use chrono::NaiveTime;
+use color_eyre::{
+    Result,
+    eyre::{Error, bail},
+};
+use regex::Regex;
+
+use crate::handlers::data::MessageData;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    LeftParen,
+    RightParen,
+    And,
+    Or,
+    Not,
+    Operator(Operator),
+    /// A word, or anything between double quotes
+    Value(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LeftParen => write!(f, "("),
+            Self::RightParen => write!(f, ")"),
+            Self::And => write!(f, "and"),
+            Self::Or => write!(f, "or"),
+            Self::Not => write!(f, "not"),
+            Self::Operator(operator) => write!(f, "{operator}"),
+            Self::Value(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    /// Matches a regex
+    Matches,
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Equal => "==",
+                Self::NotEqual => "!=",
+                Self::Less => "<",
+                Self::LessOrEqual => "<=",
+                Self::Greater => ">",
+                Self::GreaterOrEqual => ">=",
+                Self::Matches => "~",
+            }
+        )
+    }
+}
+
+impl Operator {
+    fn compare<T: Ord>(self, left: &T, right: &T) -> bool {
+        let ordering = left.cmp(right);
+
+        match self {
+            Self::Equal | Self::Matches => ordering == Ordering::Equal,
+            Self::NotEqual => ordering != Ordering::Equal,
+            Self::Less => ordering == Ordering::Less,
+            Self::LessOrEqual => ordering != Ordering::Greater,
+            Self::Greater => ordering == Ordering::Greater,
+            Self::GreaterOrEqual => ordering != Ordering::Less,
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '~' => Token::Operator(Operator::Matches),
+            '&' if chars.next_if_eq(&'&').is_some() => Token::And,
+            '|' if chars.next_if_eq(&'|').is_some() => Token::Or,
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Equal),
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::NotEqual),
+            '!' => Token::Not,
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::LessOrEqual),
+            '<' => Token::Operator(Operator::Less),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::GreaterOrEqual),
+            '>' => Token::Operator(Operator::Greater),
+            '"' => {
+                let mut value = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped @ ('"' | '\\')) => value.push(escaped),
+                            // Anything else is left for the regex to interpret
+                            Some(escaped) => {
+                                value.push('\\');
+                                value.push(escaped);
+                            }
+                            None => bail!("Unterminated string \"{value}"),
+                        },
+                        Some(c) => value.push(c),
+                        None => bail!("Unterminated string \"{value}"),
+                    }
+                }
+
+                Token::Value(value)
+            }
+            c if c.is_alphanumeric() || c == '_' || c == ':' => {
+                let mut word = c.to_string();
+
+                while let Some(c) =
+                    chars.next_if(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '-'))
+                {
+                    word.push(c);
+                }
+
+                match word.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Value(word),
+                }
+            }
+            c => bail!("Unexpected character '{c}'"),
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Something about a message that can be checked on its own
+#[derive(Debug, Clone)]
+pub enum Condition {
+    /// The name of the author, ignoring case
+    Author(Operator, String),
+    AuthorMatches(Regex),
+    /// The text of the message, without its emotes
+    MessageMatches(Regex),
+    /// A badge that the author has, by its set ID
+    Badge(String),
+    /// The amount of characters in the text of the message
+    Length(Operator, usize),
+    /// The percentage of letters in the text of the message that are uppercase
+    Caps(Operator, usize),
+    /// If the message has nothing but emotes in it
+    EmoteOnly,
+    /// If the author had not been seen chatting in the channel since twt started
+    FirstSeen,
+    /// The local time of day that the message was sent at
+    Time(Operator, NaiveTime),
+}
+
+impl Condition {
+    fn matches(&self, message: &MessageData) -> bool {
+        match self {
+            Self::Author(operator, name) => {
+                operator.compare(&message.author.to_lowercase(), &name.to_lowercase())
+            }
+            Self::AuthorMatches(re) => re.is_match(&message.author),
+            Self::MessageMatches(re) => re.is_match(&message.text()),
+            Self::Badge(set_id) => message.badges.iter().any(|badge| &badge.set_id == set_id),
+            Self::Length(operator, length) => {
+                operator.compare(&message.text().chars().count(), length)
+            }
+            Self::Caps(operator, percentage) => {
+                let (letters, uppercase) = message
+                    .text()
+                    .chars()
+                    .filter(|c| c.is_alphabetic())
+                    .fold((0, 0), |(letters, uppercase), c| {
+                        (letters + 1, uppercase + usize::from(c.is_uppercase()))
+                    });
+
+                letters > 0 && operator.compare(&(uppercase * 100 / letters), percentage)
+            }
+            Self::EmoteOnly => message.emote_only,
+            Self::FirstSeen => message.first_seen,
+            Self::Time(operator, time) => operator.compare(&message.time_sent.time(), time),
+        }
+    }
+}
+
+/// What a filter hides, as a combination of conditions
+#[derive(Debug, Clone)]
+pub enum FilterExpression {
+    And(Box<Self>, Box<Self>),
+    Or(Box<Self>, Box<Self>),
+    Not(Box<Self>),
+    Condition(Condition),
+}
+
+impl FilterExpression {
+    pub fn matches(&self, message: &MessageData) -> bool {
+        match self {
+            Self::And(left, right) => left.matches(message) && right.matches(message),
+            Self::Or(left, right) => left.matches(message) || right.matches(message),
+            Self::Not(expression) => !expression.matches(message),
+            Self::Condition(condition) => condition.matches(message),
+        }
+    }
+}
+
+impl FromStr for FilterExpression {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(expression)?.into_iter().peekable(),
+        };
+
+        let expression = parser.or()?;
+
+        if let Some(token) = parser.tokens.next() {
+            bail!("Unexpected '{token}' after the end of the expression");
+        }
+
+        Ok(expression)
+    }
+}
+
+/// A recursive descent parser, where `not` binds tighter than `and`, which binds tighter than `or`
+struct Parser {
+    tokens: Peekable<IntoIter<Token>>,
+}
+
+impl Parser {
+    fn or(&mut self) -> Result<FilterExpression> {
+        let mut expression = self.and()?;
+
+        while self.tokens.next_if_eq(&Token::Or).is_some() {
+            expression = FilterExpression::Or(Box::new(expression), Box::new(self.and()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn and(&mut self) -> Result<FilterExpression> {
+        let mut expression = self.not()?;
+
+        while self.tokens.next_if_eq(&Token::And).is_some() {
+            expression = FilterExpression::And(Box::new(expression), Box::new(self.not()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn not(&mut self) -> Result<FilterExpression> {
+        if self.tokens.next_if_eq(&Token::Not).is_some() {
+            return Ok(FilterExpression::Not(Box::new(self.not()?)));
+        }
+
+        match self.tokens.next() {
+            Some(Token::LeftParen) => {
+                let expression = self.or()?;
+
+                if self.tokens.next() != Some(Token::RightParen) {
+                    bail!("Expected ')' to close '('");
+                }
+
+                Ok(expression)
+            }
+            Some(Token::Value(field)) => Ok(FilterExpression::Condition(self.condition(&field)?)),
+            Some(token) => bail!("Expected a condition, found '{token}'"),
+            None => bail!("Expected a condition, found the end of the expression"),
+        }
+    }
+
+    fn operator(&mut self, field: &str) -> Result<Operator> {
+        match self.tokens.next() {
+            Some(Token::Operator(operator)) => Ok(operator),
+            _ => bail!("Expected an operator after '{field}'"),
+        }
+    }
+
+    fn value(&mut self, field: &str, operator: Operator) -> Result<String> {
+        match self.tokens.next() {
+            Some(Token::Value(value)) => Ok(value),
+            _ => bail!("Expected a value after '{field} {operator}'"),
+        }
+    }
+
+    fn number(&mut self, field: &str, operator: Operator) -> Result<usize> {
+        let value = self.value(field, operator)?;
+
+        match (operator, value.parse()) {
+            (Operator::Matches, _) => bail!("'{field}' can't be matched with a regex"),
+            (_, Ok(number)) => Ok(number),
+            (_, Err(_)) => bail!("'{value}' is not a number"),
+        }
+    }
+
+    fn condition(&mut self, field: &str) -> Result<Condition> {
+        let condition = match field.to_lowercase().as_str() {
+            "author" => {
+                let operator = self.operator(field)?;
+                let value = self.value(field, operator)?;
+
+                match operator {
+                    Operator::Matches => Condition::AuthorMatches(Regex::new(&value)?),
+                    Operator::Equal | Operator::NotEqual => Condition::Author(operator, value),
+                    _ => bail!("'author' can only be compared with ==, != or ~"),
+                }
+            }
+            "message" => {
+                let operator = self.operator(field)?;
+                let value = self.value(field, operator)?;
+
+                if operator != Operator::Matches {
+                    bail!("'message' can only be matched with ~");
+                }
+
+                Condition::MessageMatches(Regex::new(&value)?)
+            }
+            "badge" => {
+                let operator = self.operator(field)?;
+                let value = self.value(field, operator)?;
+
+                if operator != Operator::Equal {
+                    bail!("'badge' can only be compared with ==");
+                }
+
+                Condition::Badge(
+                    match value.to_lowercase().as_str() {
+                        "mod" => "moderator",
+                        "sub" => "subscriber",
+                        set_id => set_id,
+                    }
+                    .to_string(),
+                )
+            }
+            "length" => {
+                let operator = self.operator(field)?;
+
+                Condition::Length(operator, self.number(field, operator)?)
+            }
+            "caps" => {
+                let operator = self.operator(field)?;
+
+                Condition::Caps(operator, self.number(field, operator)?)
+            }
+            "time" => {
+                let operator = self.operator(field)?;
+                let value = self.value(field, operator)?;
+
+                if operator == Operator::Matches {
+                    bail!("'time' can't be matched with a regex");
+                }
+
+                let Ok(time) = NaiveTime::parse_from_str(&value, "%H:%M") else {
+                    bail!("'{value}' is not a time, such as 21:30");
+                };
+
+                Condition::Time(operator, time)
+            }
+            "emote_only" => Condition::EmoteOnly,
+            "first_seen" => Condition::FirstSeen,
+            _ => bail!("Unknown condition '{field}'"),
+        };
+
+        Ok(condition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, rc::Rc};
+
+    use chrono::{Local, TimeZone};
+
+    use super::*;
+    use crate::{
+        emotes::Emotes,
+        handlers::data::RawMessageData,
+        twitch::badges::ChatBadge,
+        utils::emotes::{PRIVATE_USE_UNICODE, ZERO_WIDTH_SPACE},
+    };
+
+    fn setup_message(author: &str, payload: &str, badges: &[&str]) -> MessageData {
+        let raw_message = RawMessageData::builder()
+            .author(author.to_string())
+            .system(false)
+            .payload(payload.to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .badges(
+                badges
+                    .iter()
+                    .map(|set_id| ChatBadge {
+                        set_id: (*set_id).to_string(),
+                        id: "1".to_string(),
+                        info: String::new(),
+                        channel_id: None,
+                    })
+                    .collect(),
+            )
+            .build();
+
+        let mut message =
+            MessageData::from_twitch_message(raw_message, &Rc::new(Emotes::new(false)));
+        message.time_sent = Local.with_ymd_and_hms(2025, 1, 1, 23, 15, 0).unwrap();
+
+        message
+    }
+
+    fn matches(expression: &str, message: &MessageData) -> bool {
+        expression
+            .parse::<FilterExpression>()
+            .unwrap()
+            .matches(message)
+    }
+
+    #[test]
+    fn test_non_subs_in_all_caps() {
+        let expression = "not badge == sub and caps == 100";
+
+        assert!(matches(
+            expression,
+            &setup_message("foo", "HELLO CHAT!", &[])
+        ));
+        assert!(!matches(
+            expression,
+            &setup_message("foo", "HELLO CHAT!", &["subscriber"])
+        ));
+        assert!(!matches(
+            expression,
+            &setup_message("foo", "Hello chat", &[])
+        ));
+    }
+
+    #[test]
+    fn test_precedence_and_parentheses() {
+        let message = setup_message("Foo", "hello", &["vip"]);
+
+        assert!(matches(
+            "author == bar and length > 100 or badge == vip",
+            &message
+        ));
+        assert!(!matches(
+            "author == bar and (length > 100 || badge == vip)",
+            &message
+        ));
+        assert!(matches(
+            "!(author != FOO) && message ~ \"^hel+o$\"",
+            &message
+        ));
+    }
+
+    #[test]
+    fn test_emotes_are_left_out_of_text() {
+        let message = setup_message(
+            "foo",
+            &format!("HI{ZERO_WIDTH_SPACE}{PRIVATE_USE_UNICODE}{PRIVATE_USE_UNICODE}"),
+            &[],
+        );
+
+        assert!(matches("length == 2 and caps == 100", &message));
+        assert!(matches("message ~ \"^HI$\"", &message));
+    }
+
+    #[test]
+    fn test_time_and_flags() {
+        let message = setup_message("foo", "hello", &[]);
+
+        assert!(matches("time >= 22:00", &message));
+        assert!(!matches("time < 06:00", &message));
+        assert!(!matches("emote_only or first_seen", &message));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expression in [
+            "",
+            "author",
+            "author >",
+            "length > many",
+            "badge ~ mod",
+            "(first_seen",
+            "first_seen)",
+            "message ~ \"[\"",
+            "time > noon",
+            "colour == red",
+            "author == \"foo",
+        ] {
+            assert!(
+                expression.parse::<FilterExpression>().is_err(),
+                "{expression:?} should not parse"
+            );
+        }
+    }
+}
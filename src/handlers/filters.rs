@@ -1,22 +1,94 @@
-use std::{cell::RefCell, fs::read_to_string, path::PathBuf, rc::Rc, string::ToString};
-
-use color_eyre::Result;
+use std::{
+    cell::RefCell,
+    fmt,
+    fs::{create_dir_all, read_to_string, write},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    rc::Rc,
+    string::ToString,
+};
+
+use color_eyre::{
+    Report, Result,
+    eyre::{Context, bail},
+};
 use regex::Regex;
 
-use crate::config::{SharedCoreConfig, get_config_dir};
+use crate::{
+    config::{SharedCoreConfig, get_config_dir},
+    handlers::{data::MessageData, filter_expression::FilterExpression},
+};
 
 const DEFAULT_MESSAGE_FILTERS_FILE_NAME: &str = "message_filters.txt";
 const DEFAULT_USERNAME_FILTERS_FILE_NAME: &str = "username_filters.txt";
 
 pub type SharedFilters = Rc<RefCell<Filters>>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterKind {
+    Message,
+    Username,
+}
+
+impl FilterKind {
+    pub const fn other(self) -> Self {
+        match self {
+            Self::Message => Self::Username,
+            Self::Username => Self::Message,
+        }
+    }
+}
+
+impl fmt::Display for FilterKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Message => write!(f, "message"),
+            Self::Username => write!(f, "username"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct Filters {
     pub message: MessageFilters,
     pub username: UsernameFilters,
+    pub presets: Vec<FilterPreset>,
 }
 
+impl Filters {
+    /// If a message should be hidden from the chat
+    pub fn hides(&self, data: &MessageData) -> bool {
+        self.message.contaminated(&data.payload)
+            || self.username.contaminated(&data.author)
+            || self.presets.iter().any(|preset| preset.hides(data))
+    }
+}
+
+/// A filter expression with a name, which can be toggled from the presets popup
 #[derive(Debug, Clone)]
+pub struct FilterPreset {
+    pub name: String,
+    /// The expression as it was written in the config
+    pub source: String,
+    expression: FilterExpression,
+    enabled: bool,
+}
+
+impl FilterPreset {
+    pub fn hides(&self, data: &MessageData) -> bool {
+        self.enabled && self.expression.matches(data)
+    }
+
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub const fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct MessageFilters {
     captures: Vec<Regex>,
     enabled: bool,
@@ -53,7 +125,7 @@ impl MessageFilters {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct UsernameFilters {
     captures: Vec<Regex>,
     enabled: bool,
@@ -90,71 +162,115 @@ impl UsernameFilters {
     }
 }
 
-fn parse_filters_regex(entries: Vec<String>) -> Vec<Regex> {
-    entries
-        .into_iter()
-        .filter(|s| !s.is_empty())
-        .flat_map(|s| Regex::new(&s))
-        .collect()
+/// Parses every filter, returning the ones that are valid along with an error for each one that isn't
+fn parse_filters_regex(entries: Vec<String>) -> (Vec<Regex>, Vec<Report>) {
+    let mut captures = vec![];
+    let mut errors = vec![];
+
+    for entry in entries.into_iter().filter(|s| !s.is_empty()) {
+        match Regex::new(&entry).with_context(|| format!("Invalid filter {entry:?}")) {
+            Ok(re) => captures.push(re),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    (captures, errors)
 }
 
-fn parse_filters_file(path: PathBuf) -> Result<Vec<Regex>> {
-    let captures = parse_filters_regex(
-        read_to_string(path)?
-            .split('\n')
-            .map(ToString::to_string)
-            .collect(),
-    );
+/// A filters file that doesn't exist has no filters in it, since saving from the filter editor creates it
+fn parse_filters_file(path: &Path) -> (Vec<Regex>, Vec<Report>) {
+    let content = match read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return (vec![], vec![]),
+        Err(err) => {
+            return (
+                vec![],
+                vec![
+                    Report::new(err)
+                        .wrap_err(format!("Filters file {} could not be read", path.display())),
+                ],
+            );
+        }
+    };
+
+    let (captures, errors) =
+        parse_filters_regex(content.lines().map(ToString::to_string).collect());
 
-    Ok(captures)
+    let errors = errors
+        .into_iter()
+        .map(|err| {
+            err.wrap_err(format!(
+                "Filters file {} could not be parsed",
+                path.display()
+            ))
+        })
+        .collect();
+
+    (captures, errors)
 }
 
 /// Get the filter array from the config.
 /// If that array doesn't exist, then we'll try seeing if there's anything
-/// at the filter config path. If we can't parse it, we populate the filters
-/// with an empty vector. Otherwise if the config path hasn't been provided,
-/// we go to the default filters path.
+/// at the filter config path. Otherwise if the config path hasn't been provided,
+/// we go to the default filters path. Neither of the files have to exist.
 fn parse_filters(
     config_filters: Option<Vec<String>>,
     filters_path: Option<PathBuf>,
-    default_filters_file: PathBuf,
-) -> Vec<Regex> {
-    config_filters.map_or_else(
-        || {
-            filters_path.map_or_else(
-                || {
-                    if default_filters_file.exists() {
-                        parse_filters_file(default_filters_file).unwrap_or_default()
-                    } else {
-                        vec![]
-                    }
-                },
-                |filters_path| parse_filters_file(filters_path).unwrap_or_default(),
-            )
-        },
-        parse_filters_regex,
-    )
+    default_filters_file: &Path,
+) -> (Vec<Regex>, Vec<Report>) {
+    match (config_filters, filters_path) {
+        (Some(config_filters), _) => parse_filters_regex(config_filters),
+        (None, Some(filters_path)) => parse_filters_file(&filters_path),
+        (None, None) => parse_filters_file(default_filters_file),
+    }
 }
 
 impl Filters {
-    pub fn new(config: &SharedCoreConfig) -> Self {
+    /// Parses every filter, failing on the first one that is invalid so that a reload can keep the previous filters
+    pub fn new(config: &SharedCoreConfig) -> Result<Self> {
+        let (filters, errors) = Self::load(config);
+
+        errors.into_iter().next().map_or(Ok(filters), Err)
+    }
+
+    /// Parses every filter, skipping the ones that are invalid and returning an error for each of them
+    pub fn load(config: &SharedCoreConfig) -> (Self, Vec<Report>) {
         let config_dir = get_config_dir();
 
         let message_filters_config = config.filters.message.clone();
-        let message_filters = parse_filters(
+        let (message_filters, mut errors) = parse_filters(
             message_filters_config.filters,
             message_filters_config.path,
-            config_dir.join(DEFAULT_MESSAGE_FILTERS_FILE_NAME),
+            &config_dir.join(DEFAULT_MESSAGE_FILTERS_FILE_NAME),
         );
 
         let username_filters_config = config.filters.username.clone();
-        let username_filters = parse_filters(
+        let (username_filters, username_errors) = parse_filters(
             username_filters_config.filters,
             username_filters_config.path,
-            config_dir.join(DEFAULT_USERNAME_FILTERS_FILE_NAME),
+            &config_dir.join(DEFAULT_USERNAME_FILTERS_FILE_NAME),
         );
+        errors.extend(username_errors);
+
+        let mut presets = vec![];
+
+        for preset in &config.filters.presets {
+            match preset
+                .expression
+                .parse()
+                .with_context(|| format!("Invalid filter preset {:?}", preset.name))
+            {
+                Ok(expression) => presets.push(FilterPreset {
+                    name: preset.name.clone(),
+                    source: preset.expression.clone(),
+                    expression,
+                    enabled: preset.enabled,
+                }),
+                Err(err) => errors.push(err),
+            }
+        }
 
-        Self {
+        let filters = Self {
             message: MessageFilters {
                 captures: message_filters,
                 enabled: message_filters_config.enabled,
@@ -165,13 +281,146 @@ impl Filters {
                 enabled: username_filters_config.enabled,
                 reversed: username_filters_config.reversed,
             },
+            presets,
+        };
+
+        (filters, errors)
+    }
+
+    /// Carries over the toggles that were flipped while twt was running, so that a reload doesn't undo them.
+    /// Toggles that still match the previous config follow the reloaded config instead.
+    pub fn keep_toggles(&mut self, previous: &Self, previous_config: &SharedCoreConfig) {
+        let filters_config = &previous_config.filters;
+
+        if previous.message.enabled != filters_config.message.enabled {
+            self.message.enabled = previous.message.enabled;
+        }
+        if previous.message.reversed != filters_config.message.reversed {
+            self.message.reversed = previous.message.reversed;
+        }
+        if previous.username.enabled != filters_config.username.enabled {
+            self.username.enabled = previous.username.enabled;
+        }
+        if previous.username.reversed != filters_config.username.reversed {
+            self.username.reversed = previous.username.reversed;
+        }
+
+        for preset in &mut self.presets {
+            let Some(previous_preset) = previous
+                .presets
+                .iter()
+                .find(|previous_preset| previous_preset.name == preset.name)
+            else {
+                continue;
+            };
+
+            let configured = filters_config
+                .presets
+                .iter()
+                .find(|preset_config| preset_config.name == preset.name)
+                .is_some_and(|preset_config| preset_config.enabled);
+
+            if previous_preset.enabled != configured {
+                preset.enabled = previous_preset.enabled;
+            }
+        }
+    }
+
+    /// The files that filters are read from, so that they can be watched for changes
+    pub fn files(config: &SharedCoreConfig) -> Vec<PathBuf> {
+        [FilterKind::Message, FilterKind::Username]
+            .into_iter()
+            .filter_map(|kind| Self::file(config, kind))
+            .collect()
+    }
+
+    /// The file that filters of a kind are read from, unless they are written out in the config
+    pub fn file(config: &SharedCoreConfig, kind: FilterKind) -> Option<PathBuf> {
+        let (filters, path, default_file_name) = match kind {
+            FilterKind::Message => (
+                &config.filters.message.filters,
+                &config.filters.message.path,
+                DEFAULT_MESSAGE_FILTERS_FILE_NAME,
+            ),
+            FilterKind::Username => (
+                &config.filters.username.filters,
+                &config.filters.username.path,
+                DEFAULT_USERNAME_FILTERS_FILE_NAME,
+            ),
+        };
+
+        filters.is_none().then(|| {
+            path.clone()
+                .unwrap_or_else(|| get_config_dir().join(default_file_name))
+        })
+    }
+
+    pub fn captures(&self, kind: FilterKind) -> &[Regex] {
+        match kind {
+            FilterKind::Message => &self.message.captures,
+            FilterKind::Username => &self.username.captures,
+        }
+    }
+
+    const fn captures_mut(&mut self, kind: FilterKind) -> &mut Vec<Regex> {
+        match kind {
+            FilterKind::Message => &mut self.message.captures,
+            FilterKind::Username => &mut self.username.captures,
         }
     }
+
+    /// Adds a filter, returning false if an identical one already exists
+    pub fn add(&mut self, kind: FilterKind, filter: Regex) -> bool {
+        let captures = self.captures_mut(kind);
+
+        if captures.iter().any(|re| re.as_str() == filter.as_str()) {
+            return false;
+        }
+
+        captures.push(filter);
+        true
+    }
+
+    /// Removes a filter by its pattern, returning false if there was no such filter
+    pub fn remove(&mut self, kind: FilterKind, pattern: &str) -> bool {
+        let captures = self.captures_mut(kind);
+        let count = captures.len();
+
+        captures.retain(|re| re.as_str() != pattern);
+
+        captures.len() != count
+    }
+
+    /// Writes the filters of a kind back to the file they were read from
+    pub fn save(&self, config: &SharedCoreConfig, kind: FilterKind) -> Result<()> {
+        let Some(path) = Self::file(config, kind) else {
+            bail!(
+                "The {kind} filters are written out in the config, so they can't be saved to a file"
+            );
+        };
+
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+
+        write(&path, filters_file_content(self.captures(kind)))
+            .with_context(|| format!("Filters file {} could not be written", path.display()))
+    }
+}
+
+/// One filter per line, as the filter files are read
+fn filters_file_content(captures: &[Regex]) -> String {
+    captures.iter().fold(String::new(), |mut content, re| {
+        content.push_str(re.as_str());
+        content.push('\n');
+        content
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::CoreConfig;
 
     fn setup_message_filters() -> MessageFilters {
         MessageFilters {
@@ -252,4 +501,106 @@ mod tests {
 
         assert!(filters.contaminated("good-username"));
     }
+
+    #[test]
+    fn test_invalid_filter_is_skipped() {
+        let (filters, errors) = parse_filters(
+            Some(vec!["^fine$".to_string(), "(unclosed".to_string()]),
+            None,
+            Path::new("missing_filters.txt"),
+        );
+
+        assert_eq!(
+            filters.iter().map(Regex::as_str).collect::<Vec<_>>(),
+            vec!["^fine$"]
+        );
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_filter_fails_strict_parsing() {
+        let mut config = CoreConfig::default();
+        config.filters.message.filters = Some(vec!["^fine$".to_string(), "(unclosed".to_string()]);
+        let config = SharedCoreConfig::new(config);
+
+        assert!(Filters::new(&config).is_err());
+
+        let (filters, errors) = Filters::load(&config);
+
+        assert_eq!(filters.captures(FilterKind::Message).len(), 1);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_missing_default_filters_file() {
+        let (filters, errors) = parse_filters(None, None, Path::new("missing_filters.txt"));
+
+        assert!(filters.is_empty());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_missing_configured_filters_file() {
+        let (filters, errors) = parse_filters(
+            None,
+            Some(PathBuf::from("missing_filters.txt")),
+            Path::new("missing_filters.txt"),
+        );
+
+        assert!(filters.is_empty());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_reload_keeps_runtime_toggles() {
+        let mut config = CoreConfig::default();
+        config.filters.message.filters = Some(vec![]);
+        config.filters.username.filters = Some(vec![]);
+        config.filters.message.enabled = false;
+        config.filters.username.enabled = false;
+        let previous_config = SharedCoreConfig::new(config.clone());
+
+        let mut previous = Filters::new(&previous_config).unwrap();
+        previous.message.toggle();
+
+        // The username filters were turned on in the config rather than at runtime
+        config.filters.username.enabled = true;
+        let mut reloaded = Filters::new(&SharedCoreConfig::new(config)).unwrap();
+        reloaded.keep_toggles(&previous, &previous_config);
+
+        assert!(reloaded.message.is_enabled());
+        assert!(reloaded.username.is_enabled());
+        assert!(!reloaded.message.is_reversed());
+    }
+
+    #[test]
+    fn test_add_and_remove_filters() {
+        let mut filters = Filters::default();
+
+        assert!(filters.add(FilterKind::Message, Regex::new("^!+$").unwrap()));
+        assert!(!filters.add(FilterKind::Message, Regex::new("^!+$").unwrap()));
+        assert!(filters.add(FilterKind::Username, Regex::new("(?i)bot$").unwrap()));
+
+        assert_eq!(filters.captures(FilterKind::Message).len(), 1);
+        assert!(!filters.remove(FilterKind::Username, "^!+$"));
+        assert!(filters.remove(FilterKind::Message, "^!+$"));
+        assert!(filters.captures(FilterKind::Message).is_empty());
+    }
+
+    #[test]
+    fn test_filters_file_content_round_trip() {
+        let captures = vec![
+            Regex::new("^bad.*$").unwrap(),
+            Regex::new("(?i)spam").unwrap(),
+        ];
+
+        let content = filters_file_content(&captures);
+        let (parsed, _) = parse_filters_regex(content.lines().map(ToString::to_string).collect());
+
+        assert_eq!(content, "^bad.*$\n(?i)spam\n");
+        assert_eq!(
+            parsed.iter().map(Regex::as_str).collect::<Vec<_>>(),
+            vec!["^bad.*$", "(?i)spam"]
+        );
+    }
 }
@@ -0,0 +1,212 @@
+use std::{cell::RefCell, rc::Rc};
+
+use regex::Regex;
+use tracing::warn;
+use tui::style::{Modifier, Style};
+
+use crate::{config::HighlightConfig, utils::styles::NO_COLOR};
+
+pub type SharedHighlights = Rc<RefCell<Highlights>>;
+
+#[derive(Debug, Clone)]
+pub struct HighlightRule {
+    regex: Option<Regex>,
+    username: Option<String>,
+    style: Style,
+    /// If messages that match the rule should be notified about
+    pub notify: bool,
+    /// If the terminal bell should ring for messages that match the rule
+    pub sound: bool,
+}
+
+impl HighlightRule {
+    fn new(config: &HighlightConfig) -> Option<Self> {
+        let regex = match config.regex.as_deref().filter(|regex| !regex.is_empty()) {
+            Some(regex) => match Regex::new(regex) {
+                Ok(regex) => Some(regex),
+                Err(err) => {
+                    warn!("Ignoring highlight rule with invalid regex {regex:?}: {err}");
+                    return None;
+                }
+            },
+            None => None,
+        };
+
+        let username = config
+            .username
+            .as_deref()
+            .filter(|username| !username.is_empty())
+            .map(ToString::to_string);
+
+        // A rule without anything to match would highlight every message
+        if regex.is_none() && username.is_none() {
+            return None;
+        }
+
+        let mut style = Style::default().add_modifier(
+            config
+                .modifiers
+                .iter()
+                .fold(Modifier::empty(), |modifiers, &modifier| {
+                    modifiers | modifier.into()
+                }),
+        );
+
+        if !*NO_COLOR {
+            style.fg = config.fg;
+            style.bg = config.bg;
+        }
+
+        Some(Self {
+            regex,
+            username,
+            style,
+            notify: config.notify,
+            sound: config.sound,
+        })
+    }
+
+    fn applies_to(&self, author: &str) -> bool {
+        self.username
+            .as_ref()
+            .is_none_or(|username| username.eq_ignore_ascii_case(author))
+    }
+
+    pub fn matches(&self, author: &str, payload: &str) -> bool {
+        self.applies_to(author) && self.regex.as_ref().is_none_or(|re| re.is_match(payload))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Highlights {
+    rules: Vec<HighlightRule>,
+}
+
+impl Highlights {
+    pub fn new(config: &[HighlightConfig]) -> Self {
+        Self {
+            rules: config.iter().filter_map(HighlightRule::new).collect(),
+        }
+    }
+
+    /// The first rule that matches a message, if any of them do
+    pub fn find(&self, author: &str, payload: &str) -> Option<&HighlightRule> {
+        self.rules.iter().find(|rule| rule.matches(author, payload))
+    }
+
+    /// The style of every message from the author, from the rules that only match usernames
+    pub fn message_style(&self, author: &str) -> Style {
+        self.rules
+            .iter()
+            .rev()
+            .filter(|rule| rule.regex.is_none() && rule.applies_to(author))
+            .fold(Style::default(), |style, rule| style.patch(rule.style))
+    }
+
+    /// Byte indices of the payload that regex rules match, sorted, along with the style of the first rule to match each of them
+    pub fn payload_highlights(&self, author: &str, payload: &str) -> Vec<(usize, Style)> {
+        let mut highlights = self
+            .rules
+            .iter()
+            .filter(|rule| rule.applies_to(author))
+            .filter_map(|rule| rule.regex.as_ref().map(|re| (re, rule.style)))
+            .flat_map(|(re, style)| {
+                re.find_iter(payload)
+                    .flat_map(move |m| m.range().map(move |index| (index, style)))
+            })
+            .collect::<Vec<_>>();
+
+        // The sort is stable, so earlier rules take precedence over later ones
+        highlights.sort_by_key(|(index, _)| *index);
+        highlights.dedup_by_key(|(index, _)| *index);
+
+        highlights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tui::style::Color;
+
+    use super::*;
+
+    fn setup_highlights() -> Highlights {
+        Highlights::new(&[
+            HighlightConfig {
+                regex: Some("(?i)ban evasion".to_string()),
+                fg: Some(Color::Red),
+                notify: true,
+                ..HighlightConfig::default()
+            },
+            toml::from_str(
+                r##"
+                username = "Watched"
+                bg = "#0000ff"
+                modifiers = ["bold", "italic"]
+                sound = true
+                "##,
+            )
+            .unwrap(),
+            HighlightConfig {
+                regex: Some("[".to_string()),
+                ..HighlightConfig::default()
+            },
+            HighlightConfig::default(),
+        ])
+    }
+
+    #[test]
+    fn test_invalid_and_empty_rules_are_ignored() {
+        assert_eq!(setup_highlights().rules.len(), 2);
+    }
+
+    #[test]
+    fn test_find_rule() {
+        let highlights = setup_highlights();
+
+        assert!(
+            highlights
+                .find("viewer32", "this is Ban Evasion")
+                .is_some_and(|rule| rule.notify)
+        );
+        assert!(
+            highlights
+                .find("watched", "hello")
+                .is_some_and(|rule| rule.sound)
+        );
+        assert!(highlights.find("viewer32", "hello").is_none());
+    }
+
+    #[test]
+    fn test_message_style() {
+        let highlights = setup_highlights();
+
+        assert_eq!(highlights.message_style("viewer32"), Style::default());
+
+        let style = highlights.message_style("WATCHED");
+        assert_eq!(style.bg, (!*NO_COLOR).then_some(Color::Rgb(0, 0, 255)));
+        assert!(
+            style
+                .add_modifier
+                .contains(Modifier::BOLD | Modifier::ITALIC)
+        );
+    }
+
+    #[test]
+    fn test_payload_highlights() {
+        let highlights = setup_highlights();
+
+        let indices = highlights
+            .payload_highlights("viewer32", "no ban evasion")
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        assert_eq!(indices, (3..14).collect::<Vec<_>>());
+        assert!(
+            highlights
+                .payload_highlights("viewer32", "hello")
+                .is_empty()
+        );
+    }
+}
@@ -1,4 +1,15 @@
+pub mod automod;
+pub mod channels;
+pub mod chat_logs;
+pub mod chatters;
+pub mod config_watcher;
 pub mod data;
+pub mod filter_expression;
 pub mod filters;
+pub mod highlights;
+pub mod notifier;
+pub mod polls;
 pub mod state;
 pub mod storage;
+pub mod stream_status;
+pub mod whispers;
@@ -0,0 +1,344 @@
+use std::{
+    collections::BTreeMap,
+    io::{Write, stdout},
+    process::{Command, Stdio},
+    thread::{self, JoinHandle},
+};
+
+use color_eyre::{Result, eyre::bail};
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+
+use crate::{
+    config::{NotificationMethod, NotificationsConfig},
+    handlers::{chat_logs::ChatLogEntry, data::RawMessageData, whispers::Whisper},
+    twitch::notices::ChatNotice,
+};
+
+/// Why a message is being notified about
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationReason {
+    Mention,
+    Keyword,
+    Highlight,
+    Raid,
+    Whisper,
+}
+
+impl NotificationReason {
+    const fn title(self) -> &'static str {
+        match self {
+            Self::Mention => "Mentioned",
+            Self::Keyword => "Keyword",
+            Self::Highlight => "Highlight",
+            Self::Raid => "Raid",
+            Self::Whisper => "Whisper",
+        }
+    }
+}
+
+/// What is written to the stdin of the notification command
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub reason: NotificationReason,
+    pub message: ChatLogEntry,
+}
+
+/// Alerts the user about messages that they might have missed
+#[derive(Debug)]
+pub struct Notifier {
+    methods: Vec<NotificationMethod>,
+    command: Vec<String>,
+    mentions: bool,
+    /// Lowercased, so that messages can be searched for them without regard to case
+    keywords: Vec<String>,
+    raids: bool,
+    whispers: bool,
+    /// The current user, whose own messages are never notified about
+    username: String,
+}
+
+impl Notifier {
+    pub fn new(config: &NotificationsConfig, username: &str) -> Self {
+        Self {
+            methods: config.methods.clone(),
+            command: config.command.clone(),
+            mentions: config.mentions,
+            keywords: config
+                .keywords
+                .iter()
+                .filter(|keyword| !keyword.is_empty())
+                .map(|keyword| keyword.to_lowercase())
+                .collect(),
+            raids: config.raids,
+            whispers: config.whispers,
+            username: username.to_string(),
+        }
+    }
+
+    /// Why a message should be notified about, if it should be at all.
+    /// `highlighted` is if the message matches a highlight rule that asks for notifications.
+    pub fn reason(
+        &self,
+        message: &RawMessageData,
+        highlighted: bool,
+    ) -> Option<NotificationReason> {
+        if message.author.eq_ignore_ascii_case(&self.username) {
+            return None;
+        }
+
+        if self.raids && message.notice == Some(ChatNotice::Raid) {
+            return Some(NotificationReason::Raid);
+        }
+
+        // Twitch and the terminal can't mention anyone
+        if message.system {
+            return None;
+        }
+
+        if self.mentions && message.ping {
+            return Some(NotificationReason::Mention);
+        }
+
+        if highlighted {
+            return Some(NotificationReason::Highlight);
+        }
+
+        let payload = message.payload.to_lowercase();
+
+        self.keywords
+            .iter()
+            .any(|keyword| payload.contains(keyword))
+            .then_some(NotificationReason::Keyword)
+    }
+
+    /// Alerts the user about a message sent in a channel, if it is something that they want to know about
+    pub fn notify(&self, channel: &str, message: &RawMessageData, highlighted: bool) {
+        let Some(reason) = self.reason(message, highlighted) else {
+            return;
+        };
+
+        let title = format!("{} in #{channel}", reason.title());
+        let body = if message.system {
+            message.payload.clone()
+        } else {
+            format!("{}: {}", message.author, message.payload)
+        };
+
+        self.send(reason, &title, &body, channel, message);
+    }
+
+    /// Alerts the user about a whisper that someone sent them
+    pub fn notify_whisper(&self, whisper: &Whisper) {
+        if !self.whispers || whisper.sent {
+            return;
+        }
+
+        let title = format!(
+            "{} from {}",
+            NotificationReason::Whisper.title(),
+            whisper.user
+        );
+
+        // Whispers aren't sent in any channel
+        let mut message = RawMessageData::builder()
+            .author(whisper.user.clone())
+            .system(false)
+            .payload(whisper.payload.clone())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .build();
+        message.time_sent = whisper.time_sent;
+
+        self.send(
+            NotificationReason::Whisper,
+            &title,
+            &whisper.payload,
+            "",
+            &message,
+        );
+    }
+
+    fn send(
+        &self,
+        reason: NotificationReason,
+        title: &str,
+        body: &str,
+        channel: &str,
+        message: &RawMessageData,
+    ) {
+        for method in &self.methods {
+            let result = match method {
+                NotificationMethod::Bell => ring_bell(),
+                NotificationMethod::Osc9 => write_escape_sequence(&format!(
+                    "\x1b]9;{}\x07",
+                    escape_osc_text(&format!("{title}: {body}"))
+                )),
+                NotificationMethod::Osc777 => write_escape_sequence(&format!(
+                    "\x1b]777;notify;{};{}\x07",
+                    escape_osc_text(title),
+                    escape_osc_text(body)
+                )),
+                NotificationMethod::Command => {
+                    let notification = Notification {
+                        reason,
+                        message: ChatLogEntry::new(channel, message),
+                    };
+
+                    self.run_command(&notification).map(|_| ())
+                }
+            };
+
+            if let Err(err) = result {
+                warn!("Failed to send {method} notification: {err}");
+            }
+        }
+    }
+
+    /// Runs the notification command in the background, writing the notification to its stdin
+    fn run_command(&self, notification: &Notification) -> Result<JoinHandle<()>> {
+        let Some((command, args)) = self.command.split_first() else {
+            bail!("No notification command has been set");
+        };
+
+        let json = serde_json::to_string(notification)?;
+
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        // Waiting for the command would freeze the terminal, so it is left to another thread
+        Ok(thread::spawn(move || {
+            if let Some(mut stdin) = child.stdin.take() {
+                if let Err(err) = stdin.write_all(json.as_bytes()) {
+                    error!("Failed to write to notification command: {err}");
+                }
+            }
+
+            if let Err(err) = child.wait() {
+                error!("Failed to wait for notification command: {err}");
+            }
+        }))
+    }
+}
+
+pub fn ring_bell() -> Result<()> {
+    write_escape_sequence("\x07")
+}
+
+fn write_escape_sequence(sequence: &str) -> Result<()> {
+    let mut stdout = stdout();
+
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()?;
+
+    Ok(())
+}
+
+/// Removes anything that would end an OSC escape sequence early, or split one of its fields
+fn escape_osc_text(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if c == ';' { ',' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(not(target_os = "windows"))]
+mod tests {
+    use std::fs::read_to_string;
+
+    use super::*;
+
+    fn setup_notifier(command: Vec<String>) -> Notifier {
+        Notifier::new(
+            &NotificationsConfig {
+                enabled: true,
+                methods: vec![NotificationMethod::Command],
+                command,
+                keywords: vec!["Giveaway".to_string()],
+                ..NotificationsConfig::default()
+            },
+            "me",
+        )
+    }
+
+    fn setup_message(author: &str, payload: &str) -> RawMessageData {
+        RawMessageData::builder()
+            .author(author.to_string())
+            .system(false)
+            .payload(payload.to_string())
+            .emotes(BTreeMap::new())
+            .highlight(false)
+            .build()
+    }
+
+    #[test]
+    fn test_notification_reasons() {
+        let notifier = setup_notifier(vec![]);
+
+        let mut mention = setup_message("viewer32", "hi @me");
+        mention.ping = true;
+
+        let mut raid = setup_message("Twitch", "viewer32 is raiding with a party of 5.");
+        raid.system = true;
+        raid.notice = Some(ChatNotice::Raid);
+
+        assert_eq!(
+            notifier.reason(&mention, false),
+            Some(NotificationReason::Mention)
+        );
+        assert_eq!(
+            notifier.reason(&setup_message("viewer32", "the GIVEAWAY starts now"), false),
+            Some(NotificationReason::Keyword)
+        );
+        assert_eq!(
+            notifier.reason(&setup_message("viewer32", "ban evasion"), true),
+            Some(NotificationReason::Highlight)
+        );
+        assert_eq!(
+            notifier.reason(&raid, false),
+            Some(NotificationReason::Raid)
+        );
+        assert_eq!(
+            notifier.reason(&setup_message("viewer32", "hello"), false),
+            None
+        );
+        assert_eq!(
+            notifier.reason(&setup_message("me", "giveaway"), true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_command_receives_json() {
+        let directory = tempfile::tempdir().unwrap();
+        let output = directory.path().join("notification.json");
+
+        let notifier = setup_notifier(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("cat > '{}'", output.display()),
+        ]);
+
+        let notification = Notification {
+            reason: NotificationReason::Keyword,
+            message: ChatLogEntry::new("bar", &setup_message("viewer32", "giveaway")),
+        };
+
+        notifier.run_command(&notification).unwrap().join().unwrap();
+
+        let written: Notification = serde_json::from_str(&read_to_string(output).unwrap()).unwrap();
+
+        assert_eq!(written, notification);
+    }
+
+    #[test]
+    fn test_escape_osc_text() {
+        assert_eq!(escape_osc_text("a;b\x07c\x1bd"), "a,bcd");
+    }
+}
@@ -0,0 +1,233 @@
+use std::{cell::RefCell, rc::Rc};
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+pub type SharedPolls = Rc<RefCell<Polls>>;
+
+/// How long a poll or prediction stays on screen after it has ended, so that its result can be seen
+const ENDED_POLL_DURATION: TimeDelta = TimeDelta::seconds(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollKind {
+    Poll,
+    Prediction,
+}
+
+impl PollKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Poll => "Poll",
+            Self::Prediction => "Prediction",
+        }
+    }
+
+    /// What the amount of each choice is counted in
+    pub const fn unit(self) -> &'static str {
+        match self {
+            Self::Poll => "votes",
+            Self::Prediction => "points",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PollStatus {
+    /// Can still be voted in, until the given time if it's known
+    Active(Option<DateTime<Utc>>),
+    /// No longer accepts votes, but hasn't ended yet
+    Locked,
+    /// Has ended, along with how it ended, such as "completed" or "canceled"
+    Ended {
+        outcome: String,
+        ended_at: DateTime<Utc>,
+    },
+}
+
+/// A choice of a poll, or an outcome of a prediction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveChoice {
+    pub title: String,
+    /// Votes for a poll, or channel points for a prediction
+    pub amount: u64,
+    /// How many viewers made a prediction, since it can't be told from the channel points alone
+    pub users: Option<u64>,
+    pub winner: bool,
+}
+
+/// A poll or prediction as it currently stands, updated whenever Twitch sends an event about it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LivePoll {
+    pub kind: PollKind,
+    pub id: String,
+    pub channel: String,
+    pub title: String,
+    pub choices: Vec<LiveChoice>,
+    pub status: PollStatus,
+}
+
+impl LivePoll {
+    pub fn total(&self) -> u64 {
+        self.choices.iter().map(|choice| choice.amount).sum()
+    }
+
+    /// The share of the total that a choice has, from 0 to 1
+    pub fn share(&self, choice: &LiveChoice) -> f64 {
+        let total = self.total();
+
+        if total == 0 {
+            return 0.0;
+        }
+
+        choice.amount as f64 / total as f64
+    }
+
+    const fn has_ended(&self) -> bool {
+        matches!(self.status, PollStatus::Ended { .. })
+    }
+}
+
+/// The polls and predictions of every joined channel
+#[derive(Debug, Default)]
+pub struct Polls {
+    polls: Vec<LivePoll>,
+}
+
+impl Polls {
+    /// Adds a poll or prediction, replacing the previous one of the same kind in its channel
+    pub fn update(&mut self, poll: LivePoll) {
+        // Events can arrive out of order, and an ended poll should never come back
+        if self
+            .polls
+            .iter()
+            .any(|previous| previous.id == poll.id && previous.has_ended() && !poll.has_ended())
+        {
+            return;
+        }
+
+        self.polls.retain(|previous| {
+            previous.kind != poll.kind || !previous.channel.eq_ignore_ascii_case(&poll.channel)
+        });
+        self.polls.push(poll);
+    }
+
+    /// The polls and predictions of a channel that are still running, or that have only just ended
+    pub fn visible(&self, channel: &str, now: DateTime<Utc>) -> Vec<&LivePoll> {
+        self.polls
+            .iter()
+            .filter(|poll| poll.channel.eq_ignore_ascii_case(channel))
+            .filter(|poll| match poll.status {
+                PollStatus::Ended { ended_at, .. } => now - ended_at < ENDED_POLL_DURATION,
+                _ => true,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live_poll(kind: PollKind, id: &str, channel: &str, status: PollStatus) -> LivePoll {
+        LivePoll {
+            kind,
+            id: id.to_string(),
+            channel: channel.to_string(),
+            title: "Best fruit?".to_string(),
+            choices: vec![
+                LiveChoice {
+                    title: "Apple".to_string(),
+                    amount: 3,
+                    users: None,
+                    winner: false,
+                },
+                LiveChoice {
+                    title: "Banana".to_string(),
+                    amount: 1,
+                    users: None,
+                    winner: false,
+                },
+            ],
+            status,
+        }
+    }
+
+    #[test]
+    fn test_choice_share() {
+        let poll = live_poll(PollKind::Poll, "1", "first", PollStatus::Locked);
+
+        assert_eq!(poll.total(), 4);
+        assert!((poll.share(&poll.choices[0]) - 0.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_one_poll_of_each_kind_per_channel() {
+        let mut polls = Polls::default();
+
+        polls.update(live_poll(PollKind::Poll, "1", "first", PollStatus::Locked));
+        polls.update(live_poll(PollKind::Poll, "2", "first", PollStatus::Locked));
+        polls.update(live_poll(
+            PollKind::Prediction,
+            "3",
+            "first",
+            PollStatus::Locked,
+        ));
+        polls.update(live_poll(PollKind::Poll, "4", "second", PollStatus::Locked));
+
+        let ids = polls
+            .visible("FIRST", Utc::now())
+            .iter()
+            .map(|poll| poll.id.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec!["2", "3"]);
+    }
+
+    #[test]
+    fn test_ended_polls_are_hidden_after_a_while() {
+        let mut polls = Polls::default();
+        let ended_at = Utc::now();
+
+        polls.update(live_poll(
+            PollKind::Poll,
+            "1",
+            "first",
+            PollStatus::Ended {
+                outcome: "completed".to_string(),
+                ended_at,
+            },
+        ));
+
+        assert_eq!(polls.visible("first", ended_at).len(), 1);
+        assert!(
+            polls
+                .visible("first", ended_at + ENDED_POLL_DURATION)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_ended_poll_is_not_reopened() {
+        let mut polls = Polls::default();
+
+        polls.update(live_poll(
+            PollKind::Poll,
+            "1",
+            "first",
+            PollStatus::Ended {
+                outcome: "completed".to_string(),
+                ended_at: Utc::now(),
+            },
+        ));
+        polls.update(live_poll(
+            PollKind::Poll,
+            "1",
+            "first",
+            PollStatus::Active(None),
+        ));
+
+        assert!(matches!(
+            polls.visible("first", Utc::now())[0].status,
+            PollStatus::Ended { .. }
+        ));
+    }
+}
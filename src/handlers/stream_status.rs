@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+
+/// What is known about the stream of a channel, kept up to date by Twitch's events and by polling for viewers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamStatus {
+    /// When the stream went live, or none while the channel is offline
+    pub started_at: Option<DateTime<Utc>>,
+    /// How many viewers were watching the last time the stream was polled, only known while live
+    pub viewers: Option<u64>,
+    pub title: String,
+    pub category: String,
+}
+
+impl StreamStatus {
+    pub const fn is_live(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    pub const fn go_live(&mut self, started_at: DateTime<Utc>) {
+        self.started_at = Some(started_at);
+    }
+
+    pub const fn go_offline(&mut self) {
+        self.started_at = None;
+        self.viewers = None;
+    }
+
+    /// How long the stream has been live for, such as `2h 05m`
+    pub fn uptime(&self, now: DateTime<Utc>) -> Option<String> {
+        let minutes = (now - self.started_at?).num_minutes().max(0);
+
+        Some(if minutes < 60 {
+            format!("{minutes}m")
+        } else {
+            format!("{}h {:02}m", minutes / 60, minutes % 60)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    #[test]
+    fn test_uptime() {
+        let now = Utc::now();
+        let mut status = StreamStatus::default();
+
+        assert_eq!(status.uptime(now), None);
+
+        status.go_live(now - TimeDelta::minutes(42));
+        assert_eq!(status.uptime(now).as_deref(), Some("42m"));
+
+        status.go_live(now - TimeDelta::minutes(125));
+        assert_eq!(status.uptime(now).as_deref(), Some("2h 05m"));
+    }
+
+    #[test]
+    fn test_go_offline_forgets_viewers() {
+        let mut status = StreamStatus {
+            started_at: Some(Utc::now()),
+            viewers: Some(1337),
+            title: "Speedrunning".to_string(),
+            category: "Celeste".to_string(),
+        };
+
+        status.go_offline();
+
+        assert!(!status.is_live());
+        assert_eq!(status.viewers, None);
+        assert_eq!(status.title, "Speedrunning");
+    }
+}
@@ -0,0 +1,179 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use chrono::{DateTime, Local};
+
+pub type SharedWhispers = Rc<RefCell<Whispers>>;
+
+/// A single whisper, either received from or sent to another user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Whisper {
+    pub time_sent: DateTime<Local>,
+    /// The other user in the conversation, regardless of who sent the whisper
+    pub user: String,
+    /// Whether the whisper was sent by the current user
+    pub sent: bool,
+    pub payload: String,
+}
+
+/// The whispers exchanged with a single user
+#[derive(Debug, Clone)]
+pub struct Conversation {
+    user: String,
+    messages: VecDeque<Whisper>,
+    unread: usize,
+}
+
+impl Conversation {
+    const fn new(user: String) -> Self {
+        Self {
+            user,
+            messages: VecDeque::new(),
+            unread: 0,
+        }
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Whispers in the order that they were sent, oldest first
+    pub const fn messages(&self) -> &VecDeque<Whisper> {
+        &self.messages
+    }
+
+    pub const fn unread(&self) -> usize {
+        self.unread
+    }
+}
+
+/// Every whisper conversation of this session, kept separate from the channels so that they survive switching between them
+#[derive(Debug)]
+pub struct Whispers {
+    /// Conversations with the most recently active one first
+    conversations: Vec<Conversation>,
+    maximum_messages: usize,
+}
+
+impl Whispers {
+    pub const fn new(maximum_messages: usize) -> Self {
+        Self {
+            conversations: vec![],
+            maximum_messages,
+        }
+    }
+
+    pub fn conversations(&self) -> &[Conversation] {
+        &self.conversations
+    }
+
+    pub fn conversation(&self, user: &str) -> Option<&Conversation> {
+        self.conversations
+            .iter()
+            .find(|conversation| conversation.user.eq_ignore_ascii_case(user))
+    }
+
+    /// Adds a whisper to the conversation with its user, moving that conversation to the front
+    pub fn push(&mut self, whisper: Whisper) {
+        let mut conversation = self
+            .conversations
+            .iter()
+            .position(|conversation| conversation.user.eq_ignore_ascii_case(&whisper.user))
+            .map_or_else(
+                || Conversation::new(whisper.user.clone()),
+                |index| self.conversations.remove(index),
+            );
+
+        if !whisper.sent {
+            conversation.unread += 1;
+        }
+
+        if conversation.messages.len() >= self.maximum_messages {
+            conversation.messages.pop_front();
+        }
+
+        conversation.messages.push_back(whisper);
+
+        self.conversations.insert(0, conversation);
+    }
+
+    pub fn mark_read(&mut self, user: &str) {
+        if let Some(conversation) = self
+            .conversations
+            .iter_mut()
+            .find(|conversation| conversation.user.eq_ignore_ascii_case(user))
+        {
+            conversation.unread = 0;
+        }
+    }
+
+    /// How many received whispers haven't been looked at, across all conversations
+    pub fn unread(&self) -> usize {
+        self.conversations.iter().map(Conversation::unread).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whisper(user: &str, sent: bool, payload: &str) -> Whisper {
+        Whisper {
+            time_sent: Local::now(),
+            user: user.to_string(),
+            sent,
+            payload: payload.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_most_recent_conversation_first() {
+        let mut whispers = Whispers::new(10);
+
+        whispers.push(whisper("first", false, "hello"));
+        whispers.push(whisper("second", false, "hi"));
+        whispers.push(whisper("First", true, "hey"));
+
+        let users = whispers
+            .conversations()
+            .iter()
+            .map(Conversation::user)
+            .collect::<Vec<_>>();
+
+        assert_eq!(users, vec!["first", "second"]);
+        assert_eq!(whispers.conversation("first").unwrap().messages().len(), 2);
+    }
+
+    #[test]
+    fn test_unread_whispers() {
+        let mut whispers = Whispers::new(10);
+
+        whispers.push(whisper("first", false, "hello"));
+        whispers.push(whisper("first", false, "are you there?"));
+        whispers.push(whisper("second", true, "hi"));
+
+        assert_eq!(whispers.unread(), 2);
+
+        whispers.mark_read("FIRST");
+
+        assert_eq!(whispers.unread(), 0);
+    }
+
+    #[test]
+    fn test_maximum_messages_per_conversation() {
+        let mut whispers = Whispers::new(2);
+
+        for payload in ["one", "two", "three"] {
+            whispers.push(whisper("first", false, payload));
+        }
+
+        let payloads = whispers
+            .conversation("first")
+            .unwrap()
+            .messages()
+            .iter()
+            .map(|whisper| whisper.payload.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(payloads, vec!["two", "three"]);
+    }
+}
@@ -31,7 +31,10 @@ use crate::{
     config::CoreConfig,
     emotes::{Emotes, initialize_emote_decoder},
     events::{Event, Events, TwitchAction},
-    twitch::{oauth::TwitchOauth, websocket::TwitchWebsocket},
+    twitch::{
+        api::set_twitch_api_base_url, oauth::TwitchOauth, recorder::FrameRecorder,
+        replay::TwitchReplay, websocket::TwitchWebsocket,
+    },
 };
 
 mod app;
@@ -50,7 +53,12 @@ mod utils;
 async fn main() -> Result<()> {
     color_eyre::install()?;
 
-    let mut config = CoreConfig::new(Cli::parse()).wrap_err("Configuration error.")?;
+    let cli = Cli::parse();
+    let replay = cli.replay.clone();
+    let replay_speed = cli.replay_speed;
+    let record = cli.record.clone();
+
+    let mut config = CoreConfig::new(cli).wrap_err("Configuration error.")?;
 
     initialize_logging(&config).wrap_err("Failed to initialize logger")?;
 
@@ -61,9 +69,15 @@ async fn main() -> Result<()> {
 
     let emotes = initialize_emote_decoder(&mut config);
 
+    set_twitch_api_base_url(&config.twitch.api_url);
+
     let config = Arc::new(config);
 
-    let twitch_oauth = TwitchOauth::default().init(config.clone()).await?;
+    let twitch_oauth = if replay.is_some() {
+        TwitchOauth::default()
+    } else {
+        TwitchOauth::default().init(config.clone()).await?
+    };
     let emotes_enabled = config.frontend.is_emotes_enabled();
     let context_emotes = Rc::new(Emotes::new(emotes_enabled));
 
@@ -86,7 +100,27 @@ async fn main() -> Result<()> {
         decoded_emotes_rx,
     );
 
-    TwitchWebsocket::new(config.clone(), twitch_oauth, event_tx.clone(), twitch_rx);
+    if let Some(replay) = replay {
+        TwitchReplay::new(
+            config.clone(),
+            replay,
+            replay_speed,
+            event_tx.clone(),
+            twitch_rx,
+        );
+    } else {
+        let recorder = record
+            .map(|path| FrameRecorder::new(&path, config.twitch.token.as_deref()))
+            .transpose()?;
+
+        TwitchWebsocket::new(
+            config.clone(),
+            twitch_oauth,
+            event_tx.clone(),
+            twitch_rx,
+            recorder,
+        );
+    }
 
     let terminal = init_terminal(&config.frontend);
     app.run(terminal).await?;
@@ -0,0 +1,64 @@
+use std::fmt;
+
+use color_eyre::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::TWITCH_API_BASE_URL;
+
+/// What a moderator does with a message that `AutoMod` held
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum AutoModAction {
+    /// The message is sent to chat
+    Allow,
+    /// The message is thrown away
+    Deny,
+}
+
+impl fmt::Display for AutoModAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Allow => "approved",
+                Self::Deny => "denied",
+            }
+        )
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ManageHeldMessagePayload {
+    /// The moderator who is approving or denying the message
+    user_id: String,
+    msg_id: String,
+    action: AutoModAction,
+}
+
+impl ManageHeldMessagePayload {
+    pub const fn new(user_id: String, msg_id: String, action: AutoModAction) -> Self {
+        Self {
+            user_id,
+            msg_id,
+            action,
+        }
+    }
+}
+
+/// Approves or denies a message that `AutoMod` held for review
+///
+/// <https://dev.twitch.tv/docs/api/reference/#manage-held-automod-messages>
+pub async fn manage_held_message(client: &Client, payload: ManageHeldMessagePayload) -> Result<()> {
+    let url = format!("{TWITCH_API_BASE_URL}/moderation/automod/message");
+
+    client
+        .post(url)
+        .json(&payload)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
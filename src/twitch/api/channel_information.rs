@@ -38,6 +38,16 @@ pub struct ChannelInformationResponse {
     is_branded_content: bool,
 }
 
+impl ChannelInformationResponse {
+    pub const fn title(&self) -> &String {
+        &self.title
+    }
+
+    pub const fn game_name(&self) -> &String {
+        &self.game_name
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct ChannelInformationResponseList {
     data: Vec<ChannelInformationResponse>,
@@ -92,7 +102,6 @@ pub async fn get_game_id(client: &Client, game_name: &str) -> Result<String> {
 /// Gets information about a channel
 ///
 /// <https://dev.twitch.tv/docs/api/reference/#get-channel-information>
-#[allow(unused)]
 pub async fn get_channel_information(
     client: &Client,
     broadcaster_id: String,
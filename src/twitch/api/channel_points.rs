@@ -0,0 +1,168 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{ResponseList, TWITCH_API_BASE_URL};
+
+/// How many unfulfilled redemptions of a single reward are fetched at a time
+const REDEMPTION_COUNT: usize = 50;
+
+/// A reward that viewers can spend channel points on
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct CustomReward {
+    pub id: String,
+    pub title: String,
+    pub cost: u64,
+}
+
+/// A viewer spending their channel points on a reward
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Redemption {
+    pub id: String,
+    pub broadcaster_id: String,
+    pub user_name: String,
+    #[serde(default)]
+    pub user_input: String,
+    pub redeemed_at: DateTime<Utc>,
+    pub reward: CustomReward,
+}
+
+impl fmt::Display for Redemption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<25.25} {:<30.30} {}",
+            self.user_name, self.reward.title, self.user_input
+        )
+    }
+}
+
+/// What happens to a redemption once it leaves the queue
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RedemptionStatus {
+    /// The reward was given, so the viewer keeps their points spent
+    Fulfilled,
+    /// The reward wasn't given, so the viewer gets their points back
+    Canceled,
+}
+
+impl fmt::Display for RedemptionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Fulfilled => "fulfilled",
+                Self::Canceled => "canceled",
+            }
+        )
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RedemptionQuery {
+    id: String,
+    broadcaster_id: String,
+    reward_id: String,
+}
+
+impl RedemptionQuery {
+    pub const fn new(id: String, broadcaster_id: String, reward_id: String) -> Self {
+        Self {
+            id,
+            broadcaster_id,
+            reward_id,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct RedemptionStatusPayload {
+    status: RedemptionStatus,
+}
+
+/// Gets the custom rewards of a channel, which only the broadcaster is allowed to do.
+/// Redemptions can only be managed for the rewards that were made with the same client ID as the token,
+/// so `only_manageable_rewards` leaves out the ones made elsewhere, such as on the Twitch dashboard.
+///
+/// <https://dev.twitch.tv/docs/api/reference/#get-custom-reward>
+pub async fn get_custom_rewards(
+    client: &Client,
+    broadcaster_id: &str,
+    only_manageable_rewards: bool,
+) -> Result<Vec<CustomReward>> {
+    let rewards = client
+        .get(format!(
+            "{TWITCH_API_BASE_URL}/channel_points/custom_rewards"
+        ))
+        .query(&[
+            ("broadcaster_id", broadcaster_id),
+            (
+                "only_manageable_rewards",
+                &only_manageable_rewards.to_string(),
+            ),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<CustomReward>>()
+        .await?
+        .data;
+
+    Ok(rewards)
+}
+
+/// Gets the redemptions of a reward that are still waiting in the queue, oldest first
+///
+/// <https://dev.twitch.tv/docs/api/reference/#get-custom-reward-redemption>
+pub async fn get_unfulfilled_redemptions(
+    client: &Client,
+    broadcaster_id: &str,
+    reward_id: &str,
+) -> Result<Vec<Redemption>> {
+    let redemptions = client
+        .get(format!(
+            "{TWITCH_API_BASE_URL}/channel_points/custom_rewards/redemptions"
+        ))
+        .query(&[
+            ("broadcaster_id", broadcaster_id),
+            ("reward_id", reward_id),
+            ("status", "UNFULFILLED"),
+            ("sort", "OLDEST"),
+            ("first", &REDEMPTION_COUNT.to_string()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<Redemption>>()
+        .await?
+        .data;
+
+    Ok(redemptions)
+}
+
+/// Takes a redemption out of the queue, either fulfilling or canceling it.
+/// Twitch only allows this for rewards that were created with the same client ID as the token.
+///
+/// <https://dev.twitch.tv/docs/api/reference/#update-redemption-status>
+pub async fn update_redemption_status(
+    client: &Client,
+    query: RedemptionQuery,
+    status: RedemptionStatus,
+) -> Result<()> {
+    client
+        .patch(format!(
+            "{TWITCH_API_BASE_URL}/channel_points/custom_rewards/redemptions"
+        ))
+        .query(&query)
+        .json(&RedemptionStatusPayload { status })
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
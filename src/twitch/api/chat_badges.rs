@@ -0,0 +1,61 @@
+use color_eyre::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{ResponseList, TWITCH_API_BASE_URL};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ChatBadgeVersion {
+    pub id: String,
+    pub image_url_1x: String,
+}
+
+impl ChatBadgeVersion {
+    /// The ID of the badge's image, which stays the same for as long as the image does
+    pub fn image_id(&self) -> Option<&str> {
+        self.image_url_1x
+            .strip_prefix("https://static-cdn.jtvnw.net/badges/v1/")?
+            .split('/')
+            .next()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ChatBadgeSet {
+    pub set_id: String,
+    pub versions: Vec<ChatBadgeVersion>,
+}
+
+async fn get_chat_badges(client: &Client, url: String) -> Result<Vec<ChatBadgeSet>> {
+    let badge_sets = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<ChatBadgeSet>>()
+        .await?
+        .data;
+
+    Ok(badge_sets)
+}
+
+/// Gets the badges that can be shown in any channel, such as moderator or partner badges
+///
+/// <https://dev.twitch.tv/docs/api/reference/#get-global-chat-badges>
+pub async fn get_global_chat_badges(client: &Client) -> Result<Vec<ChatBadgeSet>> {
+    get_chat_badges(client, format!("{TWITCH_API_BASE_URL}/chat/badges/global")).await
+}
+
+/// Gets the badges that a broadcaster made for their own channel, such as subscriber and bits badges
+///
+/// <https://dev.twitch.tv/docs/api/reference/#get-channel-chat-badges>
+pub async fn get_channel_chat_badges(
+    client: &Client,
+    broadcaster_id: &str,
+) -> Result<Vec<ChatBadgeSet>> {
+    get_chat_badges(
+        client,
+        format!("{TWITCH_API_BASE_URL}/chat/badges?broadcaster_id={broadcaster_id}"),
+    )
+    .await
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use color_eyre::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{ResponseList, TWITCH_API_BASE_URL};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CheermoteImageFormats {
+    /// Image URLs by scale, such as `1` or `1.5`
+    pub animated: HashMap<String, String>,
+    #[serde(rename = "static")]
+    pub static_: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CheermoteImages {
+    pub dark: CheermoteImageFormats,
+    pub light: CheermoteImageFormats,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CheermoteTier {
+    pub min_bits: u64,
+    pub id: String,
+    pub color: String,
+    pub images: CheermoteImages,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Cheermote {
+    pub prefix: String,
+    pub tiers: Vec<CheermoteTier>,
+}
+
+impl Cheermote {
+    /// The tier of a cheermote fragment, which Twitch identifies by the minimum amount of bits in it
+    pub fn tier(&self, tier: usize) -> Option<&CheermoteTier> {
+        let tier = tier.to_string();
+
+        self.tiers
+            .iter()
+            .find(|cheermote_tier| cheermote_tier.id == tier)
+    }
+}
+
+/// Gets the global cheermotes, along with the ones that the broadcaster made for their own channel
+///
+/// <https://dev.twitch.tv/docs/api/reference/#get-cheermotes>
+pub async fn get_cheermotes(client: &Client, broadcaster_id: &str) -> Result<Vec<Cheermote>> {
+    let cheermotes = client
+        .get(format!(
+            "{TWITCH_API_BASE_URL}/bits/cheermotes?broadcaster_id={broadcaster_id}"
+        ))
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<Cheermote>>()
+        .await?
+        .data;
+
+    Ok(cheermotes)
+}
@@ -25,6 +25,37 @@ pub static INITIAL_EVENT_SUBSCRIPTIONS: &[Subscription] = &[
     Subscription::MessageDelete,
 ];
 
+/// Events about the user rather than a channel, which are subscribed to once per session
+pub static USER_EVENT_SUBSCRIPTIONS: &[Subscription] = &[Subscription::Whisper];
+
+/// Events that only the broadcaster can subscribe to, which are subscribed to when the user joins their own channel
+pub static BROADCASTER_EVENT_SUBSCRIPTIONS: &[Subscription] = &[Subscription::Redemption];
+
+/// Events about polls and predictions, which only the broadcaster can subscribe to.
+/// These need scopes of their own, so they're subscribed to separately from the other broadcaster events.
+pub static POLL_EVENT_SUBSCRIPTIONS: &[Subscription] = &[
+    Subscription::PollBegin,
+    Subscription::PollProgress,
+    Subscription::PollEnd,
+    Subscription::PredictionBegin,
+    Subscription::PredictionProgress,
+    Subscription::PredictionLock,
+    Subscription::PredictionEnd,
+];
+
+/// Events about whether a channel is live and what it's streaming, which anyone can subscribe to
+pub static STREAM_EVENT_SUBSCRIPTIONS: &[Subscription] = &[
+    Subscription::StreamOnline,
+    Subscription::StreamOffline,
+    Subscription::ChannelUpdate,
+];
+
+/// Events about messages held by `AutoMod`, which can only be subscribed to in channels that the user moderates
+pub static AUTOMOD_EVENT_SUBSCRIPTIONS: &[Subscription] = &[
+    Subscription::AutoModMessageHold,
+    Subscription::AutoModMessageUpdate,
+];
+
 /// Subscribe to a set of events, returning a hashmap of subscription types corresponding to their ID
 ///
 /// <https://dev.twitch.tv/docs/api/reference/#create-eventsub-subscription>
@@ -45,13 +76,88 @@ pub async fn subscribe_to_events(
 ) -> Result<HashMap<Subscription, String>> {
     let session_id = session_id.context("Session ID is empty")?;
 
-    let url = format!("{TWITCH_API_BASE_URL}/eventsub/subscriptions");
+    let user_id = oauth
+        .user_id()
+        .context("Faield to get user ID from twitch OAuth context")?;
+
+    create_subscriptions(
+        client,
+        ReceivedTwitchSubscription::new(channel_id, user_id, session_id),
+        subscription_types,
+    )
+    .await
+}
+
+/// Subscribe to events about the user rather than a channel, such as whispers
+pub async fn subscribe_to_user_events(
+    client: &Client,
+    oauth: &TwitchOauth,
+    session_id: Option<String>,
+    subscription_types: Vec<Subscription>,
+) -> Result<HashMap<Subscription, String>> {
+    // Without a channel, the subscription condition only has the user in it
+    subscribe_to_events(client, oauth, session_id, String::new(), subscription_types).await
+}
 
+/// Subscribe to events on the user's own channel that only the broadcaster can see, such as channel point redemptions
+pub async fn subscribe_to_broadcaster_events(
+    client: &Client,
+    oauth: &TwitchOauth,
+    session_id: Option<String>,
+    subscription_types: Vec<Subscription>,
+) -> Result<HashMap<Subscription, String>> {
     let user_id = oauth
         .user_id()
         .context("Faield to get user ID from twitch OAuth context")?;
 
-    let mut subscription = ReceivedTwitchSubscription::new(channel_id, user_id, session_id);
+    subscribe_to_channel_events(client, session_id, user_id, subscription_types).await
+}
+
+/// Subscribe to events of a channel whose condition only has the broadcaster in it, such as the stream going live
+pub async fn subscribe_to_channel_events(
+    client: &Client,
+    session_id: Option<String>,
+    channel_id: String,
+    subscription_types: Vec<Subscription>,
+) -> Result<HashMap<Subscription, String>> {
+    let session_id = session_id.context("Session ID is empty")?;
+
+    create_subscriptions(
+        client,
+        ReceivedTwitchSubscription::new(channel_id, String::new(), session_id),
+        subscription_types,
+    )
+    .await
+}
+
+/// Subscribe to events of a channel that only its moderators can see, such as messages held by `AutoMod`
+pub async fn subscribe_to_moderator_events(
+    client: &Client,
+    oauth: &TwitchOauth,
+    session_id: Option<String>,
+    channel_id: String,
+    subscription_types: Vec<Subscription>,
+) -> Result<HashMap<Subscription, String>> {
+    let session_id = session_id.context("Session ID is empty")?;
+
+    let user_id = oauth
+        .user_id()
+        .context("Failed to get user ID from twitch OAuth context")?;
+
+    create_subscriptions(
+        client,
+        ReceivedTwitchSubscription::moderator(channel_id, user_id, session_id),
+        subscription_types,
+    )
+    .await
+}
+
+async fn create_subscriptions(
+    client: &Client,
+    mut subscription: ReceivedTwitchSubscription,
+    subscription_types: Vec<Subscription>,
+) -> Result<HashMap<Subscription, String>> {
+    let url = format!("{TWITCH_API_BASE_URL}/eventsub/subscriptions");
 
     let mut subscription_map = HashMap::new();
 
@@ -11,14 +11,22 @@ pub struct NewTwitchMessage {
     #[serde(rename = "sender_id")]
     user_id: String,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_parent_message_id: Option<String>,
 }
 
 impl NewTwitchMessage {
-    pub const fn new(broadcaster_id: String, user_id: String, message: String) -> Self {
+    pub const fn new(
+        broadcaster_id: String,
+        user_id: String,
+        message: String,
+        reply_parent_message_id: Option<String>,
+    ) -> Self {
         Self {
             channel_id: broadcaster_id,
             user_id,
             message,
+            reply_parent_message_id,
         }
     }
 }
@@ -1,23 +1,62 @@
+pub mod automod;
 pub mod channel_information;
+pub mod channel_points;
 pub mod channels;
+pub mod chat_badges;
 pub mod chat_settings;
+pub mod cheermotes;
 pub mod clear;
 pub mod commercial;
 pub mod event_sub;
 pub mod following;
 pub mod messages;
 pub mod mods;
+pub mod polls;
+pub mod predictions;
 pub mod raids;
 pub mod shoutouts;
+pub mod streams;
 pub mod subscriptions;
 pub mod timeouts;
 pub mod vips;
+pub mod whispers;
+
+use std::{fmt, sync::OnceLock};
 
 use color_eyre::Result;
 use reqwest::{Client, Method};
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+const DEFAULT_TWITCH_API_BASE_URL: &str = "https://api.twitch.tv/helix";
+
+static CONFIGURED_TWITCH_API_BASE_URL: OnceLock<String> = OnceLock::new();
 
-pub static TWITCH_API_BASE_URL: &str = "https://api.twitch.tv/helix";
+/// The Helix API that every request is sent to.
+/// This is Twitch's own API, unless another one was set with [`set_twitch_api_base_url`].
+pub struct TwitchApiBaseUrl;
+
+impl fmt::Display for TwitchApiBaseUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            CONFIGURED_TWITCH_API_BASE_URL
+                .get()
+                .map_or(DEFAULT_TWITCH_API_BASE_URL, String::as_str),
+        )
+    }
+}
+
+pub static TWITCH_API_BASE_URL: TwitchApiBaseUrl = TwitchApiBaseUrl;
+
+/// Points every API request at a different server, such as a mock one for testing.
+/// Only the first call has any effect, since requests may already be in flight afterwards.
+pub fn set_twitch_api_base_url(url: &str) {
+    let url = url.trim_end_matches('/').to_string();
+
+    if CONFIGURED_TWITCH_API_BASE_URL.set(url).is_err() {
+        warn!("Twitch API base URL was already set, ignoring the new one");
+    }
+}
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct BroadcasterQuery {
@@ -0,0 +1,146 @@
+use color_eyre::{Result, eyre::ContextCompat};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{ResponseList, TWITCH_API_BASE_URL};
+
+/// One of the choices that viewers can vote for, both in the API and in poll events
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PollChoice {
+    pub id: String,
+    pub title: String,
+    /// Every vote for the choice, including the ones paid for with bits or channel points
+    #[serde(default)]
+    pub votes: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Poll {
+    pub id: String,
+    pub title: String,
+    pub choices: Vec<PollChoice>,
+    pub status: String,
+}
+
+impl Poll {
+    pub fn is_active(&self) -> bool {
+        self.status.eq_ignore_ascii_case("active")
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct PollChoicePayload {
+    title: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreatePollPayload {
+    broadcaster_id: String,
+    title: String,
+    choices: Vec<PollChoicePayload>,
+    /// How long the poll lasts, in seconds
+    duration: usize,
+}
+
+impl CreatePollPayload {
+    pub fn new(
+        broadcaster_id: String,
+        title: String,
+        choices: Vec<String>,
+        duration: usize,
+    ) -> Self {
+        Self {
+            broadcaster_id,
+            title,
+            choices: choices
+                .into_iter()
+                .map(|title| PollChoicePayload { title })
+                .collect(),
+            duration,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EndPollPayload {
+    broadcaster_id: String,
+    id: String,
+    /// Terminated polls show their results, unlike archived ones
+    status: String,
+}
+
+impl EndPollPayload {
+    pub fn new(broadcaster_id: String, id: String) -> Self {
+        Self {
+            broadcaster_id,
+            id,
+            status: "TERMINATED".to_string(),
+        }
+    }
+}
+
+/// Gets the poll that viewers can currently vote in, if there is one
+///
+/// <https://dev.twitch.tv/docs/api/reference/#get-polls>
+pub async fn get_active_poll(client: &Client, broadcaster_id: &str) -> Result<Option<Poll>> {
+    let url = format!("{TWITCH_API_BASE_URL}/polls");
+
+    // Polls are returned most recent first, and only one can be active at a time
+    let poll = client
+        .get(url)
+        .query(&[("broadcaster_id", broadcaster_id), ("first", "1")])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<Poll>>()
+        .await?
+        .data
+        .into_iter()
+        .find(Poll::is_active);
+
+    Ok(poll)
+}
+
+/// Starts a poll on the user's own channel
+///
+/// <https://dev.twitch.tv/docs/api/reference/#create-poll>
+pub async fn create_poll(client: &Client, payload: CreatePollPayload) -> Result<Poll> {
+    let url = format!("{TWITCH_API_BASE_URL}/polls");
+
+    let poll = client
+        .post(url)
+        .json(&payload)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<Poll>>()
+        .await?
+        .data
+        .into_iter()
+        .next()
+        .context("Could not get the created poll")?;
+
+    Ok(poll)
+}
+
+/// Ends a poll before its duration is up
+///
+/// <https://dev.twitch.tv/docs/api/reference/#end-poll>
+pub async fn end_poll(client: &Client, payload: EndPollPayload) -> Result<Poll> {
+    let url = format!("{TWITCH_API_BASE_URL}/polls");
+
+    let poll = client
+        .patch(url)
+        .json(&payload)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<Poll>>()
+        .await?
+        .data
+        .into_iter()
+        .next()
+        .context("Could not get the ended poll")?;
+
+    Ok(poll)
+}
@@ -0,0 +1,190 @@
+use color_eyre::{Result, eyre::ContextCompat};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{ResponseList, TWITCH_API_BASE_URL};
+
+/// One of the outcomes that viewers can spend channel points on, both in the API and in prediction events
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PredictionOutcome {
+    pub id: String,
+    pub title: String,
+    /// How many viewers predicted the outcome
+    #[serde(default)]
+    pub users: u64,
+    #[serde(default)]
+    pub channel_points: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Prediction {
+    pub id: String,
+    pub title: String,
+    pub outcomes: Vec<PredictionOutcome>,
+    pub status: String,
+}
+
+impl Prediction {
+    /// If the prediction hasn't been resolved or canceled yet, even if it no longer accepts predictions
+    pub fn is_current(&self) -> bool {
+        ["active", "locked"]
+            .iter()
+            .any(|status| self.status.eq_ignore_ascii_case(status))
+    }
+
+    /// Finds an outcome by its title, or by its position starting from one
+    pub fn outcome(&self, outcome: &str) -> Option<&PredictionOutcome> {
+        self.outcomes
+            .iter()
+            .find(|o| o.title.eq_ignore_ascii_case(outcome))
+            .or_else(|| {
+                outcome
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|number| number.checked_sub(1))
+                    .and_then(|index| self.outcomes.get(index))
+            })
+    }
+}
+
+/// What happens to a prediction when it's ended
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum PredictionEndStatus {
+    /// A winning outcome was chosen, and its viewers were paid out
+    Resolved,
+    /// Every viewer was refunded their channel points
+    Canceled,
+    /// Viewers can no longer make predictions, but it hasn't been resolved yet
+    Locked,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct PredictionOutcomePayload {
+    title: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreatePredictionPayload {
+    broadcaster_id: String,
+    title: String,
+    outcomes: Vec<PredictionOutcomePayload>,
+    /// How long viewers can make predictions for, in seconds
+    prediction_window: usize,
+}
+
+impl CreatePredictionPayload {
+    pub fn new(
+        broadcaster_id: String,
+        title: String,
+        outcomes: Vec<String>,
+        prediction_window: usize,
+    ) -> Self {
+        Self {
+            broadcaster_id,
+            title,
+            outcomes: outcomes
+                .into_iter()
+                .map(|title| PredictionOutcomePayload { title })
+                .collect(),
+            prediction_window,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EndPredictionPayload {
+    broadcaster_id: String,
+    id: String,
+    status: PredictionEndStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    winning_outcome_id: Option<String>,
+}
+
+impl EndPredictionPayload {
+    pub const fn new(
+        broadcaster_id: String,
+        id: String,
+        status: PredictionEndStatus,
+        winning_outcome_id: Option<String>,
+    ) -> Self {
+        Self {
+            broadcaster_id,
+            id,
+            status,
+            winning_outcome_id,
+        }
+    }
+}
+
+/// Gets the prediction that hasn't been resolved or canceled yet, if there is one
+///
+/// <https://dev.twitch.tv/docs/api/reference/#get-predictions>
+pub async fn get_current_prediction(
+    client: &Client,
+    broadcaster_id: &str,
+) -> Result<Option<Prediction>> {
+    let url = format!("{TWITCH_API_BASE_URL}/predictions");
+
+    // Predictions are returned most recent first, and only one can be running at a time
+    let prediction = client
+        .get(url)
+        .query(&[("broadcaster_id", broadcaster_id), ("first", "1")])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<Prediction>>()
+        .await?
+        .data
+        .into_iter()
+        .find(Prediction::is_current);
+
+    Ok(prediction)
+}
+
+/// Starts a prediction on the user's own channel
+///
+/// <https://dev.twitch.tv/docs/api/reference/#create-prediction>
+pub async fn create_prediction(
+    client: &Client,
+    payload: CreatePredictionPayload,
+) -> Result<Prediction> {
+    let url = format!("{TWITCH_API_BASE_URL}/predictions");
+
+    let prediction = client
+        .post(url)
+        .json(&payload)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<Prediction>>()
+        .await?
+        .data
+        .into_iter()
+        .next()
+        .context("Could not get the created prediction")?;
+
+    Ok(prediction)
+}
+
+/// Locks, resolves or cancels a prediction
+///
+/// <https://dev.twitch.tv/docs/api/reference/#end-prediction>
+pub async fn end_prediction(client: &Client, payload: EndPredictionPayload) -> Result<Prediction> {
+    let url = format!("{TWITCH_API_BASE_URL}/predictions");
+
+    let prediction = client
+        .patch(url)
+        .json(&payload)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<Prediction>>()
+        .await?
+        .data
+        .into_iter()
+        .next()
+        .context("Could not get the ended prediction")?;
+
+    Ok(prediction)
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{ResponseList, TWITCH_API_BASE_URL};
+
+/// A stream that is currently live
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Stream {
+    pub user_id: String,
+    pub game_name: String,
+    pub title: String,
+    pub viewer_count: u64,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Gets the streams of the given channels, leaving out the ones that aren't live
+///
+/// <https://dev.twitch.tv/docs/api/reference/#get-streams>
+pub async fn get_streams(client: &Client, user_ids: &[String]) -> Result<Vec<Stream>> {
+    let query = user_ids
+        .iter()
+        .map(|user_id| ("user_id", user_id.as_str()))
+        .chain([("type", "live")])
+        .collect::<Vec<_>>();
+
+    let streams = client
+        .get(format!("{TWITCH_API_BASE_URL}/streams"))
+        .query(&query)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ResponseList<Stream>>()
+        .await?
+        .data;
+
+    Ok(streams)
+}
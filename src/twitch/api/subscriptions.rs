@@ -44,10 +44,104 @@ pub enum Subscription {
     #[serde(rename = "channel.ban")]
     Ban,
 
+    /// The user receives a whisper from another user
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#userwhispermessage>
+    #[serde(rename = "user.whisper.message")]
+    Whisper,
+
+    /// A viewer redeems a custom channel points reward on the user's own channel
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelchannel_points_custom_reward_redemptionadd>
+    #[serde(rename = "channel.channel_points_custom_reward_redemption.add")]
+    Redemption,
+
+    /// A poll begins on the user's own channel
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelpollbegin>
+    #[serde(rename = "channel.poll.begin")]
+    PollBegin,
+
+    /// Viewers vote in a poll on the user's own channel
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelpollprogress>
+    #[serde(rename = "channel.poll.progress")]
+    PollProgress,
+
+    /// A poll ends on the user's own channel
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelpollend>
+    #[serde(rename = "channel.poll.end")]
+    PollEnd,
+
+    /// A prediction begins on the user's own channel
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelpredictionbegin>
+    #[serde(rename = "channel.prediction.begin")]
+    PredictionBegin,
+
+    /// Viewers make predictions on the user's own channel
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelpredictionprogress>
+    #[serde(rename = "channel.prediction.progress")]
+    PredictionProgress,
+
+    /// A prediction stops accepting predictions on the user's own channel
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelpredictionlock>
+    #[serde(rename = "channel.prediction.lock")]
+    PredictionLock,
+
+    /// A prediction is resolved or canceled on the user's own channel
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelpredictionend>
+    #[serde(rename = "channel.prediction.end")]
+    PredictionEnd,
+
+    /// The broadcaster starts a stream
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#streamonline>
+    #[serde(rename = "stream.online")]
+    StreamOnline,
+
+    /// The broadcaster stops a stream
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#streamoffline>
+    #[serde(rename = "stream.offline")]
+    StreamOffline,
+
+    /// The broadcaster changes the title or category of their stream
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelupdate>
+    #[serde(rename = "channel.update")]
+    ChannelUpdate,
+
+    /// `AutoMod` holds a message for review in a channel that the user moderates
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#automodmessagehold-v2>
+    #[serde(rename = "automod.message.hold")]
+    AutoModMessageHold,
+
+    /// A message held by `AutoMod` is approved, denied or expires
+    ///
+    /// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#automodmessageupdate-v2>
+    #[serde(rename = "automod.message.update")]
+    AutoModMessageUpdate,
+
     #[serde(other)]
     Unknown,
 }
 
+impl Subscription {
+    /// The version of the subscription type that events are expected in
+    pub const fn version(&self) -> &'static str {
+        match self {
+            Self::ChannelUpdate | Self::AutoModMessageHold | Self::AutoModMessageUpdate => "2",
+            _ => "1",
+        }
+    }
+}
+
 impl fmt::Display for Subscription {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let subscription_str = match self {
@@ -57,6 +151,20 @@ impl fmt::Display for Subscription {
             Self::ClearUserMessages => "channel.chat.clear_user_messages",
             Self::MessageDelete => "channel.chat.message_delete",
             Self::Ban => "channel.ban",
+            Self::Whisper => "user.whisper.message",
+            Self::Redemption => "channel.channel_points_custom_reward_redemption.add",
+            Self::PollBegin => "channel.poll.begin",
+            Self::PollProgress => "channel.poll.progress",
+            Self::PollEnd => "channel.poll.end",
+            Self::PredictionBegin => "channel.prediction.begin",
+            Self::PredictionProgress => "channel.prediction.progress",
+            Self::PredictionLock => "channel.prediction.lock",
+            Self::PredictionEnd => "channel.prediction.end",
+            Self::StreamOnline => "stream.online",
+            Self::StreamOffline => "stream.offline",
+            Self::ChannelUpdate => "channel.update",
+            Self::AutoModMessageHold => "automod.message.hold",
+            Self::AutoModMessageUpdate => "automod.message.update",
             Self::Unknown => "unknown",
         }
         .to_string();
@@ -76,6 +184,20 @@ impl FromStr for Subscription {
             "channel.chat.clear_user_messages" => Self::ClearUserMessages,
             "channel.chat.message_delete" => Self::MessageDelete,
             "channel.ban" => Self::Ban,
+            "user.whisper.message" => Self::Whisper,
+            "channel.channel_points_custom_reward_redemption.add" => Self::Redemption,
+            "channel.poll.begin" => Self::PollBegin,
+            "channel.poll.progress" => Self::PollProgress,
+            "channel.poll.end" => Self::PollEnd,
+            "channel.prediction.begin" => Self::PredictionBegin,
+            "channel.prediction.progress" => Self::PredictionProgress,
+            "channel.prediction.lock" => Self::PredictionLock,
+            "channel.prediction.end" => Self::PredictionEnd,
+            "stream.online" => Self::StreamOnline,
+            "stream.offline" => Self::StreamOffline,
+            "channel.update" => Self::ChannelUpdate,
+            "automod.message.hold" => Self::AutoModMessageHold,
+            "automod.message.update" => Self::AutoModMessageUpdate,
             _ => bail!("Subscription '{}' cannot be deserialized", s),
         };
 
@@ -0,0 +1,53 @@
+use color_eyre::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::TWITCH_API_BASE_URL;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WhisperQuery {
+    from_user_id: String,
+    to_user_id: String,
+}
+
+impl WhisperQuery {
+    pub const fn new(from_user_id: String, to_user_id: String) -> Self {
+        Self {
+            from_user_id,
+            to_user_id,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WhisperPayload {
+    message: String,
+}
+
+impl WhisperPayload {
+    pub const fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+/// Sends a whisper message to the specified user.
+/// The user sending it needs a verified phone number.
+///
+/// <https://dev.twitch.tv/docs/api/reference/#send-whisper>
+pub async fn send_whisper(
+    client: &Client,
+    query: WhisperQuery,
+    payload: WhisperPayload,
+) -> Result<()> {
+    let url = format!("{TWITCH_API_BASE_URL}/whispers");
+
+    client
+        .post(url)
+        .query(&query)
+        .json(&payload)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
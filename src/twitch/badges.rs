@@ -1,30 +1,102 @@
 use std::{collections::HashMap, sync::LazyLock};
 
+use serde::{Deserialize, Serialize};
+
 use super::models::ReceivedTwitchEventBadges;
 
-const VIP_BADGE: char = '\u{1F48E}';
+const BROADCASTER_BADGE: char = '\u{1F3A5}';
 const MODERATOR_BADGE: char = '\u{1F528}';
+const VIP_BADGE: char = '\u{1F48E}';
+const STAFF_BADGE: char = '\u{1F527}';
+const PARTNER_BADGE: char = '\u{2705}';
+const ARTIST_BADGE: char = '\u{1F3A8}';
+const FOUNDER_BADGE: char = '\u{1F31F}';
 const SUBSCRIBER_BADGE: char = '\u{2B50}';
+const SUB_GIFTER_BADGE: char = '\u{1F381}';
+const SUB_GIFT_LEADER_BADGE: char = '\u{1F3C5}';
+const BITS_BADGE: char = '\u{1F4A0}';
+const BITS_LEADER_BADGE: char = '\u{1F3C6}';
 const PRIME_GAMING_BADGE: char = '\u{1F451}';
+const TURBO_BADGE: char = '\u{26A1}';
 
+/// What badges look like on terminals that can't display their images, by set ID
 static BADGES: LazyLock<HashMap<&str, char>> = LazyLock::new(|| {
     HashMap::from_iter(vec![
-        ("vip", VIP_BADGE),
+        ("broadcaster", BROADCASTER_BADGE),
         ("moderator", MODERATOR_BADGE),
+        ("vip", VIP_BADGE),
+        ("staff", STAFF_BADGE),
+        ("partner", PARTNER_BADGE),
+        ("artist-badge", ARTIST_BADGE),
+        ("founder", FOUNDER_BADGE),
         ("subscriber", SUBSCRIBER_BADGE),
+        ("sub-gifter", SUB_GIFTER_BADGE),
+        ("sub-gift-leader", SUB_GIFT_LEADER_BADGE),
+        ("bits", BITS_BADGE),
+        ("bits-leader", BITS_LEADER_BADGE),
         ("premium", PRIME_GAMING_BADGE),
+        ("turbo", TURBO_BADGE),
     ])
 });
 
-pub fn retrieve_user_badges(badges: &Vec<ReceivedTwitchEventBadges>) -> String {
-    let mut badges_str = String::new();
+/// A badge that a chatter has in the channel that they sent a message in
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChatBadge {
+    /// The kind of badge, such as `subscriber`
+    pub set_id: String,
+    /// Which version of the badge it is, such as the tier of a bits badge
+    pub id: String,
+    /// Extra details about the badge, such as how many months a subscriber has been subscribed for
+    pub info: String,
+    /// The ID of the channel that the badge was shown in, whose own badges take the place of the global ones
+    pub channel_id: Option<String>,
+}
 
-    for badge in badges {
-        if let Some(badge_char) = BADGES.get(badge.set_id()) {
-            badges_str.push(*badge_char);
+impl ChatBadge {
+    pub fn new(badge: &ReceivedTwitchEventBadges, channel_id: Option<String>) -> Self {
+        Self {
+            set_id: badge.set_id().to_string(),
+            id: badge.id().to_string(),
+            info: badge.info().to_string(),
+            channel_id,
         }
     }
-    badges_str
+
+    /// The key that the badge's global image is stored under
+    pub fn key(&self) -> String {
+        badge_key(&self.set_id, &self.id)
+    }
+
+    /// The keys that the badge's image could be stored under, the channel's own version first
+    pub fn keys(&self) -> Vec<String> {
+        self.channel_id
+            .iter()
+            .map(|channel_id| channel_badge_key(channel_id, &self.set_id, &self.id))
+            .chain([self.key()])
+            .collect()
+    }
+
+    /// What to show in place of the badge's image, if it is a known badge.
+    /// Subscriber badges come with the amount of months, and bits badges with their tier.
+    pub fn text(&self) -> Option<String> {
+        let badge = BADGES.get(self.set_id.as_str())?;
+
+        let details = match self.set_id.as_str() {
+            "subscriber" | "founder" => self.info.as_str(),
+            "bits" => self.id.as_str(),
+            _ => "",
+        };
+
+        Some(format!("{badge}{details}"))
+    }
+}
+
+/// The key that the image of a version of a badge is stored under
+pub fn badge_key(set_id: &str, id: &str) -> String {
+    format!("{set_id}/{id}")
 }
 
-// TODO: Tests
+/// The key that the image of a version of a badge that a channel made for itself is stored under
+pub fn channel_badge_key(channel_id: &str, set_id: &str, id: &str) -> String {
+    format!("{channel_id}/{set_id}/{id}")
+}
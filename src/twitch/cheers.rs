@@ -0,0 +1,26 @@
+use tui::style::{Color, Color::Rgb};
+
+/// The colors that Twitch gives cheers, by the minimum amount of bits of their tier
+const BITS_TIER_COLORS: [(u64, Color); 5] = [
+    (10000, Rgb(244, 48, 33)),
+    (5000, Rgb(0, 153, 254)),
+    (1000, Rgb(29, 178, 165)),
+    (100, Rgb(156, 62, 232)),
+    (1, Rgb(151, 151, 151)),
+];
+
+/// The color of the tier that an amount of bits falls into
+pub fn bits_tier_color(bits: u64) -> Color {
+    BITS_TIER_COLORS
+        .iter()
+        .find(|(min_bits, _)| bits >= *min_bits)
+        .map_or(
+            BITS_TIER_COLORS[BITS_TIER_COLORS.len() - 1].1,
+            |(_, color)| *color,
+        )
+}
+
+/// Where the image of a cheermote is stored, such as `cheermote_cheer_100`
+pub fn cheermote_filename(prefix: &str, tier: usize) -> String {
+    format!("cheermote_{}_{tier}", prefix.to_lowercase())
+}
@@ -1,8 +1,72 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use reqwest::Client;
 
-use super::{api::subscriptions::Subscription, oauth::TwitchOauth};
+use super::{
+    api::{channel_points::CustomReward, subscriptions::Subscription},
+    oauth::TwitchOauth,
+};
+use crate::handlers::stream_status::StreamStatus;
+
+/// A channel that has been joined in the current session
+#[derive(Debug, Clone, Default)]
+pub struct JoinedChannel {
+    /// The name of the channel
+    name: String,
+    /// Events that are subscribed to for this channel
+    event_subscriptions: HashMap<Subscription, String>,
+    /// The channel point rewards of the channel by their ID, only known for the user's own channel
+    custom_rewards: HashMap<String, CustomReward>,
+    /// Whether the channel is live, and what it's streaming
+    stream_status: StreamStatus,
+}
+
+impl JoinedChannel {
+    pub fn new(name: String, event_subscriptions: HashMap<Subscription, String>) -> Self {
+        Self {
+            name,
+            event_subscriptions,
+            custom_rewards: HashMap::new(),
+            stream_status: StreamStatus::default(),
+        }
+    }
+
+    pub const fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub const fn event_subscriptions(&self) -> &HashMap<Subscription, String> {
+        &self.event_subscriptions
+    }
+
+    pub fn set_event_subscriptions(&mut self, event_subscriptions: HashMap<Subscription, String>) {
+        self.event_subscriptions = event_subscriptions;
+    }
+
+    pub fn custom_reward(&self, reward_id: &str) -> Option<&CustomReward> {
+        self.custom_rewards.get(reward_id)
+    }
+
+    pub fn set_custom_rewards(&mut self, custom_rewards: Vec<CustomReward>) {
+        self.custom_rewards = custom_rewards
+            .into_iter()
+            .map(|reward| (reward.id.clone(), reward))
+            .collect();
+    }
+
+    pub const fn stream_status(&self) -> &StreamStatus {
+        &self.stream_status
+    }
+
+    pub const fn stream_status_mut(&mut self) -> &mut StreamStatus {
+        &mut self.stream_status
+    }
+
+    /// Remembers a reward, such as one that was created after the channel was joined
+    pub fn add_custom_reward(&mut self, reward: CustomReward) {
+        self.custom_rewards.insert(reward.id.clone(), reward);
+    }
+}
 
 #[derive(Debug, Clone, Default)]
 pub struct TwitchWebsocketContext {
@@ -13,7 +77,9 @@ pub struct TwitchWebsocketContext {
 
     /// Current session ID
     session_id: Option<String>,
-    /// Which channel ID the client is currently connected to
+    /// How long the current session may go without any messages before it is considered lost
+    keepalive_timeout: Option<Duration>,
+    /// Which channel ID messages and commands are currently sent to
     channel_id: Option<String>,
     /// The current channel name
     channel_name: Option<String>,
@@ -21,8 +87,10 @@ pub struct TwitchWebsocketContext {
     /// Are emotes enabled right now?
     emotes_enabled: bool,
 
-    /// Events that are subscribed to in this session
-    event_subscriptions: HashMap<Subscription, String>,
+    /// Every channel joined in this session, by channel ID
+    joined_channels: HashMap<String, JoinedChannel>,
+    /// Events about the user that are subscribed to in this session, such as whispers
+    user_event_subscriptions: HashMap<Subscription, String>,
 }
 
 impl TwitchWebsocketContext {
@@ -34,14 +102,45 @@ impl TwitchWebsocketContext {
         self.oauth.as_ref()
     }
 
-    pub const fn event_subscriptions(&self) -> &HashMap<Subscription, String> {
-        &self.event_subscriptions
+    pub const fn joined_channels(&self) -> &HashMap<String, JoinedChannel> {
+        &self.joined_channels
+    }
+
+    pub const fn user_event_subscriptions(&self) -> &HashMap<Subscription, String> {
+        &self.user_event_subscriptions
+    }
+
+    pub fn set_user_event_subscriptions(
+        &mut self,
+        user_event_subscriptions: HashMap<Subscription, String>,
+    ) {
+        self.user_event_subscriptions = user_event_subscriptions;
+    }
+
+    pub fn joined_channel(&self, channel_id: &str) -> Option<&JoinedChannel> {
+        self.joined_channels.get(channel_id)
+    }
+
+    pub fn joined_channel_mut(&mut self, channel_id: &str) -> Option<&mut JoinedChannel> {
+        self.joined_channels.get_mut(channel_id)
+    }
+
+    /// Finds the ID of a joined channel through its name
+    pub fn joined_channel_id(&self, channel_name: &str) -> Option<&String> {
+        self.joined_channels
+            .iter()
+            .find(|(_, channel)| channel.name == channel_name)
+            .map(|(channel_id, _)| channel_id)
     }
 
     pub const fn session_id(&self) -> Option<&String> {
         self.session_id.as_ref()
     }
 
+    pub const fn keepalive_timeout(&self) -> Option<Duration> {
+        self.keepalive_timeout
+    }
+
     pub const fn channel_id(&self) -> Option<&String> {
         self.channel_id.as_ref()
     }
@@ -62,6 +161,10 @@ impl TwitchWebsocketContext {
         self.session_id = session_id;
     }
 
+    pub const fn set_keepalive_timeout(&mut self, keepalive_timeout: Option<Duration>) {
+        self.keepalive_timeout = keepalive_timeout;
+    }
+
     pub fn set_channel_id(&mut self, channel_id: Option<String>) {
         self.channel_id = channel_id;
     }
@@ -78,7 +181,11 @@ impl TwitchWebsocketContext {
         self.emotes_enabled
     }
 
-    pub fn set_event_subscriptions(&mut self, event_subscriptions: HashMap<Subscription, String>) {
-        self.event_subscriptions = event_subscriptions;
+    pub fn add_joined_channel(&mut self, channel_id: String, channel: JoinedChannel) {
+        self.joined_channels.insert(channel_id, channel);
+    }
+
+    pub fn remove_joined_channel(&mut self, channel_id: &str) -> Option<JoinedChannel> {
+        self.joined_channels.remove(channel_id)
     }
 }
@@ -1,17 +1,36 @@
+use std::collections::BTreeMap;
+
+use chrono::{Local, Utc};
 use color_eyre::{Result, eyre::ContextCompat};
 use futures::StreamExt;
 use tokio::sync::mpsc::Sender;
 
 use crate::{
-    config::SharedCoreConfig,
-    emotes::get_twitch_emote,
+    emotes::{get_twitch_cheermote, get_twitch_emote},
     events::{Event, TwitchEvent, TwitchNotification},
-    handlers::data::DataBuilder,
+    handlers::{
+        automod::HeldMessage,
+        data::{DataBuilder, RawMessageData, ReplyParent},
+        polls::{LiveChoice, LivePoll, PollKind, PollStatus},
+        whispers::Whisper,
+    },
     twitch::{
-        api::subscriptions::Subscription,
-        badges::retrieve_user_badges,
+        api::{
+            channel_points::CustomReward,
+            event_sub::{
+                AUTOMOD_EVENT_SUBSCRIPTIONS, POLL_EVENT_SUBSCRIPTIONS, STREAM_EVENT_SUBSCRIPTIONS,
+            },
+            subscriptions::Subscription,
+        },
+        badges::ChatBadge,
         context::TwitchWebsocketContext,
-        models::{ReceivedTwitchEvent, ReceivedTwitchEventMessageFragment, ReceivedTwitchMessage},
+        handlers::stream_status::handle_stream_event,
+        models::{
+            ReceivedTwitchEvent, ReceivedTwitchEventCheer, ReceivedTwitchEventMessageFragment,
+            ReceivedTwitchMessage,
+        },
+        notices::ChatNotice,
+        oauth::TwitchOauth,
     },
     utils::text::{clean_message, parse_message_action},
 };
@@ -20,24 +39,22 @@ async fn handle_chat_notification(
     event_tx: &Sender<Event>,
     event: ReceivedTwitchEvent,
     subscription_type: Subscription,
+    channel: Option<String>,
 ) -> Result<()> {
     match subscription_type {
-        Subscription::Notification => {
-            if let Some(twitch_notification_message) = event.system_message() {
-                event_tx
-                    .send(DataBuilder::twitch(twitch_notification_message.clone()).into())
-                    .await?;
-            }
-        }
         Subscription::Clear => {
             event_tx
                 .send(Event::Twitch(TwitchEvent::Notification(
-                    TwitchNotification::ClearChat(None),
+                    TwitchNotification::ClearChat {
+                        channel: channel.clone(),
+                        user_id: None,
+                    },
                 )))
                 .await?;
             event_tx
                 .send(
-                    DataBuilder::twitch(
+                    DataBuilder::channel_twitch(
+                        channel,
                         "Chat was cleared for non-Moderators viewing this room".to_string(),
                     )
                     .into(),
@@ -48,7 +65,10 @@ async fn handle_chat_notification(
             if let Some(target_user_id) = event.target_user_id() {
                 event_tx
                     .send(Event::Twitch(TwitchEvent::Notification(
-                        TwitchNotification::ClearChat(Some(target_user_id.clone())),
+                        TwitchNotification::ClearChat {
+                            channel,
+                            user_id: Some(target_user_id.clone()),
+                        },
                     )))
                     .await?;
             }
@@ -75,7 +95,7 @@ async fn handle_chat_notification(
             );
 
             event_tx
-                .send(DataBuilder::twitch(timeout_message).into())
+                .send(DataBuilder::channel_twitch(channel, timeout_message).into())
                 .await?;
         }
         _ => {}
@@ -85,31 +105,407 @@ async fn handle_chat_notification(
 }
 
 pub async fn handle_incoming_message(
-    config: SharedCoreConfig,
-    context: &TwitchWebsocketContext,
+    context: &mut TwitchWebsocketContext,
     event_tx: &Sender<Event>,
     received_message: ReceivedTwitchMessage,
 ) -> Result<()> {
-    // Don't allow messages from other channels go through
-    if let Some(condition) = received_message.subscription_condition() {
-        if context
-            .channel_id()
-            .is_some_and(|channel_id| channel_id != condition.broadcaster_user_id())
-        {
-            return Ok(());
-        }
+    // Whispers are sent to the user rather than in a channel, so they aren't tied to any joined channel
+    if received_message.subscription_type() == Some(Subscription::Whisper) {
+        return handle_whisper(event_tx, &received_message).await;
     }
 
+    let broadcaster_id = received_message
+        .subscription_condition()
+        .map(|condition| condition.broadcaster_user_id().clone());
+
+    // Don't allow messages from channels that haven't been joined go through
+    let channel = if let Some(condition) = received_message.subscription_condition() {
+        let Some(joined_channel) = context.joined_channel(condition.broadcaster_user_id()) else {
+            return Ok(());
+        };
+
+        Some(joined_channel.name().clone())
+    } else {
+        None
+    };
+
     let Some(event) = received_message.event() else {
         return Ok(());
     };
 
     if let Some(subscription_type) = received_message.subscription_type() {
+        if subscription_type == Subscription::Notification {
+            return handle_chat_notice(context, event_tx, &event, broadcaster_id, channel).await;
+        }
+
+        if subscription_type == Subscription::Redemption {
+            return handle_redemption(context, event_tx, &event, broadcaster_id, channel).await;
+        }
+
+        if POLL_EVENT_SUBSCRIPTIONS.contains(&subscription_type) {
+            return handle_poll(event_tx, &event, &subscription_type, channel).await;
+        }
+
+        if AUTOMOD_EVENT_SUBSCRIPTIONS.contains(&subscription_type) {
+            return handle_automod(event_tx, &event, &subscription_type, channel).await;
+        }
+
+        if STREAM_EVENT_SUBSCRIPTIONS.contains(&subscription_type) {
+            return handle_stream_event(
+                context,
+                event_tx,
+                &subscription_type,
+                &event,
+                broadcaster_id,
+            )
+            .await;
+        }
+
         if subscription_type != Subscription::Message {
-            return handle_chat_notification(event_tx, event, subscription_type).await;
+            return handle_chat_notification(event_tx, event, subscription_type, channel).await;
         }
     }
 
+    // Rewards that ask for text are redeemed through a chat message
+    if let Some(reward_id) = event.channel_points_custom_reward_id() {
+        let reward = broadcaster_id
+            .as_ref()
+            .and_then(|broadcaster_id| context.joined_channel(broadcaster_id))
+            .and_then(|joined_channel| joined_channel.custom_reward(reward_id));
+
+        let chatter = event
+            .chatter_user_name()
+            .map_or("Unknown Twitch user", |chatter| chatter.as_str());
+
+        event_tx
+            .send(
+                DataBuilder::notice(
+                    channel.clone(),
+                    ChatNotice::Redemption,
+                    redemption_notice(chatter, reward),
+                )
+                .into(),
+            )
+            .await?;
+    }
+
+    event_tx
+        .send(
+            user_message(context, &event, broadcaster_id, channel, None)
+                .await?
+                .into(),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// The line that is shown when a viewer redeems a reward.
+/// Only the broadcaster can look up rewards, so anyone else just sees that something was redeemed.
+pub fn redemption_notice(user: &str, reward: Option<&CustomReward>) -> String {
+    reward.map_or_else(
+        || format!("{user} redeemed a reward"),
+        |reward| {
+            format!(
+                "{user} redeemed {} for {} points",
+                reward.title, reward.cost
+            )
+        },
+    )
+}
+
+/// Shows a viewer redeeming a reward on the user's own channel
+async fn handle_redemption(
+    context: &mut TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    event: &ReceivedTwitchEvent,
+    broadcaster_id: Option<String>,
+    channel: Option<String>,
+) -> Result<()> {
+    let reward = event
+        .reward()
+        .context("Could not find the reward that was redeemed")?;
+
+    // The reward may have been created after the channel was joined
+    if let Some(joined_channel) = broadcaster_id
+        .as_ref()
+        .and_then(|broadcaster_id| context.joined_channel_mut(broadcaster_id))
+    {
+        joined_channel.add_custom_reward(reward.clone());
+    }
+
+    // The text of the redemption comes through as a chat message, which is shown along with the reward
+    if event.user_input().is_some_and(|input| !input.is_empty()) {
+        return Ok(());
+    }
+
+    let user = event
+        .user_name()
+        .map_or("Unknown Twitch user", |user| user.as_str());
+
+    event_tx
+        .send(
+            DataBuilder::notice(
+                channel,
+                ChatNotice::Redemption,
+                redemption_notice(user, Some(reward)),
+            )
+            .into(),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// The state of a poll or prediction after an event about it
+pub fn live_poll(
+    subscription_type: &Subscription,
+    event: &ReceivedTwitchEvent,
+    channel: String,
+) -> Option<LivePoll> {
+    let ended = || {
+        Some(PollStatus::Ended {
+            outcome: event.status()?.clone(),
+            ended_at: event.ended_at().unwrap_or_else(Utc::now),
+        })
+    };
+
+    let (kind, status) = match subscription_type {
+        Subscription::PollBegin | Subscription::PollProgress => {
+            (PollKind::Poll, PollStatus::Active(event.ends_at()))
+        }
+        Subscription::PollEnd => (PollKind::Poll, ended()?),
+        Subscription::PredictionBegin | Subscription::PredictionProgress => {
+            (PollKind::Prediction, PollStatus::Active(event.locks_at()))
+        }
+        Subscription::PredictionLock => (PollKind::Prediction, PollStatus::Locked),
+        Subscription::PredictionEnd => (PollKind::Prediction, ended()?),
+        _ => return None,
+    };
+
+    let choices = match kind {
+        PollKind::Poll => {
+            let choices = event.choices()?;
+            let has_ended = matches!(status, PollStatus::Ended { .. });
+            let most_votes = choices
+                .iter()
+                .map(|choice| choice.votes)
+                .max()
+                .unwrap_or_default();
+
+            // Every choice that is tied for the most votes wins
+            choices
+                .iter()
+                .map(|choice| LiveChoice {
+                    title: choice.title.clone(),
+                    amount: choice.votes,
+                    users: None,
+                    winner: has_ended && most_votes > 0 && choice.votes == most_votes,
+                })
+                .collect()
+        }
+        PollKind::Prediction => event
+            .outcomes()?
+            .iter()
+            .map(|outcome| LiveChoice {
+                title: outcome.title.clone(),
+                amount: outcome.channel_points,
+                users: Some(outcome.users),
+                winner: event.winning_outcome_id() == Some(&outcome.id),
+            })
+            .collect(),
+    };
+
+    Some(LivePoll {
+        kind,
+        id: event.id()?.clone(),
+        channel,
+        title: event.title()?.clone(),
+        choices,
+        status,
+    })
+}
+
+/// Updates the poll or prediction panel of the user's own channel
+async fn handle_poll(
+    event_tx: &Sender<Event>,
+    event: &ReceivedTwitchEvent,
+    subscription_type: &Subscription,
+    channel: Option<String>,
+) -> Result<()> {
+    let Some(channel) = channel else {
+        return Ok(());
+    };
+
+    let poll = live_poll(subscription_type, event, channel)
+        .context(format!("Could not read the {subscription_type} event"))?;
+
+    event_tx
+        .send(Event::Twitch(TwitchEvent::Notification(
+            TwitchNotification::Poll(poll),
+        )))
+        .await?;
+
+    Ok(())
+}
+
+/// A message that `AutoMod` held, along with why it was held
+pub fn held_message(event: &ReceivedTwitchEvent, channel: String) -> Option<HeldMessage> {
+    let reason = if event.reason()? == "blocked_term" {
+        "Blocked term".to_string()
+    } else {
+        let (category, level) = event.automod_category()?;
+
+        format!("{category}, level {level}")
+    };
+
+    Some(HeldMessage {
+        id: event.message_id()?.clone(),
+        channel,
+        user_name: event.user_name()?.clone(),
+        text: event.message_text()?,
+        reason,
+        held_at: event.held_at().unwrap_or_else(Utc::now),
+    })
+}
+
+/// Adds messages held by `AutoMod` to the queue, and takes them out again once they've been dealt with
+async fn handle_automod(
+    event_tx: &Sender<Event>,
+    event: &ReceivedTwitchEvent,
+    subscription_type: &Subscription,
+    channel: Option<String>,
+) -> Result<()> {
+    let Some(channel) = channel else {
+        return Ok(());
+    };
+
+    if *subscription_type == Subscription::AutoModMessageUpdate {
+        let message_id = event
+            .message_id()
+            .context("Could not find the ID of the held message")?;
+
+        event_tx
+            .send(Event::Twitch(TwitchEvent::Notification(
+                TwitchNotification::HeldMessageResolved(message_id.clone()),
+            )))
+            .await?;
+
+        return Ok(());
+    }
+
+    let held_message = held_message(event, channel.clone())
+        .context(format!("Could not read the {subscription_type} event"))?;
+
+    event_tx
+        .send(
+            DataBuilder::channel_twitch(
+                Some(channel),
+                format!(
+                    "AutoMod held a message from {} for review",
+                    held_message.user_name
+                ),
+            )
+            .into(),
+        )
+        .await?;
+
+    event_tx
+        .send(Event::Twitch(TwitchEvent::Notification(
+            TwitchNotification::HeldMessage(held_message),
+        )))
+        .await?;
+
+    Ok(())
+}
+
+async fn handle_whisper(
+    event_tx: &Sender<Event>,
+    received_message: &ReceivedTwitchMessage,
+) -> Result<()> {
+    let Some(event) = received_message.event() else {
+        return Ok(());
+    };
+
+    let user = event
+        .whisper_sender()
+        .context("Could not find the sender of the whisper")?;
+    let payload = event
+        .whisper_text()
+        .context("Could not find whisper text")?;
+
+    let whisper = Whisper {
+        time_sent: received_message
+            .message_timestamp()
+            .map_or_else(Local::now, |timestamp| timestamp.with_timezone(&Local)),
+        user: user.clone(),
+        sent: false,
+        payload: payload.to_string(),
+    };
+
+    event_tx
+        .send(Event::Twitch(TwitchEvent::Notification(
+            TwitchNotification::Whisper(whisper),
+        )))
+        .await?;
+
+    Ok(())
+}
+
+/// Shows a chat notification, such as a subscription or a raid, along with the message that came with it
+async fn handle_chat_notice(
+    context: &TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    event: &ReceivedTwitchEvent,
+    broadcaster_id: Option<String>,
+    channel: Option<String>,
+) -> Result<()> {
+    let notice = event.notice().unwrap_or(ChatNotice::Other);
+
+    // Announcements are messages from the announcer, rather than a notice from Twitch
+    if let ChatNotice::Announcement(_) = notice {
+        event_tx
+            .send(
+                user_message(context, event, broadcaster_id, channel, Some(notice))
+                    .await?
+                    .into(),
+            )
+            .await?;
+
+        return Ok(());
+    }
+
+    if let Some(system_message) = event.system_message().filter(|message| !message.is_empty()) {
+        event_tx
+            .send(DataBuilder::notice(channel.clone(), notice, system_message.clone()).into())
+            .await?;
+    }
+
+    if notice.has_user_message()
+        && event
+            .message_text()
+            .is_some_and(|message_text| !message_text.is_empty())
+    {
+        event_tx
+            .send(
+                user_message(context, event, broadcaster_id, channel, None)
+                    .await?
+                    .into(),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Builds a message sent by a chatter, downloading the emotes and cheermotes in it
+async fn user_message(
+    context: &TwitchWebsocketContext,
+    event: &ReceivedTwitchEvent,
+    broadcaster_id: Option<String>,
+    channel: Option<String>,
+    notice: Option<ChatNotice>,
+) -> Result<TwitchNotification> {
     let message_text = event
         .message_text()
         .context("Could not find message text")?;
@@ -143,16 +539,52 @@ pub async fn handle_incoming_message(
     .buffer_unordered(10)
     .collect::<Vec<Result<(String, (String, bool))>>>();
 
+    // The same cheermote can be used many times in one message, but only has to be downloaded once
+    let received_cheermotes = if context.is_emotes_enabled() {
+        event.cheermote_fragments()
+    } else {
+        Option::default()
+    }
+    .unwrap_or_default()
+    .into_iter()
+    .filter_map(|fragment| {
+        let cheermote = fragment.cheermote()?;
+
+        Some((
+            fragment.cheermote_name()?.clone(),
+            (cheermote.prefix().to_string(), cheermote.tier()),
+        ))
+    })
+    .collect::<BTreeMap<String, (String, usize)>>();
+
+    let twitch_client = context.twitch_client();
+    let twitch_client = twitch_client.as_ref();
+    let broadcaster_id = broadcaster_id.as_ref();
+
+    let cheermotes = futures::stream::iter(received_cheermotes.into_iter().map(
+        |(cheermote_name, (prefix, tier))| async move {
+            let client = twitch_client.context("Twitch client has not been initialized")?;
+            let broadcaster_id =
+                broadcaster_id.context("Could not find the channel that was cheered in")?;
+
+            let filename = get_twitch_cheermote(client, broadcaster_id, &prefix, tier).await?;
+
+            Ok((cheermote_name, (filename, false)))
+        },
+    ))
+    .buffer_unordered(10)
+    .collect::<Vec<Result<(String, (String, bool))>>>();
+
     let chatter_user_name = event
         .chatter_user_name()
         .context("Could not find chatter user name")?
         .clone();
-    let badges = event.badges().unwrap_or_default();
-    let badges = if config.frontend.badges {
-        Some(retrieve_user_badges(&badges))
-    } else {
-        None
-    };
+    let badges = event
+        .badges()
+        .unwrap_or_default()
+        .iter()
+        .map(|badge| ChatBadge::new(badge, broadcaster_id.cloned()))
+        .collect();
 
     let chatter_user_id = event
         .chatter_user_id()
@@ -163,22 +595,59 @@ pub async fn handle_incoming_message(
         .context("Could not find message ID")?
         .clone();
 
-    let message_emotes = emotes.await.into_iter().flatten().collect();
+    // Chatters that have never picked a color have an empty one
+    let color = event.color().filter(|color| !color.is_empty()).cloned();
 
-    event_tx
-        .send(
-            DataBuilder::user(
-                chatter_user_name,
-                Some(chatter_user_id.clone()),
-                cleaned_message,
-                message_emotes,
-                Some(message_id),
-                highlight,
-                badges,
-            )
-            .into(),
-        )
-        .await?;
+    let reply = event.reply().map(|reply| {
+        Box::new(ReplyParent {
+            message_id: reply.parent_message_id().clone(),
+            author: reply.parent_user_name().clone(),
+            payload: reply.parent_message_body().clone(),
+        })
+    });
 
-    Ok(())
+    let bits = event.cheer().map(ReceivedTwitchEventCheer::bits);
+
+    let mention_fragments = event.mention_fragments().unwrap_or_default();
+    let mentions = mention_fragments
+        .iter()
+        .filter_map(ReceivedTwitchEventMessageFragment::mention_name)
+        .cloned()
+        .collect();
+
+    // Mentions are matched by ID, so that they still count after the current user changes their name
+    let user_id = context.oauth().and_then(TwitchOauth::user_id);
+    let ping = mention_fragments.iter().any(|fragment| {
+        fragment
+            .mention()
+            .is_some_and(|mention| Some(mention.user_id()) == user_id.as_deref())
+    });
+
+    let message_emotes = emotes
+        .await
+        .into_iter()
+        .chain(cheermotes.await)
+        .flatten()
+        .collect();
+
+    let message = RawMessageData::builder()
+        .author(chatter_user_name)
+        .user_id(chatter_user_id.clone())
+        .system(false)
+        .payload(cleaned_message)
+        .emotes(message_emotes)
+        .message_id(message_id)
+        .highlight(highlight)
+        .badges(badges)
+        .maybe_color(color)
+        .maybe_bits(bits)
+        .maybe_notice(notice)
+        .mentions(mentions)
+        .ping(ping)
+        .emote_only(event.emote_only())
+        .maybe_reply(reply)
+        .maybe_channel(channel)
+        .build();
+
+    Ok(TwitchNotification::Message(Box::new(message)))
 }
@@ -1,13 +1,14 @@
 use std::str::FromStr;
 
+use chrono::Local;
 use color_eyre::{Result, eyre::ContextCompat};
 use tokio::sync::mpsc::Sender;
 use tracing::debug;
 
 use super::super::oauth::TwitchOauth;
 use crate::{
-    events::Event,
-    handlers::data::DataBuilder,
+    events::{Event, TwitchEvent, TwitchNotification},
+    handlers::{data::DataBuilder, whispers::Whisper},
     twitch::{
         api::{
             BroadcasterQuery, ModeratorQuery,
@@ -19,10 +20,16 @@ use crate::{
             clear::{DeleteMessageQuery, delete_twitch_messages},
             commercial::{CommercialPayload, start_commercial},
             mods::{mod_twitch_user, unmod_twitch_user},
+            polls::{CreatePollPayload, EndPollPayload, create_poll, end_poll, get_active_poll},
+            predictions::{
+                CreatePredictionPayload, EndPredictionPayload, PredictionEndStatus,
+                create_prediction, end_prediction, get_current_prediction,
+            },
             raids::{RaidQuery, raid_twitch_user, unraid_twitch_user},
             shoutouts::{ShoutoutQuery, shoutout_twitch_user},
             timeouts::{TimeoutPayload, UnbanQuery, timeout_twitch_user, unban_twitch_user},
             vips::{unvip_twitch_user, vip_twitch_user},
+            whispers::{WhisperPayload, WhisperQuery, send_whisper},
         },
         context::TwitchWebsocketContext,
         handlers::twitch_commands::TwitchCommand,
@@ -282,6 +289,123 @@ pub async fn handle_command_message(
 
             format!("The category of the stream was changed to {game_name}")
         }
+        TwitchCommand::Whisper(username, message) => {
+            let target_user_id = get_channel_id(&twitch_client, &username).await?;
+
+            let whisper_query = WhisperQuery::new(user_id, target_user_id);
+            let whisper_payload = WhisperPayload::new(message.clone());
+
+            send_whisper(&twitch_client, whisper_query, whisper_payload).await?;
+
+            // Twitch doesn't send whispers back to the user that sent them
+            event_tx
+                .send(Event::Twitch(TwitchEvent::Notification(
+                    TwitchNotification::Whisper(Whisper {
+                        time_sent: Local::now(),
+                        user: username.clone(),
+                        sent: true,
+                        payload: message,
+                    }),
+                )))
+                .await?;
+
+            format!("Whispered to {username}")
+        }
+        TwitchCommand::Poll(title, choices, duration) => {
+            let poll_payload = CreatePollPayload::new(channel_id.clone(), title, choices, duration);
+
+            let poll = create_poll(&twitch_client, poll_payload).await?;
+
+            format!("Started the poll {} for {duration} seconds", poll.title)
+        }
+        TwitchCommand::EndPoll => {
+            let poll = get_active_poll(&twitch_client, channel_id)
+                .await?
+                .context("There is no active poll to end")?;
+
+            let end_payload = EndPollPayload::new(channel_id.clone(), poll.id);
+
+            end_poll(&twitch_client, end_payload).await?;
+
+            format!("Ended the poll {}", poll.title)
+        }
+        TwitchCommand::Prediction(title, outcomes, prediction_window) => {
+            let prediction_payload = CreatePredictionPayload::new(
+                channel_id.clone(),
+                title,
+                outcomes,
+                prediction_window,
+            );
+
+            let prediction = create_prediction(&twitch_client, prediction_payload).await?;
+
+            format!(
+                "Started the prediction {}, predictions can be made for {prediction_window} seconds",
+                prediction.title
+            )
+        }
+        TwitchCommand::LockPrediction => {
+            let prediction = get_current_prediction(&twitch_client, channel_id)
+                .await?
+                .context("There is no prediction to lock")?;
+
+            let end_payload = EndPredictionPayload::new(
+                channel_id.clone(),
+                prediction.id,
+                PredictionEndStatus::Locked,
+                None,
+            );
+
+            end_prediction(&twitch_client, end_payload).await?;
+
+            format!("Locked the prediction {}", prediction.title)
+        }
+        TwitchCommand::ResolvePrediction(outcome) => {
+            let prediction = get_current_prediction(&twitch_client, channel_id)
+                .await?
+                .context("There is no prediction to resolve")?;
+
+            let winning_outcome = prediction
+                .outcome(&outcome)
+                .context(format!(
+                    "Prediction {} has no outcome {outcome}",
+                    prediction.title
+                ))?
+                .clone();
+
+            let end_payload = EndPredictionPayload::new(
+                channel_id.clone(),
+                prediction.id,
+                PredictionEndStatus::Resolved,
+                Some(winning_outcome.id),
+            );
+
+            end_prediction(&twitch_client, end_payload).await?;
+
+            format!(
+                "Resolved the prediction {}, {} won",
+                prediction.title, winning_outcome.title
+            )
+        }
+        TwitchCommand::CancelPrediction => {
+            let prediction = get_current_prediction(&twitch_client, channel_id)
+                .await?
+                .context("There is no prediction to cancel")?;
+
+            let end_payload = EndPredictionPayload::new(
+                channel_id.clone(),
+                prediction.id,
+                PredictionEndStatus::Canceled,
+                None,
+            );
+
+            end_prediction(&twitch_client, end_payload).await?;
+
+            format!(
+                "Canceled the prediction {}, every channel point spent on it was refunded",
+                prediction.title
+            )
+        }
     };
 
     debug!("Sending command message: {command_message}");
@@ -1,5 +1,7 @@
 pub mod incoming_message;
 pub mod message_commands;
+pub mod moderation;
 pub mod send_message;
+pub mod stream_status;
 pub mod twitch_commands;
 pub mod welcome_message;
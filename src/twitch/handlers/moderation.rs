@@ -0,0 +1,82 @@
+use color_eyre::{Result, eyre::ContextCompat};
+use tokio::sync::mpsc::Sender;
+
+use super::super::oauth::TwitchOauth;
+use crate::{
+    events::Event,
+    handlers::data::DataBuilder,
+    twitch::{
+        api::{
+            ModeratorQuery,
+            clear::{DeleteMessageQuery, delete_twitch_messages},
+            timeouts::{TimeoutPayload, timeout_twitch_user},
+        },
+        context::TwitchWebsocketContext,
+    },
+};
+
+/// The channel and user ID that moderation actions are taken in and by
+fn moderation_ids(context: &TwitchWebsocketContext) -> Result<(String, String)> {
+    let channel_id = context
+        .channel_id()
+        .context("Channel ID could not be found when moderating")?;
+
+    let user_id = context
+        .oauth()
+        .and_then(TwitchOauth::user_id)
+        .context("Twitch OAuth could not be found when moderating")?;
+
+    Ok((channel_id.clone(), user_id))
+}
+
+/// Handles the user deleting a single message in the current channel
+pub async fn handle_delete_message(
+    context: &TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    message_id: String,
+) -> Result<()> {
+    let twitch_client = context
+        .twitch_client()
+        .context("Twitch client could not be found when deleting message")?;
+
+    let (channel_id, user_id) = moderation_ids(context)?;
+
+    let delete_message_query = DeleteMessageQuery::new(channel_id, user_id, Some(message_id));
+    delete_twitch_messages(&twitch_client, delete_message_query).await?;
+
+    event_tx
+        .send(DataBuilder::twitch("Message was deleted".to_string()).into())
+        .await?;
+
+    Ok(())
+}
+
+/// Handles the user timing out another user in the current channel,
+/// or banning them if there is no duration
+pub async fn handle_user_timeout(
+    context: &TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    target_user_id: String,
+    username: &str,
+    duration: Option<usize>,
+) -> Result<()> {
+    let twitch_client = context
+        .twitch_client()
+        .context("Twitch client could not be found when timing out user")?;
+
+    let (channel_id, user_id) = moderation_ids(context)?;
+
+    let timeout_query = ModeratorQuery::new(channel_id, user_id);
+    let timeout_payload = TimeoutPayload::new(target_user_id, duration, None);
+
+    timeout_twitch_user(&twitch_client, timeout_query, timeout_payload).await?;
+
+    let message = duration.map_or_else(
+        || format!("User {username} banned."),
+        |duration| format!("User {username} timed out for {duration} seconds."),
+    );
+
+    event_tx.send(DataBuilder::twitch(message).into()).await?;
+
+    Ok(())
+}
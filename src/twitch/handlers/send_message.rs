@@ -6,8 +6,13 @@ use crate::twitch::{
     context::TwitchWebsocketContext,
 };
 
-/// Handles the user wanting to send a message from the terminal to the WebSocket server
-pub async fn handle_send_message(context: &TwitchWebsocketContext, message: String) -> Result<()> {
+/// Handles the user wanting to send a message from the terminal to the WebSocket server,
+/// optionally as a reply to another message
+pub async fn handle_send_message(
+    context: &TwitchWebsocketContext,
+    message: String,
+    reply_parent_message_id: Option<String>,
+) -> Result<()> {
     let twitch_client = context
         .twitch_client()
         .context("Twitch client could not be found when sending message")?;
@@ -21,7 +26,12 @@ pub async fn handle_send_message(context: &TwitchWebsocketContext, message: Stri
         .and_then(TwitchOauth::user_id)
         .context("Twitch OAuth could not be found when sending message")?;
 
-    let new_message = NewTwitchMessage::new(channel_id.clone(), user_id.clone(), message);
+    let new_message = NewTwitchMessage::new(
+        channel_id.clone(),
+        user_id.clone(),
+        message,
+        reply_parent_message_id,
+    );
 
     send_twitch_message(&twitch_client, new_message).await?;
 
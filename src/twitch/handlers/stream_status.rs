@@ -0,0 +1,140 @@
+use chrono::Utc;
+use color_eyre::{Result, eyre::ContextCompat};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    events::{Event, TwitchEvent, TwitchNotification},
+    handlers::stream_status::StreamStatus,
+    twitch::{
+        api::{
+            channel_information::get_channel_information,
+            streams::{Stream, get_streams},
+            subscriptions::Subscription,
+        },
+        context::{JoinedChannel, TwitchWebsocketContext},
+        models::ReceivedTwitchEvent,
+    },
+};
+
+/// How many channels Get Streams can be asked about at once
+const MAXIMUM_STREAMS_PER_REQUEST: usize = 100;
+
+fn apply_stream(status: &mut StreamStatus, stream: &Stream) {
+    status.go_live(stream.started_at);
+    status.viewers = Some(stream.viewer_count);
+    status.title.clone_from(&stream.title);
+    status.category.clone_from(&stream.game_name);
+}
+
+/// Lets the frontend know what the stream of a joined channel currently looks like
+async fn send_stream_status(event_tx: &Sender<Event>, channel: &JoinedChannel) -> Result<()> {
+    event_tx
+        .send(Event::Twitch(TwitchEvent::Notification(
+            TwitchNotification::StreamStatus {
+                channel: channel.name().clone(),
+                status: channel.stream_status().clone(),
+            },
+        )))
+        .await?;
+
+    Ok(())
+}
+
+/// Gets whether a channel that was just joined is live, along with its title and category
+pub async fn handle_stream_status_join(
+    context: &mut TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    channel_id: &str,
+) -> Result<()> {
+    let twitch_client = context.twitch_client().context("Twitch client not found")?;
+
+    let stream = get_streams(&twitch_client, &[channel_id.to_string()])
+        .await?
+        .into_iter()
+        .next();
+
+    let mut status = StreamStatus::default();
+
+    if let Some(stream) = stream {
+        apply_stream(&mut status, &stream);
+    } else {
+        // Offline channels aren't returned by Get Streams, so their title and category are looked up instead
+        let channel_information =
+            get_channel_information(&twitch_client, channel_id.to_string()).await?;
+
+        status.title.clone_from(channel_information.title());
+        status.category.clone_from(channel_information.game_name());
+    }
+
+    let channel = context
+        .joined_channel_mut(channel_id)
+        .context("Channel has not been joined")?;
+    *channel.stream_status_mut() = status;
+
+    send_stream_status(event_tx, channel).await
+}
+
+/// Polls the streams of every joined channel, mostly to keep their viewer counts up to date
+pub async fn handle_stream_status_refresh(
+    context: &mut TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+) -> Result<()> {
+    let twitch_client = context.twitch_client().context("Twitch client not found")?;
+    let channel_ids: Vec<String> = context.joined_channels().keys().cloned().collect();
+
+    let mut streams = vec![];
+
+    for channel_ids in channel_ids.chunks(MAXIMUM_STREAMS_PER_REQUEST) {
+        streams.extend(get_streams(&twitch_client, channel_ids).await?);
+    }
+
+    for channel_id in channel_ids {
+        let Some(channel) = context.joined_channel_mut(&channel_id) else {
+            continue;
+        };
+
+        match streams.iter().find(|stream| stream.user_id == channel_id) {
+            Some(stream) => apply_stream(channel.stream_status_mut(), stream),
+            None => channel.stream_status_mut().go_offline(),
+        }
+
+        send_stream_status(event_tx, channel).await?;
+    }
+
+    Ok(())
+}
+
+/// Updates the stream status of a channel when it goes live, goes offline, or changes its title or category
+pub async fn handle_stream_event(
+    context: &mut TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    subscription_type: &Subscription,
+    event: &ReceivedTwitchEvent,
+    broadcaster_id: Option<String>,
+) -> Result<()> {
+    let Some(channel) = broadcaster_id
+        .as_ref()
+        .and_then(|broadcaster_id| context.joined_channel_mut(broadcaster_id))
+    else {
+        return Ok(());
+    };
+
+    let status = channel.stream_status_mut();
+
+    match subscription_type {
+        Subscription::StreamOnline => status.go_live(event.started_at().unwrap_or_else(Utc::now)),
+        Subscription::StreamOffline => status.go_offline(),
+        Subscription::ChannelUpdate => {
+            if let Some(title) = event.title() {
+                status.title.clone_from(title);
+            }
+
+            if let Some(category) = event.category_name() {
+                status.category.clone_from(category);
+            }
+        }
+        _ => return Ok(()),
+    }
+
+    send_stream_status(event_tx, channel).await
+}
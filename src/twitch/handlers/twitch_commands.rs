@@ -4,6 +4,12 @@ use color_eyre::eyre::{Error, bail};
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 
+/// How many seconds a poll lasts when no duration is given
+const DEFAULT_POLL_DURATION: usize = 60;
+
+/// How many seconds viewers can make predictions for when no duration is given
+const DEFAULT_PREDICTION_WINDOW: usize = 120;
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub enum TwitchCommand {
     /// Clear the chat
@@ -54,6 +60,20 @@ pub enum TwitchCommand {
     Title(String),
     /// Set the category of a stream
     Category(String),
+    /// Whisper a message to username
+    Whisper(String, String),
+    /// Start a poll with a title, its choices, and how long it lasts in seconds
+    Poll(String, Vec<String>, usize),
+    /// End the active poll early, showing its results
+    EndPoll,
+    /// Start a prediction with a title, its outcomes, and how long predictions can be made for in seconds
+    Prediction(String, Vec<String>, usize),
+    /// Stop accepting predictions, without choosing an outcome yet
+    LockPrediction,
+    /// Choose the winning outcome of the prediction, either by its title or its number
+    ResolvePrediction(String),
+    /// Cancel the prediction, refunding every channel point that was spent on it
+    CancelPrediction,
 }
 
 impl TwitchCommand {
@@ -120,6 +140,72 @@ impl TwitchCommand {
         };
         Ok(Self::Commercial(duration))
     }
+    fn handle_whisper_command(args: &[&str]) -> Result<Self, Error> {
+        debug!("Whisper command received as {:?}", args);
+        match args.iter().as_slice() {
+            [username, message @ ..] if !message.is_empty() => Ok(Self::Whisper(
+                username.trim_start_matches('@').to_lowercase(),
+                message.join(" "),
+            )),
+            _ => bail!("Invalid whisper command arguments"),
+        }
+    }
+    /// Splits `[duration] <title> | <choice> | <choice>...` into the title, choices and duration
+    fn parse_choices(
+        args: &[&str],
+        default_duration: usize,
+    ) -> Result<(String, Vec<String>, usize), Error> {
+        let joined_args = args.join(" ");
+        let mut parts = joined_args.split('|').map(str::trim);
+
+        let header = parts.next().unwrap_or_default();
+        let choices = parts
+            .filter(|choice| !choice.is_empty())
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        let (duration, title) = match header.split_once(' ') {
+            Some((duration, title)) if duration.parse::<usize>().is_ok() => {
+                (duration.parse::<usize>()?, title.trim())
+            }
+            _ => (default_duration, header),
+        };
+
+        if title.is_empty() || choices.len() < 2 {
+            bail!("A title and at least two choices are needed, separated by |");
+        }
+
+        Ok((title.to_string(), choices, duration))
+    }
+    fn handle_poll_command(args: &[&str]) -> Result<Self, Error> {
+        debug!("Poll command received as {:?}", args);
+        match args {
+            [action] if action.eq_ignore_ascii_case("end") => Ok(Self::EndPoll),
+            _ => {
+                let (title, choices, duration) = Self::parse_choices(args, DEFAULT_POLL_DURATION)?;
+
+                Ok(Self::Poll(title, choices, duration))
+            }
+        }
+    }
+    fn handle_prediction_command(args: &[&str]) -> Result<Self, Error> {
+        debug!("Prediction command received as {:?}", args);
+        let action = args.first().map(|action| action.to_lowercase());
+
+        match (action.as_deref(), args) {
+            (Some("lock"), [_]) => Ok(Self::LockPrediction),
+            (Some("cancel"), [_]) => Ok(Self::CancelPrediction),
+            (Some("resolve"), [_, outcome @ ..]) if !outcome.is_empty() => {
+                Ok(Self::ResolvePrediction(outcome.join(" ")))
+            }
+            _ => {
+                let (title, outcomes, prediction_window) =
+                    Self::parse_choices(args, DEFAULT_PREDICTION_WINDOW)?;
+
+                Ok(Self::Prediction(title, outcomes, prediction_window))
+            }
+        }
+    }
     fn handle_title_command(args: &[&str]) -> Self {
         let title = args.join(" ");
         Self::Title(title)
@@ -134,6 +220,17 @@ impl FromStr for TwitchCommand {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Whispers, polls and predictions keep their case, so they're parsed before everything is lowercased
+        let words = s.split_whitespace().collect::<Vec<&str>>();
+        if let [command, args @ ..] = words.as_slice() {
+            match command.to_lowercase().as_str() {
+                "w" => return Self::handle_whisper_command(args),
+                "poll" => return Self::handle_poll_command(args),
+                "prediction" => return Self::handle_prediction_command(args),
+                _ => {}
+            }
+        }
+
         let parts = s.trim().to_lowercase();
 
         let cmd = match parts.split_whitespace().collect::<Vec<&str>>().as_slice() {
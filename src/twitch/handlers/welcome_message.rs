@@ -1,85 +1,221 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use color_eyre::{
     Result,
-    eyre::{Context, ContextCompat},
+    eyre::{Context, ContextCompat, Error},
 };
 use tokio::sync::mpsc::Sender;
 use tokio_tungstenite::tungstenite::Utf8Bytes;
+use tracing::{debug, warn};
 
 use crate::{
     events::Event,
     handlers::data::DataBuilder,
     twitch::{
         api::{
+            channel_points::get_custom_rewards,
             channels::get_channel_id,
             chat_settings::get_chat_settings,
             event_sub::{
-                INITIAL_EVENT_SUBSCRIPTIONS, subscribe_to_events, unsubscribe_from_events,
+                AUTOMOD_EVENT_SUBSCRIPTIONS, BROADCASTER_EVENT_SUBSCRIPTIONS,
+                INITIAL_EVENT_SUBSCRIPTIONS, POLL_EVENT_SUBSCRIPTIONS, STREAM_EVENT_SUBSCRIPTIONS,
+                USER_EVENT_SUBSCRIPTIONS, subscribe_to_broadcaster_events,
+                subscribe_to_channel_events, subscribe_to_events, subscribe_to_moderator_events,
+                subscribe_to_user_events, unsubscribe_from_events,
             },
             subscriptions::Subscription,
         },
-        context::TwitchWebsocketContext,
+        context::{JoinedChannel, TwitchWebsocketContext},
+        handlers::stream_status::handle_stream_status_join,
         models::ReceivedTwitchMessage,
         roomstate::handle_roomstate,
     },
 };
 
-/// Handling either the terminal joining a new channel, or the application just starting up
-pub async fn handle_channel_join(
+fn keepalive_timeout(received_message: &ReceivedTwitchMessage) -> Option<Duration> {
+    received_message
+        .keepalive_timeout_seconds()
+        .map(|seconds| Duration::from_secs(seconds as u64))
+}
+
+/// Subscribes to the events of a channel, adding it to the channels joined in this session
+async fn join_channel(
     context: &mut TwitchWebsocketContext,
     event_tx: &Sender<Event>,
+    channel_id: String,
     channel_name: String,
-    first_channel: bool,
 ) -> Result<()> {
     let twitch_client = context.twitch_client().context("Twitch client not found")?;
     let twitch_oauth = context.oauth().context("No OAuth found")?;
-    let current_subscriptions: Vec<Subscription> = context
-        .event_subscriptions()
-        .keys()
-        .map(std::borrow::ToOwned::to_owned)
-        .collect();
 
-    // Unsubscribe from previous channel
-    if !first_channel {
-        unsubscribe_from_events(
-            &twitch_client,
-            context.event_subscriptions(),
-            current_subscriptions.clone(),
+    let mut event_subscriptions = subscribe_to_events(
+        &twitch_client,
+        twitch_oauth,
+        context.session_id().cloned(),
+        channel_id.clone(),
+        INITIAL_EVENT_SUBSCRIPTIONS.to_vec(),
+    )
+    .await
+    .context(format!(
+        "Failed to subscribe to new channel '{channel_name}'"
+    ))?;
+
+    // The chat can be used without knowing whether the channel is live, so this isn't an error
+    match subscribe_to_channel_events(
+        &twitch_client,
+        context.session_id().cloned(),
+        channel_id.clone(),
+        STREAM_EVENT_SUBSCRIPTIONS.to_vec(),
+    )
+    .await
+    {
+        Ok(stream_subscriptions) => event_subscriptions.extend(stream_subscriptions),
+        Err(err) => warn!("Failed to subscribe to the stream status of '{channel_name}': {err}"),
+    }
+
+    // Held messages can only be seen by moderators, which the user isn't in most channels
+    match subscribe_to_moderator_events(
+        &twitch_client,
+        twitch_oauth,
+        context.session_id().cloned(),
+        channel_id.clone(),
+        AUTOMOD_EVENT_SUBSCRIPTIONS.to_vec(),
+    )
+    .await
+    {
+        Ok(automod_subscriptions) => event_subscriptions.extend(automod_subscriptions),
+        Err(err) => debug!(
+            "Not subscribed to AutoMod in '{channel_name}', the user may not moderate it: {err}"
+        ),
+    }
+
+    let mut joined_channel = JoinedChannel::new(channel_name, HashMap::new());
+
+    if twitch_oauth.user_id().as_ref() == Some(&channel_id) {
+        join_own_channel(
+            context,
+            event_tx,
+            &channel_id,
+            &mut joined_channel,
+            &mut event_subscriptions,
         )
         .await?;
     }
 
-    // Subscribe to new channel
-    let channel_id = if first_channel {
-        context
-            .channel_id()
-            .context("Failed to get channel ID from context")?
-    } else {
-        &get_channel_id(&twitch_client, &channel_name).await?
+    joined_channel.set_event_subscriptions(event_subscriptions);
+    context.add_joined_channel(channel_id.clone(), joined_channel);
+
+    if let Err(err) = handle_stream_status_join(context, event_tx, &channel_id).await {
+        warn!("Failed to get the stream status of a joined channel: {err}");
+    }
+
+    Ok(())
+}
+
+/// Subscribes to the events of the user's own channel that only the broadcaster can see.
+/// Chat works without channel points, polls or predictions, so a token without their scopes isn't an error.
+async fn join_own_channel(
+    context: &TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    channel_id: &str,
+    joined_channel: &mut JoinedChannel,
+    event_subscriptions: &mut HashMap<Subscription, String>,
+) -> Result<()> {
+    let twitch_client = context.twitch_client().context("Twitch client not found")?;
+    let twitch_oauth = context.oauth().context("No OAuth found")?;
+
+    let redemption_events = async {
+        let redemption_subscriptions = subscribe_to_broadcaster_events(
+            &twitch_client,
+            twitch_oauth,
+            context.session_id().cloned(),
+            BROADCASTER_EVENT_SUBSCRIPTIONS.to_vec(),
+        )
+        .await?;
+        // Every reward is needed to name the redemptions of the ones made on the Twitch dashboard
+        let custom_rewards = get_custom_rewards(&twitch_client, channel_id, false).await?;
+
+        Ok::<_, Error>((redemption_subscriptions, custom_rewards))
     };
 
-    let new_subscriptions = subscribe_to_events(
+    match redemption_events.await {
+        Ok((redemption_subscriptions, custom_rewards)) => {
+            event_subscriptions.extend(redemption_subscriptions);
+            joined_channel.set_custom_rewards(custom_rewards);
+        }
+        Err(err) => {
+            warn!("Failed to subscribe to channel point redemptions: {err}");
+
+            event_tx
+                .send(
+                    DataBuilder::system(
+                        "Channel point redemptions can't be shown, the token may be missing the channel:manage:redemptions scope"
+                            .to_string(),
+                    )
+                    .into(),
+                )
+                .await?;
+        }
+    }
+
+    match subscribe_to_broadcaster_events(
         &twitch_client,
         twitch_oauth,
         context.session_id().cloned(),
-        channel_id.clone(),
-        current_subscriptions,
+        POLL_EVENT_SUBSCRIPTIONS.to_vec(),
     )
     .await
-    .context(format!(
-        "Failed to subscribe to new channel '{channel_name}'"
-    ))?;
+    {
+        Ok(poll_subscriptions) => event_subscriptions.extend(poll_subscriptions),
+        Err(err) => {
+            warn!("Failed to subscribe to polls and predictions: {err}");
+
+            event_tx
+                .send(
+                    DataBuilder::system(
+                        "Polls and predictions can't be shown, the token may be missing the channel:manage:polls and channel:manage:predictions scopes"
+                            .to_string(),
+                    )
+                    .into(),
+                )
+                .await?;
+        }
+    }
+
+    Ok(())
+}
 
-    let context_channel_id = channel_id.clone();
+/// Unsubscribes from the events of a channel, removing it from the channels joined in this session
+async fn leave_channel(context: &mut TwitchWebsocketContext, channel_id: &str) -> Result<()> {
+    let twitch_client = context.twitch_client().context("Twitch client not found")?;
 
-    context.set_event_subscriptions(new_subscriptions);
+    let Some(channel) = context.remove_joined_channel(channel_id) else {
+        return Ok(());
+    };
 
-    // Set old channel to new channel
-    context.set_channel_name(Some(channel_name.clone()));
-    context.set_channel_id(Some(context_channel_id));
+    let subscription_types: Vec<Subscription> =
+        channel.event_subscriptions().keys().cloned().collect();
 
-    // Notify frontend that new channel has been joined
+    unsubscribe_from_events(
+        &twitch_client,
+        channel.event_subscriptions(),
+        subscription_types,
+    )
+    .await
+}
+
+/// Makes a joined channel the one that messages and commands are sent to
+fn focus_channel(context: &mut TwitchWebsocketContext, channel_id: String, channel_name: String) {
+    context.set_channel_name(Some(channel_name));
+    context.set_channel_id(Some(channel_id));
+}
+
+/// Lets the frontend know that a channel has been joined, along with its chat settings
+async fn notify_channel_join(
+    context: &TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    channel_name: &str,
+) -> Result<()> {
     event_tx
         .send(DataBuilder::twitch(format!("Joined #{channel_name}")).into())
         .await
@@ -93,6 +229,91 @@ pub async fn handle_channel_join(
     Ok(())
 }
 
+/// Handling either the terminal joining a new channel in place of the current one,
+/// or the application just starting up
+pub async fn handle_channel_join(
+    context: &mut TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    channel_name: String,
+    first_channel: bool,
+) -> Result<()> {
+    let twitch_client = context.twitch_client().context("Twitch client not found")?;
+
+    // Leave the channel that is being replaced
+    if !first_channel {
+        if let Some(channel_id) = context.channel_id().cloned() {
+            leave_channel(context, &channel_id).await?;
+        }
+    }
+
+    let channel_id = if first_channel {
+        context
+            .channel_id()
+            .context("Failed to get channel ID from context")?
+            .clone()
+    } else {
+        get_channel_id(&twitch_client, &channel_name).await?
+    };
+
+    // The channel may have already been joined alongside the one that was replaced
+    if context.joined_channel(&channel_id).is_none() {
+        join_channel(context, event_tx, channel_id.clone(), channel_name.clone()).await?;
+    }
+
+    focus_channel(context, channel_id, channel_name.clone());
+
+    notify_channel_join(context, event_tx, &channel_name).await
+}
+
+/// Joining another channel alongside the ones that have already been joined
+pub async fn handle_channel_open(
+    context: &mut TwitchWebsocketContext,
+    event_tx: &Sender<Event>,
+    channel_name: String,
+) -> Result<()> {
+    if let Some(channel_id) = context.joined_channel_id(&channel_name).cloned() {
+        focus_channel(context, channel_id, channel_name);
+
+        return Ok(());
+    }
+
+    let twitch_client = context.twitch_client().context("Twitch client not found")?;
+    let channel_id = get_channel_id(&twitch_client, &channel_name).await?;
+
+    join_channel(context, event_tx, channel_id.clone(), channel_name.clone()).await?;
+    focus_channel(context, channel_id, channel_name.clone());
+
+    notify_channel_join(context, event_tx, &channel_name).await
+}
+
+/// Leaving one of the joined channels
+pub async fn handle_channel_leave(
+    context: &mut TwitchWebsocketContext,
+    channel_name: &str,
+) -> Result<()> {
+    let channel_id = context
+        .joined_channel_id(channel_name)
+        .context(format!("Channel '{channel_name}' has not been joined"))?
+        .clone();
+
+    leave_channel(context, &channel_id).await
+}
+
+/// Switching which of the joined channels messages and commands are sent to
+pub fn handle_channel_focus(
+    context: &mut TwitchWebsocketContext,
+    channel_name: String,
+) -> Result<()> {
+    let channel_id = context
+        .joined_channel_id(&channel_name)
+        .context(format!("Channel '{channel_name}' has not been joined"))?
+        .clone();
+
+    focus_channel(context, channel_id, channel_name);
+
+    Ok(())
+}
+
 pub async fn handle_welcome_message(
     context: &mut TwitchWebsocketContext,
     event_tx: &Sender<Event>,
@@ -103,6 +324,7 @@ pub async fn handle_welcome_message(
 
     let session_id = received_message.session_id();
     context.set_session_id(session_id.clone());
+    context.set_keepalive_timeout(keepalive_timeout(&received_message));
 
     let twitch_client = context
         .twitch_client()
@@ -114,19 +336,162 @@ pub async fn handle_welcome_message(
         .clone();
     let channel_id = get_channel_id(&twitch_client, &channel_name).await?;
 
-    context.set_channel_id(Some(channel_id.clone()));
-
-    let initial_event_subscriptions: HashMap<_, _> = INITIAL_EVENT_SUBSCRIPTIONS
-        .iter()
-        .cloned()
-        .map(|item| (item, String::new()))
-        .collect();
-
-    context.set_event_subscriptions(initial_event_subscriptions);
+    context.set_channel_id(Some(channel_id));
 
     handle_channel_join(context, event_tx, channel_name, true)
         .await
         .context("Failed to join first channel")?;
 
+    // Chat works without whispers, so a token without the whisper scopes isn't an error
+    if let Err(err) = subscribe_user_events(context).await {
+        warn!("Failed to subscribe to whispers: {err}");
+
+        event_tx
+            .send(
+                DataBuilder::system(
+                    "Whispers can't be received, the token may be missing the user:read:whispers scope"
+                        .to_string(),
+                )
+                .into(),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Subscribes to the events about the user in the current session
+async fn subscribe_user_events(context: &mut TwitchWebsocketContext) -> Result<()> {
+    let twitch_client = context.twitch_client().context("Twitch client not found")?;
+    let twitch_oauth = context.oauth().context("No OAuth found")?;
+
+    let user_event_subscriptions = subscribe_to_user_events(
+        &twitch_client,
+        twitch_oauth,
+        context.session_id().cloned(),
+        USER_EVENT_SUBSCRIPTIONS.to_vec(),
+    )
+    .await?;
+
+    context.set_user_event_subscriptions(user_event_subscriptions);
+
+    Ok(())
+}
+
+/// Handles the welcome message of a session that replaces a previous one.
+///
+/// When Twitch asks the client to move to a `reconnect_url`, every subscription is carried over to the new session.
+/// If the previous connection was lost instead, its subscriptions are disabled and have to be created again.
+///
+/// <https://dev.twitch.tv/docs/eventsub/handling-websocket-events/#reconnect-message>
+pub async fn handle_reconnect_welcome_message(
+    context: &mut TwitchWebsocketContext,
+    message: Utf8Bytes,
+    resubscribe: bool,
+) -> Result<()> {
+    let received_message = serde_json::from_str::<ReceivedTwitchMessage>(&message)
+        .context("Could not convert reconnect welcome message to received message")?;
+
+    context.set_session_id(received_message.session_id());
+    context.set_keepalive_timeout(keepalive_timeout(&received_message));
+
+    if !resubscribe {
+        return Ok(());
+    }
+
+    let twitch_client = context
+        .twitch_client()
+        .context("Failed to get twitch client from context")?;
+    let twitch_oauth = context.oauth().context("No OAuth found")?.clone();
+    let channel_ids: Vec<String> = context.joined_channels().keys().cloned().collect();
+
+    for channel_id in channel_ids {
+        let Some(channel) = context.joined_channel_mut(&channel_id) else {
+            continue;
+        };
+        let (broadcaster_subscriptions, other_subscriptions): (Vec<_>, Vec<_>) = channel
+            .event_subscriptions()
+            .keys()
+            .cloned()
+            .partition(|subscription| {
+                BROADCASTER_EVENT_SUBSCRIPTIONS.contains(subscription)
+                    || POLL_EVENT_SUBSCRIPTIONS.contains(subscription)
+            });
+        let (stream_subscriptions, other_subscriptions): (Vec<_>, Vec<_>) = other_subscriptions
+            .into_iter()
+            .partition(|subscription| STREAM_EVENT_SUBSCRIPTIONS.contains(subscription));
+        let (automod_subscriptions, current_subscriptions): (Vec<_>, Vec<_>) = other_subscriptions
+            .into_iter()
+            .partition(|subscription| AUTOMOD_EVENT_SUBSCRIPTIONS.contains(subscription));
+
+        let mut new_subscriptions = subscribe_to_events(
+            &twitch_client,
+            &twitch_oauth,
+            received_message.session_id(),
+            channel_id.clone(),
+            current_subscriptions,
+        )
+        .await
+        .context("Failed to resubscribe to events after reconnecting")?;
+
+        // Like when the channel was joined, the chat keeps working without these
+        if !broadcaster_subscriptions.is_empty() {
+            match subscribe_to_broadcaster_events(
+                &twitch_client,
+                &twitch_oauth,
+                received_message.session_id(),
+                broadcaster_subscriptions,
+            )
+            .await
+            {
+                Ok(subscriptions) => new_subscriptions.extend(subscriptions),
+                Err(err) => {
+                    warn!("Failed to resubscribe to broadcaster events after reconnecting: {err}");
+                }
+            }
+        }
+
+        if !stream_subscriptions.is_empty() {
+            match subscribe_to_channel_events(
+                &twitch_client,
+                received_message.session_id(),
+                channel_id.clone(),
+                stream_subscriptions,
+            )
+            .await
+            {
+                Ok(subscriptions) => new_subscriptions.extend(subscriptions),
+                Err(err) => {
+                    warn!("Failed to resubscribe to stream events after reconnecting: {err}");
+                }
+            }
+        }
+
+        if !automod_subscriptions.is_empty() {
+            match subscribe_to_moderator_events(
+                &twitch_client,
+                &twitch_oauth,
+                received_message.session_id(),
+                channel_id.clone(),
+                automod_subscriptions,
+            )
+            .await
+            {
+                Ok(subscriptions) => new_subscriptions.extend(subscriptions),
+                Err(err) => warn!(
+                    "Failed to resubscribe to AutoMod events after reconnecting, the user may no longer moderate the channel: {err}"
+                ),
+            }
+        }
+
+        channel.set_event_subscriptions(new_subscriptions);
+    }
+
+    if !context.user_event_subscriptions().is_empty() {
+        if let Err(err) = subscribe_user_events(context).await {
+            warn!("Failed to resubscribe to user events after reconnecting: {err}");
+        }
+    }
+
     Ok(())
 }
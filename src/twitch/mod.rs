@@ -1,10 +1,14 @@
 pub mod api;
 pub mod badges;
 pub mod channels;
+pub mod cheers;
 pub mod context;
 pub mod handlers;
 pub mod models;
+pub mod notices;
 pub mod oauth;
+pub mod recorder;
+pub mod replay;
 pub mod roomstate;
 pub mod websocket;
 
@@ -1,7 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::api::subscriptions::Subscription;
+use super::{
+    api::{
+        channel_points::CustomReward, polls::PollChoice, predictions::PredictionOutcome,
+        subscriptions::Subscription,
+    },
+    notices::ChatNotice,
+};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ReceivedTwitchMessageMetadata {
@@ -10,11 +16,21 @@ pub struct ReceivedTwitchMessageMetadata {
     message_type: String,
 }
 
+impl ReceivedTwitchMessageMetadata {
+    pub fn message_type(&self) -> &str {
+        self.message_type.as_ref()
+    }
+
+    pub fn message_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.message_timestamp.parse().ok()
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ReceivedTwitchMessageSession {
     connected_at: String,
     id: String,
-    keepalive_timeout_seconds: usize,
+    keepalive_timeout_seconds: Option<usize>,
     reconnect_url: Option<String>,
     recovery_url: Option<String>,
     status: String,
@@ -22,8 +38,15 @@ pub struct ReceivedTwitchMessageSession {
 
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct ReceivedTwitchSubscriptionCondition {
+    /// Empty for events about the user rather than a channel, such as whispers
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     broadcaster_user_id: String,
+    /// Empty for events that are only about the broadcaster, such as channel point redemptions
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     user_id: String,
+    /// Only set for events that need the user to moderate the channel, such as messages held by `AutoMod`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    moderator_user_id: String,
 }
 
 impl ReceivedTwitchSubscriptionCondition {
@@ -32,6 +55,16 @@ impl ReceivedTwitchSubscriptionCondition {
         Self {
             broadcaster_user_id,
             user_id,
+            moderator_user_id: String::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn moderator(broadcaster_user_id: String, moderator_user_id: String) -> Self {
+        Self {
+            broadcaster_user_id,
+            user_id: String::new(),
+            moderator_user_id,
         }
     }
 
@@ -47,6 +80,21 @@ pub struct ReceivedTwitchEventMessageFragmentCheermote {
     tier: usize,
 }
 
+impl ReceivedTwitchEventMessageFragmentCheermote {
+    pub fn prefix(&self) -> &str {
+        self.prefix.as_ref()
+    }
+
+    #[cfg(test)]
+    pub const fn bits(&self) -> usize {
+        self.bits
+    }
+
+    pub const fn tier(&self) -> usize {
+        self.tier
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ReceivedTwitchEventMessageFragmentEmote {
     id: Option<String>,
@@ -62,6 +110,12 @@ pub struct ReceivedTwitchEventMessageFragmentMention {
     user_name: String,
 }
 
+impl ReceivedTwitchEventMessageFragmentMention {
+    pub fn user_id(&self) -> &str {
+        self.user_id.as_ref()
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ReceivedTwitchEventMessageFragment {
     #[serde(rename = "type")]
@@ -89,6 +143,14 @@ impl ReceivedTwitchEventBadges {
     pub fn set_id(&self) -> &str {
         self.set_id.as_ref()
     }
+
+    pub fn id(&self) -> &str {
+        self.id.as_ref()
+    }
+
+    pub fn info(&self) -> &str {
+        self.info.as_ref()
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -97,12 +159,16 @@ pub struct ReceivedTwitchEventCheer {
 }
 
 impl ReceivedTwitchEventCheer {
-    #[cfg(test)]
     pub const fn bits(&self) -> u64 {
         self.bits
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReceivedTwitchEventAnnouncement {
+    color: String,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ReceivedTwitchEventReply {
     parent_message_id: String,
@@ -117,10 +183,29 @@ pub struct ReceivedTwitchEventReply {
 }
 
 impl ReceivedTwitchEventReply {
-    #[cfg(test)]
+    pub const fn parent_message_id(&self) -> &String {
+        &self.parent_message_id
+    }
+
     pub const fn parent_message_body(&self) -> &String {
         &self.parent_message_body
     }
+
+    pub const fn parent_user_name(&self) -> &String {
+        &self.parent_user_name
+    }
+}
+
+/// Why `AutoMod` held a message, when it was caught by one of its categories rather than a blocked term
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReceivedTwitchEventAutoMod {
+    category: String,
+    level: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReceivedTwitchEventWhisper {
+    text: String,
 }
 
 /// All attributes that are to come through during a channel chat notification event
@@ -128,8 +213,11 @@ impl ReceivedTwitchEventReply {
 /// <https://dev.twitch.tv/docs/eventsub/eventsub-reference/#channel-chat-notification-event>
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ReceivedTwitchEvent {
+    #[serde(default)]
     broadcaster_user_id: String,
+    #[serde(default)]
     broadcaster_user_login: String,
+    #[serde(default)]
     broadcaster_user_name: String,
     chatter_user_id: Option<String>,
     chatter_user_login: Option<String>,
@@ -139,6 +227,9 @@ pub struct ReceivedTwitchEvent {
     message_type: Option<String>,
     message: Option<ReceivedTwitchEventMessage>,
     system_message: Option<String>,
+    notice_type: Option<String>,
+    announcement: Option<ReceivedTwitchEventAnnouncement>,
+    shared_chat_announcement: Option<ReceivedTwitchEventAnnouncement>,
     badges: Option<Vec<ReceivedTwitchEventBadges>>,
     cheer: Option<ReceivedTwitchEventCheer>,
     reply: Option<ReceivedTwitchEventReply>,
@@ -161,6 +252,24 @@ pub struct ReceivedTwitchEvent {
     banned_at: Option<DateTime<Utc>>,
     ends_at: Option<DateTime<Utc>>,
     is_permanent: Option<bool>,
+    from_user_id: Option<String>,
+    from_user_login: Option<String>,
+    from_user_name: Option<String>,
+    whisper: Option<ReceivedTwitchEventWhisper>,
+    user_input: Option<String>,
+    reward: Option<CustomReward>,
+    id: Option<String>,
+    title: Option<String>,
+    choices: Option<Vec<PollChoice>>,
+    outcomes: Option<Vec<PredictionOutcome>>,
+    status: Option<String>,
+    locks_at: Option<DateTime<Utc>>,
+    ended_at: Option<DateTime<Utc>>,
+    winning_outcome_id: Option<String>,
+    started_at: Option<DateTime<Utc>>,
+    category_name: Option<String>,
+    automod: Option<ReceivedTwitchEventAutoMod>,
+    held_at: Option<DateTime<Utc>>,
 }
 
 impl ReceivedTwitchEventMessageFragmentEmote {
@@ -178,6 +287,24 @@ impl ReceivedTwitchEventMessageFragment {
         self.emote.is_some().then_some(&self.text)
     }
 
+    pub const fn cheermote(&self) -> Option<&ReceivedTwitchEventMessageFragmentCheermote> {
+        self.cheermote.as_ref()
+    }
+
+    pub const fn mention(&self) -> Option<&ReceivedTwitchEventMessageFragmentMention> {
+        self.mention.as_ref()
+    }
+
+    /// How the mention was written in the message, such as `@viewer32`
+    pub fn mention_name(&self) -> Option<&String> {
+        self.mention.is_some().then_some(&self.text)
+    }
+
+    /// The word that a cheermote fragment was written as, such as `Cheer100`
+    pub fn cheermote_name(&self) -> Option<&String> {
+        self.cheermote.is_some().then_some(&self.text)
+    }
+
     #[cfg(test)]
     pub fn text(&self) -> String {
         self.text.clone()
@@ -185,6 +312,10 @@ impl ReceivedTwitchEventMessageFragment {
 }
 
 impl ReceivedTwitchEvent {
+    pub const fn broadcaster_user_login(&self) -> &String {
+        &self.broadcaster_user_login
+    }
+
     pub const fn chatter_user_id(&self) -> Option<&String> {
         self.chatter_user_id.as_ref()
     }
@@ -197,6 +328,10 @@ impl ReceivedTwitchEvent {
         self.message_id.as_ref()
     }
 
+    pub const fn color(&self) -> Option<&String> {
+        self.color.as_ref()
+    }
+
     pub fn message_text(&self) -> Option<String> {
         self.message.as_ref().map(|message| message.text.clone())
     }
@@ -205,11 +340,22 @@ impl ReceivedTwitchEvent {
         self.system_message.as_ref()
     }
 
+    /// What a chat notification is about, if this is one
+    pub fn notice(&self) -> Option<ChatNotice> {
+        let notice_type = self.notice_type.as_ref()?;
+        let announcement_color = self
+            .announcement
+            .as_ref()
+            .or(self.shared_chat_announcement.as_ref())
+            .map(|announcement| announcement.color.as_str());
+
+        Some(ChatNotice::new(notice_type, announcement_color))
+    }
+
     pub fn badges(&self) -> Option<Vec<ReceivedTwitchEventBadges>> {
         self.badges.clone()
     }
 
-    #[cfg(test)]
     pub const fn cheer(&self) -> Option<&ReceivedTwitchEventCheer> {
         self.cheer.as_ref()
     }
@@ -232,7 +378,43 @@ impl ReceivedTwitchEvent {
         })
     }
 
-    #[cfg(test)]
+    pub fn mention_fragments(&self) -> Option<Vec<ReceivedTwitchEventMessageFragment>> {
+        self.message.as_ref().map(|message| {
+            message
+                .fragments
+                .iter()
+                .filter(|fragment| fragment.mention.is_some())
+                .cloned()
+                .collect()
+        })
+    }
+
+    pub fn cheermote_fragments(&self) -> Option<Vec<ReceivedTwitchEventMessageFragment>> {
+        self.message.as_ref().map(|message| {
+            message
+                .fragments
+                .iter()
+                .filter(|fragment| fragment.cheermote.is_some())
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// If the message has at least one emote, and nothing but whitespace between its emotes
+    pub fn emote_only(&self) -> bool {
+        self.message.as_ref().is_some_and(|message| {
+            message
+                .fragments
+                .iter()
+                .any(|fragment| fragment.emote.is_some() || fragment.cheermote.is_some())
+                && message.fragments.iter().all(|fragment| {
+                    fragment.emote.is_some()
+                        || fragment.cheermote.is_some()
+                        || fragment.text.trim().is_empty()
+                })
+        })
+    }
+
     pub const fn reply(&self) -> Option<&ReceivedTwitchEventReply> {
         self.reply.as_ref()
     }
@@ -257,6 +439,96 @@ impl ReceivedTwitchEvent {
     pub const fn user_name(&self) -> Option<&String> {
         self.user_name.as_ref()
     }
+
+    /// Who sent a whisper to the user, if this is a whisper
+    pub const fn whisper_sender(&self) -> Option<&String> {
+        self.from_user_name.as_ref()
+    }
+
+    pub fn whisper_text(&self) -> Option<&str> {
+        self.whisper.as_ref().map(|whisper| whisper.text.as_str())
+    }
+
+    /// The channel points reward that a chat message was sent with, if any
+    pub const fn channel_points_custom_reward_id(&self) -> Option<&String> {
+        self.channel_points_custom_reward_id.as_ref()
+    }
+
+    /// The reward that was redeemed, if this is a channel points redemption
+    pub const fn reward(&self) -> Option<&CustomReward> {
+        self.reward.as_ref()
+    }
+
+    /// What the viewer wrote when redeeming a reward that asks for text
+    pub fn user_input(&self) -> Option<&str> {
+        self.user_input.as_deref()
+    }
+
+    /// The ID of the poll or prediction that this event is about
+    pub const fn id(&self) -> Option<&String> {
+        self.id.as_ref()
+    }
+
+    pub const fn title(&self) -> Option<&String> {
+        self.title.as_ref()
+    }
+
+    pub const fn choices(&self) -> Option<&Vec<PollChoice>> {
+        self.choices.as_ref()
+    }
+
+    pub const fn outcomes(&self) -> Option<&Vec<PredictionOutcome>> {
+        self.outcomes.as_ref()
+    }
+
+    /// How a poll or prediction ended, such as "completed" or "canceled"
+    pub const fn status(&self) -> Option<&String> {
+        self.status.as_ref()
+    }
+
+    /// When a poll ends, or when a timeout is over
+    pub const fn ends_at(&self) -> Option<DateTime<Utc>> {
+        self.ends_at
+    }
+
+    /// When a prediction stops accepting predictions
+    pub const fn locks_at(&self) -> Option<DateTime<Utc>> {
+        self.locks_at
+    }
+
+    pub const fn ended_at(&self) -> Option<DateTime<Utc>> {
+        self.ended_at
+    }
+
+    pub const fn winning_outcome_id(&self) -> Option<&String> {
+        self.winning_outcome_id.as_ref()
+    }
+
+    /// When a stream went live, or when a poll or prediction began
+    pub const fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.started_at
+    }
+
+    /// The category that a channel changed to
+    pub const fn category_name(&self) -> Option<&String> {
+        self.category_name.as_ref()
+    }
+
+    /// Why a user was banned, or why `AutoMod` held a message, such as `automod` or `blocked_term`
+    pub const fn reason(&self) -> Option<&String> {
+        self.reason.as_ref()
+    }
+
+    /// The `AutoMod` category that a held message was caught by, along with its level
+    pub fn automod_category(&self) -> Option<(&str, usize)> {
+        self.automod
+            .as_ref()
+            .map(|automod| (automod.category.as_str(), automod.level))
+    }
+
+    pub const fn held_at(&self) -> Option<DateTime<Utc>> {
+        self.held_at
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -319,11 +591,24 @@ impl ReceivedTwitchSubscription {
         }
     }
 
+    /// A subscription to the events of a channel that the user moderates
+    #[must_use]
+    pub fn moderator(channel_id: String, moderator_user_id: String, session_id: String) -> Self {
+        Self {
+            condition: ReceivedTwitchSubscriptionCondition::moderator(
+                channel_id,
+                moderator_user_id,
+            ),
+            ..Self::new(String::new(), String::new(), session_id)
+        }
+    }
+
     pub const fn subscription_type(&self) -> Option<&Subscription> {
         self.subscription_type.as_ref()
     }
 
-    pub const fn set_subscription_type(&mut self, subscription_type: Subscription) {
+    pub fn set_subscription_type(&mut self, subscription_type: Subscription) {
+        self.version = subscription_type.version().to_string();
         self.subscription_type = Some(subscription_type);
     }
 
@@ -383,6 +668,39 @@ impl ReceivedTwitchMessage {
             .map(|session| session.id.clone())
     }
 
+    /// The URL that the client should reconnect to, only sent with `session_reconnect` messages
+    #[must_use]
+    pub fn reconnect_url(&self) -> Option<String> {
+        self.payload
+            .as_ref()?
+            .session
+            .as_ref()?
+            .reconnect_url
+            .clone()
+    }
+
+    /// How long the server is allowed to stay silent before the connection should be considered lost
+    #[must_use]
+    pub fn keepalive_timeout_seconds(&self) -> Option<usize> {
+        self.payload
+            .as_ref()?
+            .session
+            .as_ref()?
+            .keepalive_timeout_seconds
+    }
+
+    pub fn message_type(&self) -> Option<&str> {
+        self.metadata
+            .as_ref()
+            .map(ReceivedTwitchMessageMetadata::message_type)
+    }
+
+    pub fn message_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.metadata
+            .as_ref()
+            .and_then(ReceivedTwitchMessageMetadata::message_timestamp)
+    }
+
     #[must_use]
     pub fn event(&self) -> Option<ReceivedTwitchEvent> {
         self.payload.as_ref()?.event.clone()
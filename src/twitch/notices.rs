@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use tui::style::{Color, Color::Rgb, Style};
+
+use crate::utils::styles::{
+    BITS_NOTICE_STYLE, BOLD, CHARITY_NOTICE_STYLE, GIFT_NOTICE_STYLE, NO_COLOR, NOTICE_STYLE,
+    RAID_NOTICE_STYLE, REDEMPTION_NOTICE_STYLE, SUB_NOTICE_STYLE,
+};
+
+/// The color that a broadcaster or moderator picked for an announcement
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnnouncementColor {
+    /// The channel's accent color, which is left to the color of the announcer
+    #[default]
+    Primary,
+    Blue,
+    Green,
+    Orange,
+    Purple,
+}
+
+impl From<&str> for AnnouncementColor {
+    fn from(color: &str) -> Self {
+        match color.to_uppercase().as_str() {
+            "BLUE" => Self::Blue,
+            "GREEN" => Self::Green,
+            "ORANGE" => Self::Orange,
+            "PURPLE" => Self::Purple,
+            _ => Self::Primary,
+        }
+    }
+}
+
+impl AnnouncementColor {
+    /// The color of the announcement, or nothing if it should be the color of the announcer
+    pub fn color(self) -> Option<Color> {
+        if *NO_COLOR {
+            return None;
+        }
+
+        match self {
+            Self::Primary => None,
+            Self::Blue => Some(Rgb(0, 214, 214)),
+            Self::Green => Some(Rgb(0, 219, 132)),
+            Self::Orange => Some(Rgb(255, 179, 26)),
+            Self::Purple => Some(Rgb(145, 70, 255)),
+        }
+    }
+}
+
+/// What a channel chat notification is about
+///
+/// <https://dev.twitch.tv/docs/eventsub/eventsub-subscription-types/#channelchatnotification>
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatNotice {
+    Sub,
+    Resub,
+    SubGift,
+    CommunitySubGift,
+    GiftPaidUpgrade,
+    PrimePaidUpgrade,
+    PayItForward,
+    Raid,
+    Unraid,
+    Announcement(AnnouncementColor),
+    BitsBadgeTier,
+    CharityDonation,
+    /// A viewer spent channel points on a custom reward.
+    /// Twitch doesn't send these as chat notifications, but they're shown the same way.
+    Redemption,
+    /// A notice that Twitch added after this was written
+    Other,
+}
+
+impl ChatNotice {
+    /// Parses the `notice_type` of a notification.
+    /// Notices from other channels in a shared chat session are treated the same as the ones from this channel.
+    pub fn new(notice_type: &str, announcement_color: Option<&str>) -> Self {
+        match notice_type
+            .strip_prefix("shared_chat_")
+            .unwrap_or(notice_type)
+        {
+            "sub" => Self::Sub,
+            "resub" => Self::Resub,
+            "sub_gift" => Self::SubGift,
+            "community_sub_gift" => Self::CommunitySubGift,
+            "gift_paid_upgrade" => Self::GiftPaidUpgrade,
+            "prime_paid_upgrade" => Self::PrimePaidUpgrade,
+            "pay_it_forward" => Self::PayItForward,
+            "raid" => Self::Raid,
+            "unraid" => Self::Unraid,
+            "announcement" => {
+                Self::Announcement(announcement_color.map(Into::into).unwrap_or_default())
+            }
+            "bits_badge_tier" => Self::BitsBadgeTier,
+            "charity_donation" => Self::CharityDonation,
+            _ => Self::Other,
+        }
+    }
+
+    /// If the chatter can attach a message of their own, which is shown under the notice
+    pub const fn has_user_message(self) -> bool {
+        matches!(self, Self::Sub | Self::Resub)
+    }
+
+    /// How the text of the notice is shown, where announcements in the channel's accent color
+    /// take the color of the announcer instead
+    pub fn style(self, announcer_color: Color) -> Style {
+        match self {
+            Self::Sub | Self::Resub | Self::GiftPaidUpgrade | Self::PrimePaidUpgrade => {
+                *SUB_NOTICE_STYLE
+            }
+            Self::SubGift | Self::CommunitySubGift | Self::PayItForward => *GIFT_NOTICE_STYLE,
+            Self::Raid | Self::Unraid => *RAID_NOTICE_STYLE,
+            Self::BitsBadgeTier => *BITS_NOTICE_STYLE,
+            Self::CharityDonation => *CHARITY_NOTICE_STYLE,
+            Self::Redemption => *REDEMPTION_NOTICE_STYLE,
+            Self::Announcement(color) => Style::default()
+                .fg(color.color().unwrap_or(announcer_color))
+                .add_modifier(*BOLD),
+            Self::Other => *NOTICE_STYLE,
+        }
+    }
+}
@@ -31,36 +31,20 @@ pub struct TwitchOauth {
 impl TwitchOauth {
     pub async fn init(&mut self, config: SharedCoreConfig) -> Result<Self> {
         let token = config.twitch.token.as_ref();
-        self.init_oauth(token).await?;
+        self.init_oauth(token, &config.twitch.oauth_validate_url)
+            .await?;
         self.init_client(token)?;
 
         Ok(self.to_owned())
     }
 
-    async fn init_oauth(&mut self, token: Option<&String>) -> Result<()> {
+    async fn init_oauth(&mut self, token: Option<&String>, validate_url: &str) -> Result<()> {
         if self.inner_oauth.is_some() {
             warn!("Twitch OAuth tried to re-initialize. Maybe a second call happened somewhere?");
             return Ok(());
         }
 
-        let token = token
-            .context("Twitch token is empty")?
-            .strip_prefix("oauth:")
-            .context("token does not start with `oauth:`")?;
-
-        // Strips the `oauth:` prefix if it exists
-        let token = token.strip_prefix("oauth:").unwrap_or(token);
-
-        let client = Client::new();
-
-        let data = client
-            .get("https://id.twitch.tv/oauth2/validate")
-            .header(AUTHORIZATION, &format!("OAuth {token}"))
-            .send()
-            .await?
-            .error_for_status()?;
-
-        let twitch_oauth = data.json::<TwitchOauthInner>().await?;
+        let twitch_oauth = validate_token(token, validate_url).await?;
 
         info!(
             "Authentication successful. Enabled scopes: {:?}",
@@ -114,3 +98,36 @@ impl TwitchOauth {
             .map(|oauth| oauth.client_id.clone())
     }
 }
+
+/// Asks Twitch who the token belongs to, which fails if it is invalid, expired or revoked
+///
+/// <https://dev.twitch.tv/docs/authentication/validate-tokens/>
+async fn validate_token(token: Option<&String>, validate_url: &str) -> Result<TwitchOauthInner> {
+    let token = token
+        .context("Twitch token is empty")?
+        .strip_prefix("oauth:")
+        .context("token does not start with `oauth:`")?;
+
+    // Strips the `oauth:` prefix if it exists
+    let token = token.strip_prefix("oauth:").unwrap_or(token);
+
+    let client = Client::new();
+
+    let data = client
+        .get(validate_url)
+        .header(AUTHORIZATION, &format!("OAuth {token}"))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(data.json::<TwitchOauthInner>().await?)
+}
+
+/// If Twitch turns the token down when it is validated again, rather than not being reachable
+pub async fn is_token_rejected(token: Option<&String>, validate_url: &str) -> bool {
+    validate_token(token, validate_url).await.is_err_and(|err| {
+        err.downcast_ref::<reqwest::Error>()
+            .and_then(reqwest::Error::status)
+            .is_some_and(|status| status.is_client_error())
+    })
+}
@@ -0,0 +1,106 @@
+use std::{fs::File, io::Write, path::Path};
+
+use chrono::{DateTime, Utc};
+use color_eyre::{Result, eyre::Context};
+use serde::Serialize;
+use serde_json::Value;
+
+const REDACTED: &str = "[redacted]";
+
+/// Keys whose values could be used to take over the session that a frame was received in
+const REDACTED_KEYS: &[&str] = &["session_id", "reconnect_url", "recovery_url"];
+
+/// A single frame as it is written to the capture file
+#[derive(Serialize, Debug)]
+struct RecordedFrame {
+    received_at: DateTime<Utc>,
+    frame: Value,
+}
+
+/// Writes every text frame received from the websocket server to a file, one per line,
+/// so that what Twitch sent can be inspected or replayed later.
+#[derive(Debug)]
+pub struct FrameRecorder {
+    file: File,
+    /// Values that should never end up in the capture file
+    secrets: Vec<String>,
+}
+
+impl FrameRecorder {
+    pub fn new(path: &Path, token: Option<&str>) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create capture file {}", path.display()))?;
+
+        let secrets = token
+            .map(|token| {
+                let token = token.strip_prefix("oauth:").unwrap_or(token);
+
+                vec![token.to_string()]
+            })
+            .unwrap_or_default();
+
+        Ok(Self { file, secrets })
+    }
+
+    pub fn record(&mut self, frame: &str) -> Result<()> {
+        // Frames that can't be parsed are kept as they are, since those are the most interesting ones
+        let mut frame = serde_json::from_str::<Value>(frame)
+            .unwrap_or_else(|_| Value::String(frame.to_string()));
+
+        // Session IDs are also found outside of the session itself, such as in subscriptions
+        if let Some(session_id) = frame
+            .pointer("/payload/session/id")
+            .and_then(Value::as_str)
+            .filter(|session_id| !self.secrets.iter().any(|secret| secret == session_id))
+        {
+            self.secrets.push(session_id.to_string());
+        }
+
+        redact(&mut frame, &self.secrets);
+
+        let recorded_frame = RecordedFrame {
+            received_at: Utc::now(),
+            frame,
+        };
+
+        writeln!(self.file, "{}", serde_json::to_string(&recorded_frame)?)?;
+
+        Ok(())
+    }
+}
+
+/// Replaces anything that identifies the user's session or credentials
+pub fn redact<S: AsRef<str>>(value: &mut Value, secrets: &[S]) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::Object(session)) = map.get_mut("session") {
+                if let Some(id) = session.get_mut("id") {
+                    *id = Value::String(REDACTED.to_string());
+                }
+            }
+
+            for (key, value) in map.iter_mut() {
+                if REDACTED_KEYS.contains(&key.as_str()) && !value.is_null() {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact(value, secrets);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                redact(value, secrets);
+            }
+        }
+        Value::String(string) => {
+            for secret in secrets {
+                let secret = secret.as_ref();
+
+                if !secret.is_empty() && string.contains(secret) {
+                    *string = string.replace(secret, REDACTED);
+                }
+            }
+        }
+        _ => {}
+    }
+}
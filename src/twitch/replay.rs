@@ -0,0 +1,258 @@
+use std::{collections::HashMap, fs::read_to_string, path::PathBuf, time::Duration};
+
+use color_eyre::{Result, eyre::Context};
+use serde_json::Value;
+use tokio::{
+    sync::mpsc::{Receiver, Sender},
+    time::sleep,
+};
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    config::SharedCoreConfig,
+    events::{ConnectionState, Event, TwitchAction, TwitchEvent, TwitchNotification},
+    handlers::data::DataBuilder,
+    twitch::{
+        context::{JoinedChannel, TwitchWebsocketContext},
+        handlers::incoming_message::handle_incoming_message,
+        models::ReceivedTwitchMessage,
+    },
+};
+
+/// Frames captured by the [`FrameRecorder`](crate::twitch::recorder::FrameRecorder) are kept under this key
+const RECORDED_FRAME_KEY: &str = "frame";
+
+fn parse_recorded_message(mut value: Value) -> Result<ReceivedTwitchMessage> {
+    if let Some(frame) = value.get_mut(RECORDED_FRAME_KEY) {
+        value = frame.take();
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Reads every recorded message from a file.
+/// Messages can either be one after another, such as one per line, or inside of a single JSON array.
+/// Frames that aren't messages are skipped, and so is the rest of the file once it stops being JSON,
+/// such as a last line cut off by stopping a recording.
+pub fn parse_recording(contents: &str) -> Vec<ReceivedTwitchMessage> {
+    let mut messages = vec![];
+    let mut skipped_frames = 0;
+
+    let mut push_message = |value| match parse_recorded_message(value) {
+        Ok(message) => messages.push(message),
+        Err(err) => {
+            debug!("Skipping recorded frame that isn't a message: {err}");
+            skipped_frames += 1;
+        }
+    };
+
+    for value in serde_json::Deserializer::from_str(contents).into_iter::<Value>() {
+        match value {
+            Ok(Value::Array(values)) => values.into_iter().for_each(&mut push_message),
+            Ok(value) => push_message(value),
+            Err(err) => {
+                warn!("Stopped reading the recording at invalid JSON: {err}");
+                skipped_frames += 1;
+                break;
+            }
+        }
+    }
+
+    if skipped_frames > 0 {
+        warn!("Skipped {skipped_frames} recorded frame(s) that could not be replayed");
+    }
+
+    messages
+}
+
+/// How long to wait between two recorded messages, given how many times faster than real time to replay them.
+/// A speed of 0 or less replays everything without waiting.
+pub fn replay_delay(
+    previous: &ReceivedTwitchMessage,
+    next: &ReceivedTwitchMessage,
+    speed: f64,
+) -> Duration {
+    if speed.is_nan() || speed <= 0.0 {
+        return Duration::ZERO;
+    }
+
+    let (Some(previous), Some(next)) = (previous.message_timestamp(), next.message_timestamp())
+    else {
+        return Duration::ZERO;
+    };
+
+    // A speed close enough to 0 makes the delay too long to be represented, which is as good as never
+    (next - previous).to_std().map_or(Duration::ZERO, |delay| {
+        Duration::try_from_secs_f64(delay.as_secs_f64() / speed).unwrap_or(Duration::MAX)
+    })
+}
+
+/// Feeds recorded messages through the same handlers as a live session, without connecting to Twitch
+pub struct TwitchReplay;
+
+impl TwitchReplay {
+    pub fn new(
+        config: SharedCoreConfig,
+        path: PathBuf,
+        speed: f64,
+        event_tx: Sender<Event>,
+        twitch_rx: Receiver<TwitchAction>,
+    ) -> Self {
+        let mut actor = TwitchReplayThread {
+            config,
+            context: TwitchWebsocketContext::default(),
+            event_tx,
+            action_rx: twitch_rx,
+        };
+
+        tokio::task::spawn(async move {
+            if let Err(err) = actor.run(path, speed).await {
+                error!("Failed to replay recording: {err}");
+            }
+        });
+
+        Self {}
+    }
+}
+
+struct TwitchReplayThread {
+    config: SharedCoreConfig,
+    context: TwitchWebsocketContext,
+    event_tx: Sender<Event>,
+    action_rx: Receiver<TwitchAction>,
+}
+
+impl TwitchReplayThread {
+    async fn run(&mut self, path: PathBuf, speed: f64) -> Result<()> {
+        let messages = match read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))
+            .map(|contents| parse_recording(&contents))
+        {
+            Ok(messages) => messages,
+            Err(err) => {
+                self.send_system_message(format!("Could not load replay: {err}"))
+                    .await?;
+
+                return Err(err);
+            }
+        };
+
+        info!(
+            "Replaying {} messages from {}",
+            messages.len(),
+            path.display()
+        );
+
+        self.event_tx
+            .send(Event::Twitch(TwitchEvent::Notification(
+                TwitchNotification::ConnectionState(ConnectionState::Connected),
+            )))
+            .await?;
+
+        let mut previous: Option<ReceivedTwitchMessage> = None;
+
+        for message in messages {
+            if let Some(previous) = &previous {
+                self.wait(replay_delay(previous, &message, speed)).await?;
+            }
+
+            self.join_recorded_channel(&message).await?;
+
+            if let Err(err) =
+                handle_incoming_message(&mut self.context, &self.event_tx, message.clone()).await
+            {
+                error!("Failed to handle replayed message: {err}");
+            }
+
+            previous = Some(message);
+        }
+
+        self.send_system_message("Replay finished".to_string())
+            .await?;
+
+        while let Some(action) = self.action_rx.recv().await {
+            self.handle_twitch_action(action).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Waits for the next message to be due, while still responding to the user
+    async fn wait(&mut self, delay: Duration) -> Result<()> {
+        // Sleeping caps delays that are too long, where adding them to the current time would overflow
+        let deadline = sleep(delay);
+        tokio::pin!(deadline);
+
+        loop {
+            tokio::select! {
+                biased;
+
+                Some(action) = self.action_rx.recv() => {
+                    self.handle_twitch_action(action).await?;
+                }
+                () = &mut deadline => {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Since there is nothing to join while replaying, each recorded channel is joined the first time
+    /// that it shows up, and opened in twt so that its messages have somewhere to go
+    async fn join_recorded_channel(&mut self, message: &ReceivedTwitchMessage) -> Result<()> {
+        let Some(condition) = message.subscription_condition() else {
+            return Ok(());
+        };
+
+        let channel_id = condition.broadcaster_user_id();
+
+        if self.context.joined_channel(channel_id).is_some() {
+            return Ok(());
+        }
+
+        let channel_name = message
+            .event()
+            .map(|event| event.broadcaster_user_login().clone())
+            .filter(|login| !login.is_empty())
+            .unwrap_or_else(|| self.config.twitch.channel.clone());
+
+        self.context.add_joined_channel(
+            channel_id.clone(),
+            JoinedChannel::new(channel_name.clone(), HashMap::new()),
+        );
+
+        self.event_tx
+            .send(Event::Twitch(TwitchEvent::Action(
+                TwitchAction::OpenChannel(channel_name),
+            )))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn handle_twitch_action(&self, action: TwitchAction) -> Result<()> {
+        match action {
+            TwitchAction::Message(_)
+            | TwitchAction::Reply { .. }
+            | TwitchAction::DeleteMessage(_)
+            | TwitchAction::TimeoutUser { .. } => {
+                self.send_system_message(
+                    "Nothing can be sent to Twitch while replaying a recording".to_string(),
+                )
+                .await
+            }
+            TwitchAction::JoinChannel(_)
+            | TwitchAction::OpenChannel(_)
+            | TwitchAction::LeaveChannel(_)
+            | TwitchAction::FocusChannel(_) => Ok(()),
+        }
+    }
+
+    async fn send_system_message(&self, message: String) -> Result<()> {
+        self.event_tx
+            .send(DataBuilder::system(message).into())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -346,3 +346,97 @@ fn test_twitch_command_category_valid() {
         TwitchCommand::Category("name with space".to_string())
     );
 }
+
+#[test]
+fn test_twitch_command_whisper_valid() {
+    assert_eq!(
+        TwitchCommand::from_str("w username Hello There").unwrap(),
+        TwitchCommand::Whisper("username".to_string(), "Hello There".to_string())
+    );
+    assert_eq!(
+        TwitchCommand::from_str("W @UserName hi").unwrap(),
+        TwitchCommand::Whisper("username".to_string(), "hi".to_string())
+    );
+}
+
+#[test]
+fn test_twitch_command_whisper_invalid() {
+    assert!(TwitchCommand::from_str("w").is_err());
+    assert!(TwitchCommand::from_str("w username").is_err());
+    assert!(TwitchCommand::from_str("wasdf username message").is_err());
+}
+
+#[test]
+fn test_twitch_command_poll_valid() {
+    assert_eq!(
+        TwitchCommand::from_str("poll Best Fruit? | Apple | Banana").unwrap(),
+        TwitchCommand::Poll(
+            "Best Fruit?".to_string(),
+            vec!["Apple".to_string(), "Banana".to_string()],
+            60
+        )
+    );
+    assert_eq!(
+        TwitchCommand::from_str("Poll 300 Best Fruit?|Apple|Banana|Cherry").unwrap(),
+        TwitchCommand::Poll(
+            "Best Fruit?".to_string(),
+            vec![
+                "Apple".to_string(),
+                "Banana".to_string(),
+                "Cherry".to_string()
+            ],
+            300
+        )
+    );
+    assert_eq!(
+        TwitchCommand::from_str("poll END").unwrap(),
+        TwitchCommand::EndPoll
+    );
+}
+
+#[test]
+fn test_twitch_command_poll_invalid() {
+    assert!(TwitchCommand::from_str("poll").is_err());
+    assert!(TwitchCommand::from_str("poll Best Fruit? | Apple").is_err());
+    assert!(TwitchCommand::from_str("poll | Apple | Banana").is_err());
+}
+
+#[test]
+fn test_twitch_command_prediction_valid() {
+    assert_eq!(
+        TwitchCommand::from_str("prediction Will I Win? | Yes | No").unwrap(),
+        TwitchCommand::Prediction(
+            "Will I Win?".to_string(),
+            vec!["Yes".to_string(), "No".to_string()],
+            120
+        )
+    );
+    assert_eq!(
+        TwitchCommand::from_str("prediction 30 Will I Win? | Yes | No").unwrap(),
+        TwitchCommand::Prediction(
+            "Will I Win?".to_string(),
+            vec!["Yes".to_string(), "No".to_string()],
+            30
+        )
+    );
+    assert_eq!(
+        TwitchCommand::from_str("prediction lock").unwrap(),
+        TwitchCommand::LockPrediction
+    );
+    assert_eq!(
+        TwitchCommand::from_str("prediction Resolve Not Yet").unwrap(),
+        TwitchCommand::ResolvePrediction("Not Yet".to_string())
+    );
+    assert_eq!(
+        TwitchCommand::from_str("prediction cancel").unwrap(),
+        TwitchCommand::CancelPrediction
+    );
+}
+
+#[test]
+fn test_twitch_command_prediction_invalid() {
+    assert!(TwitchCommand::from_str("prediction").is_err());
+    assert!(TwitchCommand::from_str("prediction resolve").is_err());
+    assert!(TwitchCommand::from_str("prediction lock now").is_err());
+    assert!(TwitchCommand::from_str("prediction Will I Win? | Yes").is_err());
+}
@@ -0,0 +1,449 @@
+use color_eyre::Result;
+use serde_json::json;
+
+use super::server::RecordedRequest;
+
+use crate::{
+    events::{Event, TwitchAction, TwitchEvent, TwitchNotification},
+    handlers::stream_status::StreamStatus,
+    twitch::{
+        api::{
+            event_sub::{
+                AUTOMOD_EVENT_SUBSCRIPTIONS, INITIAL_EVENT_SUBSCRIPTIONS,
+                STREAM_EVENT_SUBSCRIPTIONS,
+            },
+            subscriptions::Subscription,
+        },
+        notices::ChatNotice,
+        tests::integration::{
+            MockSession,
+            server::{MOCK_USER_ID, mock_user_id},
+        },
+    },
+};
+
+/// Whether a request subscribes to one of the given events
+fn is_subscription_to(request: &RecordedRequest, subscriptions: &[Subscription]) -> bool {
+    subscriptions
+        .iter()
+        .any(|subscription| request.body["type"] == subscription.to_string())
+}
+
+/// Waits for the stream status of a channel to be sent to the frontend
+async fn wait_for_stream_status(
+    session: &mut MockSession,
+    channel_name: &str,
+) -> Result<StreamStatus> {
+    let event = session
+        .wait_for_event(|event| {
+            matches!(
+                event,
+                Event::Twitch(TwitchEvent::Notification(TwitchNotification::StreamStatus { channel, .. }))
+                    if channel == channel_name
+            )
+        })
+        .await?;
+
+    let Event::Twitch(TwitchEvent::Notification(TwitchNotification::StreamStatus {
+        status, ..
+    })) = event
+    else {
+        unreachable!();
+    };
+
+    Ok(status)
+}
+
+#[tokio::test]
+async fn test_join_channel_subscribes_to_events() -> Result<()> {
+    let session = MockSession::connect("mock_join").await?;
+    let channel_id = session.channel_id.clone();
+
+    assert!(
+        !session
+            .server
+            .requests(|request| request.path == "/helix/users"
+                && request.query("login") == Some("mock_join"))
+            .is_empty()
+    );
+
+    let subscriptions = session.server.requests(|request| {
+        request.method == "POST"
+            && request.path == "/helix/eventsub/subscriptions"
+            && request.body["condition"]["broadcaster_user_id"] == channel_id.as_str()
+            && is_subscription_to(request, INITIAL_EVENT_SUBSCRIPTIONS)
+    });
+
+    assert_eq!(subscriptions.len(), INITIAL_EVENT_SUBSCRIPTIONS.len());
+
+    for (subscription, subscription_type) in subscriptions.iter().zip(INITIAL_EVENT_SUBSCRIPTIONS) {
+        assert_eq!(subscription.body["type"], subscription_type.to_string());
+        assert_eq!(subscription.body["condition"]["user_id"], MOCK_USER_ID);
+        assert_eq!(subscription.body["transport"]["method"], "websocket");
+        assert!(subscription.body["transport"]["session_id"].is_string());
+    }
+
+    assert!(
+        !session
+            .server
+            .requests(|request| request.path == "/helix/chat/settings"
+                && request.query("broadcaster_id") == Some(&channel_id))
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_open_second_channel() -> Result<()> {
+    let mut session = MockSession::connect("mock_open").await?;
+    let second_channel_id = mock_user_id("mock_open_second");
+
+    session
+        .send_action(TwitchAction::OpenChannel("mock_open_second".to_string()))
+        .await?;
+
+    session.wait_for_message("Joined #mock_open_second").await?;
+
+    let subscriptions = session.server.requests(|request| {
+        request.path == "/helix/eventsub/subscriptions"
+            && request.body["condition"]["broadcaster_user_id"] == second_channel_id.as_str()
+            && is_subscription_to(request, INITIAL_EVENT_SUBSCRIPTIONS)
+    });
+
+    assert_eq!(subscriptions.len(), INITIAL_EVENT_SUBSCRIPTIONS.len());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stream_status_updates_in_place() -> Result<()> {
+    let mut session = MockSession::connect("mock_stream").await?;
+    let channel_id = session.channel_id.clone();
+
+    let subscriptions = session.server.requests(|request| {
+        request.path == "/helix/eventsub/subscriptions"
+            && request.body["condition"]["broadcaster_user_id"] == channel_id.as_str()
+            && is_subscription_to(request, STREAM_EVENT_SUBSCRIPTIONS)
+    });
+
+    assert_eq!(subscriptions.len(), STREAM_EVENT_SUBSCRIPTIONS.len());
+
+    // Stream events can only be filtered by broadcaster
+    for subscription in &subscriptions {
+        assert!(subscription.body["condition"].get("user_id").is_none());
+    }
+
+    // The channel is offline, so its title and category are looked up while it's being joined
+    assert!(
+        !session
+            .server
+            .requests(|request| request.path == "/helix/streams"
+                && request.query("user_id") == Some(&channel_id))
+            .is_empty()
+    );
+    assert!(
+        !session
+            .server
+            .requests(|request| request.path == "/helix/channels"
+                && request.query("broadcaster_id") == Some(&channel_id))
+            .is_empty()
+    );
+
+    session.server.send_notification(
+        &channel_id,
+        "stream.online",
+        &json!({
+            "id": "9001",
+            "broadcaster_user_id": channel_id,
+            "broadcaster_user_login": "mock_stream",
+            "broadcaster_user_name": "mock_stream",
+            "type": "live",
+            "started_at": "2024-02-23T21:12:33Z",
+        }),
+    )?;
+
+    let status = wait_for_stream_status(&mut session, "mock_stream").await?;
+
+    assert!(status.is_live());
+    assert_eq!(status.title, "Mock stream");
+    assert_eq!(status.category, "Just Chatting");
+
+    session.server.send_notification(
+        &channel_id,
+        "channel.update",
+        &json!({
+            "broadcaster_user_id": channel_id,
+            "broadcaster_user_login": "mock_stream",
+            "broadcaster_user_name": "mock_stream",
+            "title": "Speedrunning",
+            "language": "en",
+            "category_id": "504461",
+            "category_name": "Celeste",
+            "content_classification_labels": [],
+        }),
+    )?;
+
+    let status = wait_for_stream_status(&mut session, "mock_stream").await?;
+
+    assert!(status.is_live());
+    assert_eq!(status.title, "Speedrunning");
+    assert_eq!(status.category, "Celeste");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_automod_held_message() -> Result<()> {
+    let mut session = MockSession::connect("mock_automod").await?;
+    let channel_id = session.channel_id.clone();
+
+    let subscriptions = session.server.requests(|request| {
+        request.path == "/helix/eventsub/subscriptions"
+            && request.body["condition"]["broadcaster_user_id"] == channel_id.as_str()
+            && is_subscription_to(request, AUTOMOD_EVENT_SUBSCRIPTIONS)
+    });
+
+    assert_eq!(subscriptions.len(), AUTOMOD_EVENT_SUBSCRIPTIONS.len());
+
+    for subscription in &subscriptions {
+        assert_eq!(subscription.body["version"], "2");
+        assert_eq!(
+            subscription.body["condition"]["moderator_user_id"],
+            MOCK_USER_ID
+        );
+    }
+
+    session.server.send_notification(
+        &channel_id,
+        "automod.message.hold",
+        &json!({
+            "broadcaster_user_id": channel_id,
+            "broadcaster_user_login": "mock_automod",
+            "broadcaster_user_name": "mock_automod",
+            "user_id": "4145994",
+            "user_login": "viewer32",
+            "user_name": "viewer32",
+            "message_id": "bad-message",
+            "message": {
+                "text": "you are bad",
+                "fragments": [{ "type": "text", "text": "you are bad" }]
+            },
+            "reason": "automod",
+            "automod": { "category": "aggressive", "level": 1, "boundaries": [] },
+            "blocked_term": null,
+            "held_at": "2024-02-23T21:12:33Z",
+        }),
+    )?;
+
+    session
+        .wait_for_message("AutoMod held a message from viewer32 for review")
+        .await?;
+
+    let event = session
+        .wait_for_event(|event| {
+            matches!(
+                event,
+                Event::Twitch(TwitchEvent::Notification(TwitchNotification::HeldMessage(
+                    _
+                )))
+            )
+        })
+        .await?;
+
+    let Event::Twitch(TwitchEvent::Notification(TwitchNotification::HeldMessage(held_message))) =
+        event
+    else {
+        unreachable!();
+    };
+
+    assert_eq!(held_message.id, "bad-message");
+    assert_eq!(held_message.channel, "mock_automod");
+    assert_eq!(held_message.text, "you are bad");
+    assert_eq!(held_message.reason, "aggressive, level 1");
+
+    session.server.send_notification(
+        &channel_id,
+        "automod.message.update",
+        &json!({
+            "broadcaster_user_id": channel_id,
+            "broadcaster_user_login": "mock_automod",
+            "broadcaster_user_name": "mock_automod",
+            "user_id": "4145994",
+            "user_login": "viewer32",
+            "user_name": "viewer32",
+            "moderator_user_id": "9001",
+            "moderator_user_login": "other_mod",
+            "moderator_user_name": "other_mod",
+            "message_id": "bad-message",
+            "message": {
+                "text": "you are bad",
+                "fragments": [{ "type": "text", "text": "you are bad" }]
+            },
+            "reason": "automod",
+            "automod": { "category": "aggressive", "level": 1, "boundaries": [] },
+            "blocked_term": null,
+            "status": "denied",
+            "held_at": "2024-02-23T21:12:33Z",
+        }),
+    )?;
+
+    session
+        .wait_for_event(|event| {
+            matches!(
+                event,
+                Event::Twitch(TwitchEvent::Notification(TwitchNotification::HeldMessageResolved(id)))
+                    if id == "bad-message"
+            )
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_message() -> Result<()> {
+    let session = MockSession::connect("mock_send").await?;
+    let channel_id = session.channel_id.clone();
+
+    session
+        .send_action(TwitchAction::Message("Hello from the mock".to_string()))
+        .await?;
+
+    let request = session
+        .server
+        .wait_for_request(|request| {
+            request.path == "/helix/chat/messages"
+                && request.body["broadcaster_id"] == channel_id.as_str()
+        })
+        .await?;
+
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.body["sender_id"], MOCK_USER_ID);
+    assert_eq!(request.body["message"], "Hello from the mock");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_receive_chat_message() -> Result<()> {
+    let mut session = MockSession::connect("mock_receive").await?;
+
+    session.server.send_notification(
+        &session.channel_id,
+        "channel.chat.message",
+        &json!({
+            "broadcaster_user_id": session.channel_id,
+            "broadcaster_user_login": "mock_receive",
+            "broadcaster_user_name": "mock_receive",
+            "chatter_user_id": "4145994",
+            "chatter_user_login": "viewer32",
+            "chatter_user_name": "viewer32",
+            "message_id": "cc106a89-1814-919d-454c-f4f2f970aae7",
+            "message": {
+                "text": "Hi chat",
+                "fragments": [{ "type": "text", "text": "Hi chat" }]
+            },
+            "color": "#00FF7F",
+            "badges": [],
+            "message_type": "text",
+        }),
+    )?;
+
+    let message = session.wait_for_message("Hi chat").await?;
+
+    assert_eq!(message.author, "viewer32");
+    assert_eq!(message.user_id.as_deref(), Some("4145994"));
+    assert_eq!(
+        message.message_id.as_deref(),
+        Some("cc106a89-1814-919d-454c-f4f2f970aae7")
+    );
+    assert!(!message.system);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_receive_resub_with_message() -> Result<()> {
+    let mut session = MockSession::connect("mock_resub").await?;
+
+    session.server.send_notification(
+        &session.channel_id,
+        "channel.chat.notification",
+        &json!({
+            "broadcaster_user_id": session.channel_id,
+            "broadcaster_user_login": "mock_resub",
+            "broadcaster_user_name": "mock_resub",
+            "chatter_user_id": "49912639",
+            "chatter_user_login": "viewer23",
+            "chatter_user_name": "viewer23",
+            "color": "",
+            "badges": [],
+            "system_message": "viewer23 subscribed at Tier 1. They've subscribed for 10 months!",
+            "message_id": "d62235c8-47ff-a4f4-84e8-5a29a65a9c03",
+            "message": {
+                "text": "Still here",
+                "fragments": [{ "type": "text", "text": "Still here" }]
+            },
+            "notice_type": "resub",
+        }),
+    )?;
+
+    let notice = session
+        .wait_for_message("viewer23 subscribed at Tier 1. They've subscribed for 10 months!")
+        .await?;
+
+    assert!(notice.system);
+    assert_eq!(notice.notice, Some(ChatNotice::Resub));
+
+    let message = session.wait_for_message("Still here").await?;
+
+    assert_eq!(message.author, "viewer23");
+    assert!(!message.system);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_receive_mention_of_current_user() -> Result<()> {
+    let mut session = MockSession::connect("mock_mention").await?;
+
+    session.server.send_notification(
+        &session.channel_id,
+        "channel.chat.message",
+        &json!({
+            "broadcaster_user_id": session.channel_id,
+            "broadcaster_user_login": "mock_mention",
+            "broadcaster_user_name": "mock_mention",
+            "chatter_user_id": "4145994",
+            "chatter_user_login": "viewer32",
+            "chatter_user_name": "viewer32",
+            "message_id": "8f1d7c3e-3a56-4e4a-9f38-2e5d0f0b8d11",
+            "message": {
+                "text": "hi @mock_user",
+                "fragments": [
+                    { "type": "text", "text": "hi " },
+                    {
+                        "type": "mention",
+                        "text": "@mock_user",
+                        "mention": {
+                            "user_id": MOCK_USER_ID,
+                            "user_login": "mock_user",
+                            "user_name": "mock_user"
+                        }
+                    }
+                ]
+            },
+            "color": "",
+            "badges": [],
+            "message_type": "text",
+        }),
+    )?;
+
+    let message = session.wait_for_message("hi @mock_user").await?;
+
+    assert_eq!(message.mentions, vec!["@mock_user".to_string()]);
+    assert!(message.ping);
+
+    Ok(())
+}
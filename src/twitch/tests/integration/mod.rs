@@ -0,0 +1,125 @@
+mod channels;
+mod moderation;
+mod polls;
+mod server;
+
+use std::sync::Arc;
+
+use color_eyre::{Result, eyre::bail};
+use tokio::{
+    sync::mpsc::{Receiver, Sender, channel},
+    time::timeout,
+};
+
+use crate::{
+    config::CoreConfig,
+    events::{ConnectionState, Event, TwitchAction, TwitchEvent, TwitchNotification},
+    handlers::{data::RawMessageData, state::State},
+    twitch::{
+        oauth::TwitchOauth,
+        tests::integration::server::{MOCK_TIMEOUT, MOCK_USER_LOGIN, MockTwitchServer},
+        websocket::TwitchWebsocket,
+    },
+};
+
+/// A connection to the mock Twitch server, with the first channel already joined
+struct MockSession {
+    server: &'static MockTwitchServer,
+    channel_id: String,
+    event_rx: Receiver<Event>,
+    twitch_tx: Sender<TwitchAction>,
+}
+
+impl MockSession {
+    /// Connects to the mock server the same way that the application would to Twitch.
+    /// Every test should use a channel of its own, since the server is shared between them.
+    async fn connect(channel_name: &str) -> Result<Self> {
+        let server = MockTwitchServer::shared();
+
+        let mut config = CoreConfig::default();
+        config.twitch.username = MOCK_USER_LOGIN.to_string();
+        config.twitch.channel = channel_name.to_string();
+        config.twitch.server = server.websocket_url();
+        config.twitch.token = Some("oauth:mock_token".to_string());
+        config.twitch.api_url = server.api_url();
+        config.twitch.oauth_validate_url = server.oauth_validate_url();
+        config.terminal.first_state = State::Normal;
+
+        let config = Arc::new(config);
+
+        let twitch_oauth = TwitchOauth::default().init(config.clone()).await?;
+
+        let (event_tx, event_rx) = channel(100);
+        let (twitch_tx, twitch_rx) = channel(100);
+
+        TwitchWebsocket::new(config, twitch_oauth, event_tx, twitch_rx, None);
+
+        let mut session = Self {
+            server,
+            channel_id: server::mock_user_id(channel_name),
+            event_rx,
+            twitch_tx,
+        };
+
+        session
+            .wait_for_event(|event| {
+                matches!(
+                    event,
+                    Event::Twitch(TwitchEvent::Notification(
+                        TwitchNotification::ConnectionState(ConnectionState::Connected)
+                    ))
+                )
+            })
+            .await?;
+
+        Ok(session)
+    }
+
+    async fn send_action(&self, action: TwitchAction) -> Result<()> {
+        self.twitch_tx.send(action).await?;
+
+        Ok(())
+    }
+
+    /// Waits for the websocket thread to send an event that matches the predicate, skipping any others
+    async fn wait_for_event<F>(&mut self, predicate: F) -> Result<Event>
+    where
+        F: Fn(&Event) -> bool,
+    {
+        let wait = async {
+            while let Some(event) = self.event_rx.recv().await {
+                if predicate(&event) {
+                    return Some(event);
+                }
+            }
+
+            None
+        };
+
+        match timeout(MOCK_TIMEOUT, wait).await {
+            Ok(Some(event)) => Ok(event),
+            Ok(None) => bail!("Websocket thread stopped before the expected event was sent"),
+            Err(_) => bail!("Expected event was not sent in time"),
+        }
+    }
+
+    /// Waits for a chat message with the given payload
+    async fn wait_for_message(&mut self, payload: &str) -> Result<RawMessageData> {
+        let event = self
+            .wait_for_event(|event| {
+                matches!(
+                    event,
+                    Event::Twitch(TwitchEvent::Notification(TwitchNotification::Message(message)))
+                        if message.payload == payload
+                )
+            })
+            .await?;
+
+        let Event::Twitch(TwitchEvent::Notification(TwitchNotification::Message(message))) = event
+        else {
+            unreachable!();
+        };
+
+        Ok(*message)
+    }
+}
@@ -0,0 +1,98 @@
+use color_eyre::Result;
+
+use crate::{
+    events::TwitchAction,
+    twitch::tests::integration::{
+        MockSession,
+        server::{MOCK_USER_ID, mock_user_id},
+    },
+};
+
+#[tokio::test]
+async fn test_ban_command() -> Result<()> {
+    let mut session = MockSession::connect("mock_ban").await?;
+    let channel_id = session.channel_id.clone();
+
+    session
+        .send_action(TwitchAction::Message("/ban troll spamming".to_string()))
+        .await?;
+
+    session
+        .wait_for_message("User troll banned. Reason: spamming")
+        .await?;
+
+    let request = session
+        .server
+        .wait_for_request(|request| {
+            request.path == "/helix/moderation/bans"
+                && request.query("broadcaster_id") == Some(&channel_id)
+        })
+        .await?;
+
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.query("moderator_id"), Some(MOCK_USER_ID));
+    assert_eq!(request.body["data"]["user_id"], mock_user_id("troll"));
+    assert_eq!(request.body["data"]["reason"], "spamming");
+    assert!(request.body["data"].get("duration").is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_timeout_selected_user() -> Result<()> {
+    let mut session = MockSession::connect("mock_timeout").await?;
+    let channel_id = session.channel_id.clone();
+
+    session
+        .send_action(TwitchAction::TimeoutUser {
+            user_id: "4145994".to_string(),
+            username: "viewer32".to_string(),
+            duration: Some(600),
+        })
+        .await?;
+
+    session
+        .wait_for_message("User viewer32 timed out for 600 seconds.")
+        .await?;
+
+    let request = session
+        .server
+        .wait_for_request(|request| {
+            request.path == "/helix/moderation/bans"
+                && request.query("broadcaster_id") == Some(&channel_id)
+        })
+        .await?;
+
+    assert_eq!(request.body["data"]["user_id"], "4145994");
+    assert_eq!(request.body["data"]["duration"], 600);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_selected_message() -> Result<()> {
+    let mut session = MockSession::connect("mock_delete").await?;
+    let channel_id = session.channel_id.clone();
+
+    session
+        .send_action(TwitchAction::DeleteMessage(
+            "mock_deleted_message".to_string(),
+        ))
+        .await?;
+
+    session.wait_for_message("Message was deleted").await?;
+
+    let request = session
+        .server
+        .wait_for_request(|request| {
+            request.path == "/helix/moderation/chat"
+                && request.query("broadcaster_id") == Some(&channel_id)
+        })
+        .await?;
+
+    assert_eq!(request.method, "DELETE");
+    assert_eq!(request.query("moderator_id"), Some(MOCK_USER_ID));
+    assert_eq!(request.query("message_id"), Some("mock_deleted_message"));
+
+    Ok(())
+}
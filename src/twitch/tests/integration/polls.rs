@@ -0,0 +1,65 @@
+use color_eyre::Result;
+
+use crate::{events::TwitchAction, twitch::tests::integration::MockSession};
+
+#[tokio::test]
+async fn test_poll_command() -> Result<()> {
+    let mut session = MockSession::connect("mock_poll").await?;
+    let channel_id = session.channel_id.clone();
+
+    session
+        .send_action(TwitchAction::Message(
+            "/poll 90 Best Fruit? | Apple | Banana".to_string(),
+        ))
+        .await?;
+
+    session
+        .wait_for_message("Started the poll Best Fruit? for 90 seconds")
+        .await?;
+
+    let request = session
+        .server
+        .wait_for_request(|request| {
+            request.path == "/helix/polls" && request.body["broadcaster_id"] == channel_id
+        })
+        .await?;
+
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.body["title"], "Best Fruit?");
+    assert_eq!(request.body["duration"], 90);
+    assert_eq!(
+        request.body["choices"],
+        serde_json::json!([{ "title": "Apple" }, { "title": "Banana" }])
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resolve_prediction_command() -> Result<()> {
+    let mut session = MockSession::connect("mock_prediction").await?;
+    let channel_id = session.channel_id.clone();
+
+    session
+        .send_action(TwitchAction::Message("/prediction resolve no".to_string()))
+        .await?;
+
+    session
+        .wait_for_message("Resolved the prediction Will it work?, No won")
+        .await?;
+
+    let request = session
+        .server
+        .wait_for_request(|request| {
+            request.method == "PATCH"
+                && request.path == "/helix/predictions"
+                && request.body["broadcaster_id"] == channel_id
+        })
+        .await?;
+
+    assert_eq!(request.body["id"], "mock_prediction");
+    assert_eq!(request.body["status"], "RESOLVED");
+    assert_eq!(request.body["winning_outcome_id"], "mock_outcome_no");
+
+    Ok(())
+}
@@ -0,0 +1,576 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, LazyLock, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use chrono::Utc;
+use color_eyre::{
+    Result,
+    eyre::{ContextCompat, bail},
+};
+use futures::{SinkExt, StreamExt};
+use reqwest::Url;
+use serde_json::{Value, json};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    runtime::Builder,
+    sync::mpsc::{UnboundedSender, unbounded_channel},
+    time::{sleep, timeout},
+};
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+use tracing::error;
+
+use crate::twitch::api::set_twitch_api_base_url;
+
+/// The user that every token is validated as
+pub const MOCK_USER_ID: &str = "mock_user_id";
+pub const MOCK_USER_LOGIN: &str = "mock_user";
+
+/// How long to wait for something to happen before failing a test
+pub const MOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+static MOCK_TWITCH_SERVER: LazyLock<MockTwitchServer> = LazyLock::new(MockTwitchServer::start);
+
+/// The ID that the mock server gives a user, from their login name
+pub fn mock_user_id(login: &str) -> String {
+    format!("{login}_id")
+}
+
+/// A request that was made to the mock Helix API
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub body: Value,
+}
+
+impl RecordedRequest {
+    pub fn query(&self, key: &str) -> Option<&str> {
+        self.query.get(key).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    /// Every API request, in the order that they were received
+    requests: Vec<RecordedRequest>,
+    /// Frames waiting to be sent to each websocket session, by session ID
+    sessions: HashMap<String, UnboundedSender<String>>,
+    /// The session that subscribed to a broadcaster's events most recently
+    broadcaster_sessions: HashMap<String, String>,
+    next_id: usize,
+}
+
+impl MockState {
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+
+        format!("mock_{prefix}_{}", self.next_id)
+    }
+}
+
+type SharedMockState = Arc<Mutex<MockState>>;
+
+/// A stand-in for Twitch's websocket server and the parts of the Helix API that this application uses,
+/// so that everything between the two can be tested without a network connection.
+///
+/// The server runs on its own thread, since every test has a runtime of its own.
+pub struct MockTwitchServer {
+    http_address: SocketAddr,
+    websocket_address: SocketAddr,
+    state: SharedMockState,
+}
+
+impl MockTwitchServer {
+    /// The server shared by every test, since the API base URL can only be set once per process
+    pub fn shared() -> &'static Self {
+        &MOCK_TWITCH_SERVER
+    }
+
+    fn start() -> Self {
+        let state = SharedMockState::default();
+        let server_state = state.clone();
+
+        let (address_tx, address_rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let runtime = Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("Failed to build mock Twitch server runtime");
+
+            runtime.block_on(async move {
+                let http_listener = TcpListener::bind("127.0.0.1:0")
+                    .await
+                    .expect("Failed to bind mock Helix API");
+                let websocket_listener = TcpListener::bind("127.0.0.1:0")
+                    .await
+                    .expect("Failed to bind mock websocket server");
+
+                address_tx
+                    .send((
+                        http_listener.local_addr().unwrap(),
+                        websocket_listener.local_addr().unwrap(),
+                    ))
+                    .unwrap();
+
+                tokio::join!(
+                    serve_http(server_state.clone(), http_listener),
+                    serve_websocket(server_state, websocket_listener)
+                );
+            });
+        });
+
+        let (http_address, websocket_address) = address_rx
+            .recv()
+            .expect("Mock Twitch server failed to start");
+
+        let server = Self {
+            http_address,
+            websocket_address,
+            state,
+        };
+
+        set_twitch_api_base_url(&server.api_url());
+
+        server
+    }
+
+    pub fn api_url(&self) -> String {
+        format!("http://{}/helix", self.http_address)
+    }
+
+    pub fn oauth_validate_url(&self) -> String {
+        format!("http://{}/oauth2/validate", self.http_address)
+    }
+
+    pub fn websocket_url(&self) -> String {
+        format!("ws://{}/ws", self.websocket_address)
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap()
+    }
+
+    /// Every request made so far that matches the predicate
+    pub fn requests<F>(&self, predicate: F) -> Vec<RecordedRequest>
+    where
+        F: Fn(&RecordedRequest) -> bool,
+    {
+        self.state()
+            .requests
+            .iter()
+            .filter(|request| predicate(request))
+            .cloned()
+            .collect()
+    }
+
+    /// Waits for a request that matches the predicate to be made
+    pub async fn wait_for_request<F>(&self, predicate: F) -> Result<RecordedRequest>
+    where
+        F: Fn(&RecordedRequest) -> bool,
+    {
+        let wait = async {
+            loop {
+                if let Some(request) = self.requests(&predicate).pop() {
+                    return request;
+                }
+
+                sleep(Duration::from_millis(10)).await;
+            }
+        };
+
+        let Ok(request) = timeout(MOCK_TIMEOUT, wait).await else {
+            bail!("No matching request was made to the mock Helix API");
+        };
+
+        Ok(request)
+    }
+
+    /// Sends an event to the session that is subscribed to the broadcaster, wrapped in a notification message
+    pub fn send_notification(
+        &self,
+        broadcaster_id: &str,
+        subscription_type: &str,
+        event: &Value,
+    ) -> Result<()> {
+        let (session_id, frame_tx, message_id, subscription_id) = {
+            let mut state = self.state();
+
+            let session_id = state
+                .broadcaster_sessions
+                .get(broadcaster_id)
+                .context("Nothing has subscribed to the broadcaster's events")?
+                .clone();
+            let frame_tx = state
+                .sessions
+                .get(&session_id)
+                .context("Session has already disconnected")?
+                .clone();
+
+            (
+                session_id,
+                frame_tx,
+                state.next_id("message"),
+                state.next_id("subscription"),
+            )
+        };
+
+        let frame = json!({
+            "metadata": {
+                "message_id": message_id,
+                "message_type": "notification",
+                "message_timestamp": Utc::now(),
+                "subscription_type": subscription_type,
+                "subscription_version": "1",
+            },
+            "payload": {
+                "subscription": {
+                    "id": subscription_id,
+                    "status": "enabled",
+                    "type": subscription_type,
+                    "version": "1",
+                    "condition": {
+                        "broadcaster_user_id": broadcaster_id,
+                        "user_id": MOCK_USER_ID,
+                    },
+                    "transport": {
+                        "method": "websocket",
+                        "session_id": session_id,
+                    },
+                    "created_at": Utc::now(),
+                    "cost": 0,
+                },
+                "event": event,
+            },
+        });
+
+        frame_tx.send(frame.to_string())?;
+
+        Ok(())
+    }
+}
+
+async fn serve_http(state: SharedMockState, listener: TcpListener) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+
+        let state = state.clone();
+
+        tokio::spawn(async move {
+            if let Err(err) = handle_http_connection(&state, stream).await {
+                error!("Mock Helix API failed to handle request: {err}");
+            }
+        });
+    }
+}
+
+async fn serve_websocket(state: SharedMockState, listener: TcpListener) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+
+        let state = state.clone();
+
+        tokio::spawn(async move {
+            if let Err(err) = handle_websocket_connection(&state, stream).await {
+                error!("Mock websocket server failed to handle session: {err}");
+            }
+        });
+    }
+}
+
+/// Reads a single HTTP/1.1 request, closing the connection after responding to it
+async fn handle_http_connection(state: &SharedMockState, stream: TcpStream) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        bail!("Malformed request line: {request_line}");
+    };
+    let url = Url::parse(&format!("http://localhost{target}"))?;
+    let method = method.to_string();
+
+    let mut content_length = 0;
+
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).await?;
+
+        let header = header.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse()?;
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    let request = RecordedRequest {
+        method,
+        path: url.path().to_string(),
+        query: url.query_pairs().into_owned().collect(),
+        body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+    };
+
+    let (status, response_body) = {
+        let mut state = state.lock().unwrap();
+        let response = respond(&mut state, &request);
+
+        state.requests.push(request);
+
+        response
+    };
+
+    let reason = match status {
+        200 => "OK",
+        204 => "No Content",
+        _ => "Not Found",
+    };
+    let response_body = response_body
+        .map(|body| body.to_string())
+        .unwrap_or_default();
+
+    let stream = reader.get_mut();
+    stream
+        .write_all(
+            format!(
+                "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response_body}",
+                response_body.len()
+            )
+            .as_bytes(),
+        )
+        .await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
+
+/// What the Helix API would have responded with, as a status code and a JSON body
+fn respond(state: &mut MockState, request: &RecordedRequest) -> (u16, Option<Value>) {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/oauth2/validate") => (
+            200,
+            Some(json!({
+                "client_id": "mock_client_id",
+                "login": MOCK_USER_LOGIN,
+                "scopes": [],
+                "user_id": MOCK_USER_ID,
+                "expires_in": 3600,
+            })),
+        ),
+        ("GET", "/helix/users") => {
+            let login = request.query("login").unwrap_or(MOCK_USER_LOGIN);
+
+            (
+                200,
+                Some(json!({ "data": [{ "id": mock_user_id(login) }] })),
+            )
+        }
+        ("POST", "/helix/eventsub/subscriptions") => {
+            let mut subscription = request.body.clone();
+
+            if let (Some(broadcaster_id), Some(session_id)) = (
+                subscription
+                    .pointer("/condition/broadcaster_user_id")
+                    .and_then(Value::as_str),
+                subscription
+                    .pointer("/transport/session_id")
+                    .and_then(Value::as_str),
+            ) {
+                state
+                    .broadcaster_sessions
+                    .insert(broadcaster_id.to_string(), session_id.to_string());
+            }
+
+            subscription["id"] = json!(state.next_id("subscription"));
+            subscription["status"] = json!("enabled");
+            subscription["created_at"] = json!(Utc::now());
+            subscription["cost"] = json!(0);
+
+            (
+                200,
+                Some(json!({
+                    "data": [subscription],
+                    "total": 1,
+                    "total_cost": 0,
+                    "max_total_cost": 10,
+                })),
+            )
+        }
+        ("GET", "/helix/chat/settings") => (
+            200,
+            Some(json!({
+                "data": [{
+                    "broadcaster_id": request.query("broadcaster_id"),
+                    "slow_mode": false,
+                    "slow_mode_wait_time": null,
+                    "follower_mode": false,
+                    "follower_mode_duration": null,
+                    "non_moderator_chat_delay": false,
+                    "non_moderator_chat_delay_duration": null,
+                    "subscriber_mode": false,
+                    "emote_mode": false,
+                    "unique_chat_mode": false,
+                }],
+            })),
+        ),
+        ("POST", "/helix/chat/messages") => (
+            200,
+            Some(json!({
+                "data": [{ "message_id": state.next_id("message"), "is_sent": true }],
+            })),
+        ),
+        ("POST", "/helix/moderation/bans") => (
+            200,
+            Some(json!({
+                "data": [{
+                    "broadcaster_id": request.query("broadcaster_id"),
+                    "moderator_id": request.query("moderator_id"),
+                    "user_id": request.body.pointer("/data/user_id"),
+                    "created_at": Utc::now(),
+                    "end_time": null,
+                }],
+            })),
+        ),
+        ("POST", "/helix/polls") => {
+            let choices = request.body["choices"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|choice| json!({ "id": state.next_id("choice"), "title": choice["title"] }))
+                .collect::<Vec<_>>();
+
+            (
+                200,
+                Some(json!({
+                    "data": [{
+                        "id": state.next_id("poll"),
+                        "title": request.body["title"],
+                        "choices": choices,
+                        "status": "ACTIVE",
+                    }],
+                })),
+            )
+        }
+        ("GET" | "PATCH", "/helix/predictions") => (
+            200,
+            Some(json!({
+                "data": [{
+                    "id": "mock_prediction",
+                    "title": "Will it work?",
+                    "outcomes": [
+                        { "id": "mock_outcome_yes", "title": "Yes" },
+                        { "id": "mock_outcome_no", "title": "No" },
+                    ],
+                    "status": request.body.get("status").cloned().unwrap_or_else(|| json!("ACTIVE")),
+                }],
+            })),
+        ),
+        // Every mock channel is offline until a test says otherwise with a stream.online event
+        ("GET", "/helix/streams") => (200, Some(json!({ "data": [] }))),
+        ("GET", "/helix/channels") => (
+            200,
+            Some(json!({
+                "data": [{
+                    "broadcaster_id": request.query("broadcaster_id"),
+                    "broadcaster_login": "mock_channel",
+                    "broadcaster_name": "mock_channel",
+                    "broadcaster_language": "en",
+                    "game_id": "509658",
+                    "game_name": "Just Chatting",
+                    "title": "Mock stream",
+                    "delay": 0,
+                    "tags": [],
+                    "content_classification_labels": [],
+                    "is_branded_content": false,
+                }],
+            })),
+        ),
+        ("DELETE", "/helix/eventsub/subscriptions" | "/helix/moderation/chat") => (204, None),
+        _ => (
+            404,
+            Some(json!({
+                "error": "Not Found",
+                "status": 404,
+                "message": format!("The mock Helix API has no route for {}", request.path),
+            })),
+        ),
+    }
+}
+
+/// Welcomes a new session, then forwards every notification that is sent to it
+async fn handle_websocket_connection(state: &SharedMockState, stream: TcpStream) -> Result<()> {
+    let mut websocket = accept_async(stream).await?;
+
+    let (frame_tx, mut frame_rx) = unbounded_channel();
+
+    let session_id = {
+        let mut state = state.lock().unwrap();
+        let session_id = state.next_id("session");
+
+        state.sessions.insert(session_id.clone(), frame_tx);
+
+        session_id
+    };
+
+    let welcome_message = json!({
+        "metadata": {
+            "message_id": format!("{session_id}_welcome"),
+            "message_type": "session_welcome",
+            "message_timestamp": Utc::now(),
+        },
+        "payload": {
+            "session": {
+                "id": session_id,
+                "status": "connected",
+                "connected_at": Utc::now(),
+                "keepalive_timeout_seconds": 30,
+                "reconnect_url": null,
+                "recovery_url": null,
+            },
+        },
+    });
+
+    websocket
+        .send(Message::text(welcome_message.to_string()))
+        .await?;
+
+    loop {
+        tokio::select! {
+            frame = frame_rx.recv() => {
+                let Some(frame) = frame else {
+                    break;
+                };
+
+                websocket.send(Message::text(frame)).await?;
+            }
+            message = websocket.next() => {
+                if !matches!(message, Some(Ok(message)) if !message.is_close()) {
+                    break;
+                }
+            }
+        }
+    }
+
+    state.lock().unwrap().sessions.remove(&session_id);
+
+    Ok(())
+}
@@ -1,3 +1,6 @@
 mod commands;
+mod integration;
 mod notifications;
+mod replay;
+mod session;
 mod utils;
@@ -0,0 +1,32 @@
+use color_eyre::{Result, eyre::ContextCompat};
+
+use crate::twitch::{
+    api::subscriptions::Subscription,
+    handlers::incoming_message::held_message,
+    models::{ReceivedTwitchMessagePayload, ReceivedTwitchSubscription},
+    tests::{notifications::AUTOMOD_HOLD, utils::load_data},
+};
+
+#[test]
+fn test_automod_hold_blocked_term() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(AUTOMOD_HOLD)?;
+
+    let subscription_type = message
+        .subscription()
+        .and_then(ReceivedTwitchSubscription::subscription_type)
+        .context("Could not find subscription type")?;
+
+    assert_eq!(subscription_type, &Subscription::AutoModMessageHold);
+
+    let event = message.event().context("Could not find message event")?;
+
+    let held_message =
+        held_message(event, "cool_user".to_string()).context("Could not read held message")?;
+
+    assert_eq!(held_message.id, "bad-message-id");
+    assert_eq!(held_message.user_name, "BadUser");
+    assert_eq!(held_message.text, "This is a bad message... pogchamp");
+    assert_eq!(held_message.reason, "Blocked term");
+
+    Ok(())
+}
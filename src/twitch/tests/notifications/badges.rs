@@ -3,6 +3,7 @@ use std::string::ToString;
 use color_eyre::{Result, eyre::ContextCompat};
 
 use crate::twitch::{
+    badges::ChatBadge,
     models::ReceivedTwitchMessagePayload,
     tests::{
         notifications::{BADGES, INVALID_BADGES, NO_BADGES},
@@ -40,6 +41,67 @@ fn test_deserialize_badges() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_chat_badges() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(BADGES)?;
+
+    let badges: Vec<ChatBadge> = message
+        .event()
+        .context("Could not find badges deserialized event")?
+        .badges()
+        .context("No badges were found")?
+        .iter()
+        .map(|badge| ChatBadge::new(badge, Some("141981764".to_string())))
+        .collect();
+
+    let subscriber = &badges[1];
+
+    assert_eq!(subscriber.key(), "subscriber/12");
+    assert_eq!(
+        subscriber.keys(),
+        vec!["141981764/subscriber/12", "subscriber/12"]
+    );
+
+    assert_eq!(subscriber.info, "16");
+
+    let text: String = badges.iter().filter_map(ChatBadge::text).collect();
+
+    assert_eq!(text, "\u{1F528}\u{2B50}16\u{1F381}");
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_badge_has_no_text() {
+    let badge = ChatBadge {
+        set_id: "glhf-pledge".to_string(),
+        id: "1".to_string(),
+        info: String::new(),
+        channel_id: None,
+    };
+
+    assert_eq!(badge.text(), None);
+}
+
+#[test]
+fn test_badge_text_has_details() {
+    let subscriber = ChatBadge {
+        set_id: "subscriber".to_string(),
+        id: "12".to_string(),
+        info: "14".to_string(),
+        channel_id: None,
+    };
+    let bits = ChatBadge {
+        set_id: "bits".to_string(),
+        id: "1000".to_string(),
+        info: String::new(),
+        channel_id: None,
+    };
+
+    assert_eq!(subscriber.text().as_deref(), Some("\u{2B50}14"));
+    assert_eq!(bits.text().as_deref(), Some("\u{1F4A0}1000"));
+}
+
 #[test]
 fn test_deserialize_no_badges() -> Result<()> {
     let (raw, message) = load_data::<ReceivedTwitchMessagePayload>(NO_BADGES)?;
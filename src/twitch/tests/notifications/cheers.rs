@@ -1,6 +1,9 @@
 use color_eyre::{Result, eyre::ContextCompat};
 
+use tui::style::Color::Rgb;
+
 use crate::twitch::{
+    cheers::{bits_tier_color, cheermote_filename},
     models::ReceivedTwitchMessagePayload,
     tests::{
         notifications::{CHEER, INVALID_CHEER},
@@ -30,6 +33,46 @@ fn test_deserialize_cheer() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_cheermote_fragment() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(CHEER)?;
+
+    let fragments = message
+        .event()
+        .context("Could not find cheer deserialized event")?
+        .cheermote_fragments()
+        .context("Could not find message fragments")?;
+
+    let fragment = fragments
+        .first()
+        .context("No cheermote fragments were found")?;
+    let cheermote = fragment
+        .cheermote()
+        .context("Could not find cheermote in fragment")?;
+
+    assert_eq!(
+        fragment.cheermote_name().map(String::as_str),
+        Some("Cheer100")
+    );
+    assert_eq!(cheermote.prefix(), "cheer");
+    assert_eq!(cheermote.bits(), 100);
+    assert_eq!(
+        cheermote_filename(cheermote.prefix(), cheermote.tier()),
+        "cheermote_cheer_100"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_bits_tier_colors() {
+    assert_eq!(bits_tier_color(1), Rgb(151, 151, 151));
+    assert_eq!(bits_tier_color(99), Rgb(151, 151, 151));
+    assert_eq!(bits_tier_color(100), Rgb(156, 62, 232));
+    assert_eq!(bits_tier_color(4999), Rgb(29, 178, 165));
+    assert_eq!(bits_tier_color(250_000), Rgb(244, 48, 33));
+}
+
 #[test]
 #[should_panic(expected = "Invalid cheer field")]
 fn test_deserialize_invalid_cheer() {
@@ -5,7 +5,7 @@ use color_eyre::{Result, eyre::ContextCompat};
 use crate::twitch::{
     models::ReceivedTwitchMessagePayload,
     tests::{
-        notifications::{EMOTE, MANY_EMOTES},
+        notifications::{EMOTE, MANY_EMOTES, MESSAGE_TEXT_EMOTE_FRAGMENTS},
         utils::load_data,
     },
 };
@@ -75,3 +75,20 @@ fn test_deserialize_many_emotes() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_emote_only() -> Result<()> {
+    let emote_only = |data| -> Result<bool> {
+        let (_, message) = load_data::<ReceivedTwitchMessagePayload>(data)?;
+
+        Ok(message
+            .event()
+            .context("Could not find deserialized event")?
+            .emote_only())
+    };
+
+    assert!(emote_only(MANY_EMOTES)?);
+    assert!(!emote_only(MESSAGE_TEXT_EMOTE_FRAGMENTS)?);
+
+    Ok(())
+}
@@ -1,10 +1,16 @@
+mod automod;
 mod badges;
 mod bans;
 mod cheers;
 mod commands;
 mod emotes;
 mod message_fragments;
+mod notices;
+mod polls;
+mod redemptions;
 mod reply;
+mod streams;
+mod whispers;
 
 // Badges
 static BADGES: &str = include_str!("data/badges.json");
@@ -26,12 +32,32 @@ static ME_COMMAND: &str = include_str!("data/me_command.json");
 // Replies
 static REPLY: &str = include_str!("data/reply.json");
 
+// Chat notifications
+static RESUB: &str = include_str!("data/resub.json");
+static ANNOUNCEMENT: &str = include_str!("data/announcement.json");
+
 // Bans (permanent/non-permanent timeouts)
 static USER_BAN: &str = include_str!("data/user_ban.json");
 static USER_TIMEOUT: &str = include_str!("data/user_timeout.json");
 
+// Whispers
+static WHISPER: &str = include_str!("data/whisper.json");
+
 // Multiple message fragments (text with emotes, text with a mention, etc)
 static MESSAGE_TEXT_FRAGMENT: &str = include_str!("data/message_text_fragment.json");
 static MESSAGE_TEXT_EMOTE_FRAGMENTS: &str = include_str!("data/message_text_emote_fragments.json");
 static MESSAGE_TEXT_MENTION_FRAGMENTS: &str =
     include_str!("data/message_text_mention_fragments.json");
+
+// Polls and predictions
+static POLL_PROGRESS: &str = include_str!("data/poll_progress.json");
+static PREDICTION_END: &str = include_str!("data/prediction_end.json");
+
+// Channel point redemptions
+static REDEMPTION: &str = include_str!("data/redemption.json");
+
+// Stream status
+static CHANNEL_UPDATE: &str = include_str!("data/channel_update.json");
+
+// AutoMod
+static AUTOMOD_HOLD: &str = include_str!("data/automod_hold.json");
@@ -0,0 +1,56 @@
+use color_eyre::{Result, eyre::ContextCompat};
+
+use crate::twitch::{
+    models::ReceivedTwitchMessagePayload,
+    notices::{AnnouncementColor, ChatNotice},
+    tests::{
+        notifications::{ANNOUNCEMENT, RESUB},
+        utils::load_data,
+    },
+};
+
+#[test]
+fn test_deserialize_resub() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(RESUB)?;
+
+    let event = message
+        .event()
+        .context("Could not find resub deserialized event")?;
+
+    let notice = event.notice().context("Could not find notice type")?;
+
+    assert_eq!(notice, ChatNotice::Resub);
+    assert!(notice.has_user_message());
+    assert_eq!(
+        event.message_text().as_deref(),
+        Some("Still here after all these months")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_announcement() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(ANNOUNCEMENT)?;
+
+    let notice = message
+        .event()
+        .context("Could not find announcement deserialized event")?
+        .notice()
+        .context("Could not find notice type")?;
+
+    assert_eq!(notice, ChatNotice::Announcement(AnnouncementColor::Green));
+
+    Ok(())
+}
+
+#[test]
+fn test_notice_types() {
+    assert_eq!(ChatNotice::new("sub_gift", None), ChatNotice::SubGift);
+    assert_eq!(ChatNotice::new("shared_chat_raid", None), ChatNotice::Raid);
+    assert_eq!(
+        ChatNotice::new("announcement", None),
+        ChatNotice::Announcement(AnnouncementColor::Primary)
+    );
+    assert_eq!(ChatNotice::new("watch_streak", None), ChatNotice::Other);
+}
@@ -0,0 +1,100 @@
+use color_eyre::{Result, eyre::ContextCompat};
+
+use crate::{
+    handlers::polls::{PollKind, PollStatus},
+    twitch::{
+        api::subscriptions::Subscription,
+        handlers::incoming_message::live_poll,
+        models::{ReceivedTwitchMessagePayload, ReceivedTwitchSubscription},
+        tests::{
+            notifications::{POLL_PROGRESS, PREDICTION_END},
+            utils::load_data,
+        },
+    },
+};
+
+#[test]
+fn test_poll_progress() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(POLL_PROGRESS)?;
+
+    let subscription_type = message
+        .subscription()
+        .and_then(ReceivedTwitchSubscription::subscription_type)
+        .context("Could not find subscription type")?;
+
+    assert_eq!(subscription_type, &Subscription::PollProgress);
+
+    let event = message.event().context("Could not find message event")?;
+
+    let poll = live_poll(subscription_type, event, "cool_user".to_string())
+        .context("Could not read poll")?;
+
+    assert_eq!(poll.kind, PollKind::Poll);
+    assert_eq!(poll.title, "Aren't shoes just really hard socks?");
+    assert_eq!(poll.total(), 33);
+    assert!(matches!(poll.status, PollStatus::Active(Some(_))));
+
+    let votes = poll
+        .choices
+        .iter()
+        .map(|choice| (choice.title.as_str(), choice.amount, choice.winner))
+        .collect::<Vec<_>>();
+
+    // Nothing has won while the poll is still running
+    assert_eq!(
+        votes,
+        vec![
+            ("Yeah!", 12, false),
+            ("No!", 14, false),
+            ("Maybe!", 7, false)
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_prediction_end() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(PREDICTION_END)?;
+
+    let subscription_type = message
+        .subscription()
+        .and_then(ReceivedTwitchSubscription::subscription_type)
+        .context("Could not find subscription type")?;
+
+    assert_eq!(subscription_type, &Subscription::PredictionEnd);
+
+    let event = message.event().context("Could not find message event")?;
+
+    let prediction = live_poll(subscription_type, event, "cool_user".to_string())
+        .context("Could not read prediction")?;
+
+    assert_eq!(prediction.kind, PollKind::Prediction);
+    assert!(matches!(
+        &prediction.status,
+        PollStatus::Ended { outcome, .. } if outcome == "resolved"
+    ));
+
+    let outcomes = prediction
+        .choices
+        .iter()
+        .map(|choice| {
+            (
+                choice.title.as_str(),
+                choice.amount,
+                choice.users,
+                choice.winner,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        outcomes,
+        vec![
+            ("Yeah!", 15000, Some(2), true),
+            ("No!", 200, Some(2), false)
+        ]
+    );
+
+    Ok(())
+}
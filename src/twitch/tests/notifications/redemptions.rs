@@ -0,0 +1,48 @@
+use color_eyre::{Result, eyre::ContextCompat};
+
+use crate::twitch::{
+    api::{channel_points::CustomReward, subscriptions::Subscription},
+    handlers::incoming_message::redemption_notice,
+    models::{ReceivedTwitchMessagePayload, ReceivedTwitchSubscription},
+    tests::{notifications::REDEMPTION, utils::load_data},
+};
+
+#[test]
+fn test_deserialize_redemption() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(REDEMPTION)?;
+
+    let subscription_type = message
+        .subscription()
+        .and_then(ReceivedTwitchSubscription::subscription_type)
+        .context("Could not find subscription type")?;
+
+    assert_eq!(subscription_type, &Subscription::Redemption);
+
+    let event = message.event().context("Could not find message event")?;
+
+    let reward = event.reward().context("Could not find redeemed reward")?;
+
+    assert_eq!(reward.title, "Hydrate");
+    assert_eq!(reward.cost, 500);
+    assert_eq!(event.user_input(), Some(""));
+
+    Ok(())
+}
+
+#[test]
+fn test_redemption_notice() {
+    let reward = CustomReward {
+        id: "92af127c-7326-4483-a52b-b0da0be61c01".to_string(),
+        title: "Hydrate".to_string(),
+        cost: 500,
+    };
+
+    assert_eq!(
+        redemption_notice("Cooler_User", Some(&reward)),
+        "Cooler_User redeemed Hydrate for 500 points"
+    );
+    assert_eq!(
+        redemption_notice("Cooler_User", None),
+        "Cooler_User redeemed a reward"
+    );
+}
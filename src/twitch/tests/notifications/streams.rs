@@ -0,0 +1,30 @@
+use color_eyre::{Result, eyre::ContextCompat};
+
+use crate::twitch::{
+    api::subscriptions::Subscription,
+    models::{ReceivedTwitchMessagePayload, ReceivedTwitchSubscription},
+    tests::{notifications::CHANNEL_UPDATE, utils::load_data},
+};
+
+#[test]
+fn test_channel_update() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(CHANNEL_UPDATE)?;
+
+    let subscription_type = message
+        .subscription()
+        .and_then(ReceivedTwitchSubscription::subscription_type)
+        .context("Could not find subscription type")?;
+
+    assert_eq!(subscription_type, &Subscription::ChannelUpdate);
+    assert_eq!(subscription_type.version(), "2");
+
+    let event = message.event().context("Could not find message event")?;
+
+    assert_eq!(event.title().map(String::as_str), Some("Best Stream Ever"));
+    assert_eq!(
+        event.category_name().map(String::as_str),
+        Some("Grand Theft Auto")
+    );
+
+    Ok(())
+}
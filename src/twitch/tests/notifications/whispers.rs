@@ -0,0 +1,44 @@
+use color_eyre::{Result, eyre::ContextCompat};
+
+use crate::twitch::{
+    api::subscriptions::Subscription,
+    models::{ReceivedTwitchMessagePayload, ReceivedTwitchSubscription},
+    tests::{notifications::WHISPER, utils::load_data},
+};
+
+#[test]
+fn test_deserialize_whisper() -> Result<()> {
+    let (_, message) = load_data::<ReceivedTwitchMessagePayload>(WHISPER)?;
+
+    let subscription_type = message
+        .subscription()
+        .and_then(ReceivedTwitchSubscription::subscription_type)
+        .context("Could not find subscription type")?;
+
+    assert_eq!(subscription_type, &Subscription::Whisper);
+
+    let event = message.event().context("Could not find message event")?;
+
+    assert_eq!(event.whisper_sender(), Some(&"glowillig".to_string()));
+    assert_eq!(event.whisper_text(), Some("Hey, Can You Hear Me?"));
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_user_subscription_without_broadcaster() -> Result<()> {
+    let subscription = ReceivedTwitchSubscription::new(
+        String::new(),
+        "423374343".to_string(),
+        "AgoQHR3s6Mb4T8GFB1l3DlPfiRIGY2VsbC1h".to_string(),
+    );
+
+    let condition = serde_json::to_value(&subscription)?
+        .get("condition")
+        .cloned()
+        .context("Could not find subscription condition")?;
+
+    assert_eq!(condition, serde_json::json!({ "user_id": "423374343" }));
+
+    Ok(())
+}
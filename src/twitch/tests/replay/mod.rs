@@ -0,0 +1,4 @@
+mod recorder;
+mod recording;
+
+static RECORDING: &str = include_str!("data/recording.jsonl");
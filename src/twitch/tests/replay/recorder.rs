@@ -0,0 +1,71 @@
+use std::fs::read_to_string;
+
+use color_eyre::Result;
+use serde_json::json;
+
+use crate::twitch::{
+    recorder::{FrameRecorder, redact},
+    replay::parse_recording,
+    tests::replay::RECORDING,
+};
+
+static SESSION_ID: &str = "AgoQHR3s6Mb4T8GFB1l3DlPfiRIGY2VsbC1h";
+
+#[test]
+fn test_redact_session_and_secrets() {
+    let mut frame = json!({
+        "payload": {
+            "session": {
+                "id": SESSION_ID,
+                "reconnect_url": null,
+            },
+            "subscription": {
+                "transport": {
+                    "session_id": SESSION_ID,
+                },
+            },
+            "event": {
+                "message": "my token is abc123",
+            },
+        },
+    });
+
+    redact(&mut frame, &["abc123"]);
+
+    assert_eq!(frame["payload"]["session"]["id"], "[redacted]");
+    assert!(frame["payload"]["session"]["reconnect_url"].is_null());
+    assert_eq!(
+        frame["payload"]["subscription"]["transport"]["session_id"],
+        "[redacted]"
+    );
+    assert_eq!(
+        frame["payload"]["event"]["message"],
+        "my token is [redacted]"
+    );
+}
+
+#[test]
+fn test_recorded_frames_can_be_replayed() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("capture.jsonl");
+
+    let mut recorder = FrameRecorder::new(&path, Some("oauth:abc123"))?;
+
+    for frame in RECORDING.lines() {
+        recorder.record(frame)?;
+    }
+    recorder.record("not json")?;
+
+    let capture = read_to_string(&path)?;
+
+    assert!(!capture.contains(SESSION_ID));
+    assert_eq!(capture.lines().count(), 4);
+
+    // The frame that isn't JSON is skipped, everything else is replayed
+    let messages = parse_recording(&capture);
+
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[1].message_type(), Some("notification"));
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use std::{fs::write, sync::Arc, time::Duration};
+
+use color_eyre::Result;
+use tokio::{sync::mpsc::channel, time::timeout};
+
+use crate::{
+    config::CoreConfig,
+    events::{Event, TwitchAction, TwitchEvent, TwitchNotification},
+    twitch::{
+        replay::{TwitchReplay, parse_recording, replay_delay},
+        tests::replay::RECORDING,
+    },
+};
+
+#[test]
+fn test_parse_recording_lines() {
+    let messages = parse_recording(RECORDING);
+
+    let message_types = messages
+        .iter()
+        .map(|message| message.message_type())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        message_types,
+        vec![
+            Some("session_welcome"),
+            Some("notification"),
+            Some("session_keepalive")
+        ]
+    );
+    assert!(messages[1].event().is_some());
+}
+
+#[test]
+fn test_parse_recording_array() -> Result<()> {
+    let values = RECORDING
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<serde_json::Value>, _>>()?;
+
+    let messages = parse_recording(&serde_json::to_string_pretty(&values)?);
+
+    assert_eq!(messages.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_recording_skips_invalid_frames() {
+    let mut lines = RECORDING.lines();
+    let first = lines.next().unwrap();
+    let rest = lines.collect::<Vec<_>>().join("\n");
+
+    // A frame that isn't a message in the middle, and a last line that was cut off
+    let recording = format!(
+        "{first}\n{{\"frame\": \"not a message\"}}\n{rest}\n{}",
+        &first[..first.len() / 2]
+    );
+
+    let messages = parse_recording(&recording);
+
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[1].message_type(), Some("notification"));
+}
+
+#[test]
+fn test_replay_delay_speed() {
+    let messages = parse_recording(RECORDING);
+
+    assert_eq!(
+        replay_delay(&messages[0], &messages[1], 1.0),
+        Duration::from_secs(2)
+    );
+    assert_eq!(
+        replay_delay(&messages[1], &messages[2], 4.0),
+        Duration::from_millis(2500)
+    );
+    assert_eq!(
+        replay_delay(&messages[0], &messages[2], 0.0),
+        Duration::ZERO
+    );
+    assert_eq!(
+        replay_delay(&messages[0], &messages[2], f64::NAN),
+        Duration::ZERO
+    );
+    assert_eq!(
+        replay_delay(&messages[0], &messages[2], f64::MIN_POSITIVE),
+        Duration::MAX
+    );
+}
+
+#[tokio::test]
+async fn test_replay_opens_recorded_channel() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("recording.jsonl");
+    write(&path, RECORDING)?;
+
+    let mut config = CoreConfig::default();
+    config.twitch.channel = "someone_else".to_string();
+
+    let (event_tx, mut event_rx) = channel(100);
+    let (_twitch_tx, twitch_rx) = channel(100);
+    let _replay = TwitchReplay::new(Arc::new(config), path, 0.0, event_tx, twitch_rx);
+
+    let mut opened_channels = vec![];
+    let mut message_channels = vec![];
+
+    while let Ok(Some(event)) = timeout(Duration::from_secs(5), event_rx.recv()).await {
+        match event {
+            Event::Twitch(TwitchEvent::Action(TwitchAction::OpenChannel(channel))) => {
+                opened_channels.push(channel);
+            }
+            Event::Twitch(TwitchEvent::Notification(TwitchNotification::Message(message))) => {
+                if message.payload == "Replay finished" {
+                    break;
+                }
+                if !message.system {
+                    message_channels.push(message.channel);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    assert_eq!(opened_channels, vec!["streamer".to_string()]);
+    assert_eq!(message_channels, vec![Some("streamer".to_string())]);
+
+    Ok(())
+}
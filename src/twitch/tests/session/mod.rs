@@ -0,0 +1,3 @@
+mod reconnect;
+
+static SESSION_RECONNECT: &str = include_str!("data/session_reconnect.json");
@@ -0,0 +1,27 @@
+use color_eyre::{Result, eyre::ContextCompat};
+
+use crate::twitch::{
+    models::ReceivedTwitchMessage,
+    tests::{session::SESSION_RECONNECT, utils::load_data},
+};
+
+#[test]
+fn test_deserialize_session_reconnect() -> Result<()> {
+    let (raw, message) = load_data::<ReceivedTwitchMessage>(SESSION_RECONNECT)?;
+
+    let raw_reconnect_url = raw
+        .pointer("/payload/session/reconnect_url")
+        .context("Could not find raw reconnect URL")?
+        .as_str()
+        .context("Could not convert raw reconnect URL to string")?
+        .to_string();
+
+    let reconnect_url = message
+        .reconnect_url()
+        .context("Could not find deserialized reconnect URL")?;
+
+    assert_eq!(message.message_type(), Some("session_reconnect"));
+    assert_eq!(raw_reconnect_url, reconnect_url);
+
+    Ok(())
+}
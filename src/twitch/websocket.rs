@@ -1,26 +1,59 @@
-use color_eyre::{Result, eyre::bail};
+use std::time::Duration;
+
+use color_eyre::{
+    Report, Result,
+    eyre::{ContextCompat, bail},
+};
 use futures::StreamExt;
-use tokio::sync::mpsc::{Receiver, Sender};
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
-use tracing::{debug, error, info};
+use tokio::{
+    sync::mpsc::{Receiver, Sender},
+    time::{Instant, MissedTickBehavior, interval_at, sleep, sleep_until},
+};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{self, Utf8Bytes, http::StatusCode, protocol::Message},
+};
+use tracing::{debug, error, info, warn};
 
 use crate::{
     config::SharedCoreConfig,
-    events::{Event, TwitchAction},
+    events::{ConnectionState, Event, TwitchAction, TwitchNotification},
     handlers::{data::DataBuilder, state::State},
     twitch::{
         context::TwitchWebsocketContext,
         handlers::{
             incoming_message::handle_incoming_message,
             message_commands::handle_command_message,
+            moderation::{handle_delete_message, handle_user_timeout},
             send_message::handle_send_message,
-            welcome_message::{handle_channel_join, handle_welcome_message},
+            stream_status::handle_stream_status_refresh,
+            welcome_message::{
+                handle_channel_focus, handle_channel_join, handle_channel_leave,
+                handle_channel_open, handle_reconnect_welcome_message, handle_welcome_message,
+            },
         },
         models::ReceivedTwitchMessage,
-        oauth::TwitchOauth,
+        oauth::{TwitchOauth, is_token_rejected},
+        recorder::FrameRecorder,
     },
 };
 
+/// How long to wait before the first reconnection attempt, doubled after every failure
+const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// The longest amount of time to wait between reconnection attempts
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// How many times to attempt to reconnect before giving up
+const RECONNECT_MAX_ATTEMPTS: u32 = 10;
+
+/// How long past the keepalive timeout to wait for a message, so that one that is late by a moment does not cause a reconnect
+const KEEPALIVE_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// How often the joined channels' streams are polled, since Twitch has no event for viewer counts
+const STREAM_STATUS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// <https://dev.twitch.tv/docs/eventsub/handling-websocket-events/#reconnect-message>
+const SESSION_RECONNECT_MESSAGE_TYPE: &str = "session_reconnect";
+
 pub struct TwitchWebsocket {
     // rx: Receiver<TwitchAction>,
 }
@@ -31,11 +64,12 @@ impl TwitchWebsocket {
         twitch_oauth: TwitchOauth,
         event_tx: Sender<Event>,
         twitch_rx: Receiver<TwitchAction>,
+        recorder: Option<FrameRecorder>,
     ) -> Self {
         let mut context = TwitchWebsocketContext::default();
         context.set_oauth(Some(twitch_oauth));
 
-        let mut actor = TwitchWebsocketThread::new(config, context, event_tx, twitch_rx);
+        let mut actor = TwitchWebsocketThread::new(config, context, event_tx, twitch_rx, recorder);
         tokio::task::spawn(async move { actor.run().await });
 
         Self {}
@@ -55,6 +89,8 @@ pub struct TwitchWebsocketThread {
     context: TwitchWebsocketContext,
     event_tx: Sender<Event>,
     action_rx: Receiver<TwitchAction>,
+    /// Captures every frame from Twitch to a file, if requested
+    recorder: Option<FrameRecorder>,
 }
 
 impl TwitchWebsocketThread {
@@ -63,31 +99,62 @@ impl TwitchWebsocketThread {
         context: TwitchWebsocketContext,
         event_tx: Sender<Event>,
         action_rx: Receiver<TwitchAction>,
+        recorder: Option<FrameRecorder>,
     ) -> Self {
         Self {
             config,
             context,
             event_tx,
             action_rx,
+            recorder,
         }
     }
 
-    async fn connect(&mut self) -> Result<WebsocketStream> {
-        let url = self.config.twitch.config_twitch_websocket_url();
+    fn record_frame(&mut self, frame: &str) {
+        let Some(recorder) = &mut self.recorder else {
+            return;
+        };
+
+        if let Err(err) = recorder.record(frame) {
+            warn!("Failed to record websocket frame: {err}");
+        }
+    }
+
+    async fn open_stream(url: &str) -> Result<WebsocketStream> {
         let (ws_stream, _) = match connect_async(url).await {
             Ok(websocket_connection) => websocket_connection,
             Err(err) => {
-                bail!(
-                    "Failed to connect to websocket server at {}: {}",
-                    self.config.twitch.server,
-                    err
-                )
+                let message = format!("Failed to connect to websocket server at {url}: {err}");
+
+                // The error is kept so that its status code can be looked at
+                return Err(Report::new(err).wrap_err(message));
             }
         };
 
         info!("Twitch websocket handshake successful");
 
-        let (_, mut stream) = ws_stream.split();
+        let (_, stream) = ws_stream.split();
+
+        Ok(stream)
+    }
+
+    /// The welcome message is the first text message of every session,
+    /// anything arriving before it is the websocket server making sure you're here.
+    async fn receive_welcome_message(stream: &mut WebsocketStream) -> Result<Utf8Bytes> {
+        while let Some(message) = stream.next().await {
+            if let Message::Text(message) = message? {
+                return Ok(message);
+            }
+
+            debug!("Websocket server has pinged you to make sure you're here");
+        }
+
+        bail!("Websocket connection closed before a welcome message was received")
+    }
+
+    async fn connect(&mut self) -> Result<WebsocketStream> {
+        let url = self.config.twitch.config_twitch_websocket_url();
+        let mut stream = Self::open_stream(&url).await?;
 
         // If the dashboard is the start state, wait until the user has selected
         // a channel before connecting to Twitch's websocket server.
@@ -112,18 +179,15 @@ impl TwitchWebsocketThread {
         self.context.set_emotes_state(emotes_enabled);
         self.context.set_token(self.config.twitch.token.clone());
 
-        if stream.next().await.is_some() {
-            debug!("Websocket server has pinged you to make sure you're here");
-        }
-
-        // Handle the welcome message, it should arrive after the initial ping
-        let Some(Ok(Message::Text(message))) = stream.next().await else {
+        let Ok(message) = Self::receive_welcome_message(&mut stream).await else {
             let error_message = "Welcome message from websocket server was not found, something has gone terribly wrong";
             self.event_tx
                 .send(DataBuilder::system(error_message.to_string()).into())
                 .await?;
             bail!(error_message);
         };
+        self.record_frame(&message);
+
         if let Err(err) = handle_welcome_message(&mut self.context, &self.event_tx, message).await {
             let error_message = format!("Failed to handle welcome message: {err}");
             self.event_tx
@@ -132,13 +196,157 @@ impl TwitchWebsocketThread {
             bail!(error_message);
         }
 
+        self.send_connection_state(ConnectionState::Connected)
+            .await?;
+
+        Ok(stream)
+    }
+
+    async fn send_connection_state(&self, state: ConnectionState) -> Result<()> {
+        self.event_tx
+            .send(TwitchNotification::ConnectionState(state).into())
+            .await?;
+
+        Ok(())
+    }
+
+    /// The keepalive timeout of the current session, falling back to the one requested in the config
+    fn keepalive_timeout(&self) -> Duration {
+        self.context.keepalive_timeout().unwrap_or_else(|| {
+            Duration::from_secs(self.config.twitch.keepalive_timeout_seconds as u64)
+        })
+    }
+
+    /// Connects to a new session that replaces the current one
+    async fn open_session(&mut self, url: &str, resubscribe: bool) -> Result<WebsocketStream> {
+        let mut stream = Self::open_stream(url).await?;
+
+        let message = Self::receive_welcome_message(&mut stream).await?;
+        self.record_frame(&message);
+
+        handle_reconnect_welcome_message(&mut self.context, message, resubscribe).await?;
+
         Ok(stream)
     }
 
+    /// Keeps attempting to connect to a fresh session with an exponential backoff,
+    /// Gives up once Twitch has refused the connection or rejected the token, or after [`RECONNECT_MAX_ATTEMPTS`] failures.
+    /// Gives up once Twitch has rejected the token, or after [`RECONNECT_MAX_ATTEMPTS`] failures.
+    async fn reconnect(&mut self) -> Result<WebsocketStream> {
+        self.send_connection_state(ConnectionState::Reconnecting)
+            .await?;
+        self.event_tx
+            .send(
+                DataBuilder::system("Lost connection to Twitch, reconnecting...".to_string())
+                    .into(),
+            )
+            .await?;
+
+        let url = self.config.twitch.config_twitch_websocket_url();
+        let mut backoff = RECONNECT_INITIAL_BACKOFF;
+        let mut attempts = 0;
+
+        loop {
+            match self.open_session(&url, true).await {
+                Ok(stream) => {
+                    info!("Reconnected to Twitch websocket server");
+
+                    self.event_tx
+                        .send(DataBuilder::system("Reconnected to Twitch".to_string()).into())
+                        .await?;
+                    self.send_connection_state(ConnectionState::Connected)
+                        .await?;
+
+                    return Ok(stream);
+                }
+                Err(err) => {
+                    attempts += 1;
+
+                    let reason = if is_handshake_rejected(&err) {
+                        Some("Twitch refused the connection")
+                    } else if is_token_rejected(
+                        self.config.twitch.token.as_ref(),
+                        &self.config.twitch.oauth_validate_url,
+                    )
+                    .await
+                    {
+                        Some("Twitch rejected the token, it may be invalid or expired")
+                    } else if attempts >= RECONNECT_MAX_ATTEMPTS {
+                        Some("Twitch could not be reached")
+                    } else {
+                        None
+                    };
+
+                    if let Some(reason) = reason {
+                        return self.stop_reconnecting(reason, attempts, err).await;
+                    }
+
+                    warn!(
+                        "Failed to reconnect to Twitch, retrying in {} second(s): {err}",
+                        backoff.as_secs()
+                    );
+
+                    sleep(backoff).await;
+                    backoff = (backoff * 2).min(RECONNECT_MAX_BACKOFF);
+                }
+            }
+        }
+    }
+
+    /// Tells the user why the connection to Twitch will not be attempted again
+    async fn stop_reconnecting(
+        &self,
+        reason: &str,
+        attempts: u32,
+        err: Report,
+    ) -> Result<WebsocketStream> {
+        let message =
+            format!("Stopped reconnecting to Twitch after {attempts} attempt(s). {reason}: {err}");
+
+        error!("{message}");
+
+        self.event_tx
+            .send(DataBuilder::system(message.clone()).into())
+            .await?;
+        self.send_connection_state(ConnectionState::Disconnected)
+            .await?;
+
+        bail!(message)
+    }
+
+    /// Moves to the session that Twitch has asked the client to reconnect to.
+    /// Falls back to a fresh session if the reconnect URL cannot be reached.
+    async fn handle_session_reconnect(&mut self, reconnect_url: &str) -> Result<WebsocketStream> {
+        info!("Twitch websocket server requested a reconnect");
+
+        match self.open_session(reconnect_url, false).await {
+            Ok(stream) => Ok(stream),
+            Err(err) => {
+                warn!("Failed to move to the reconnect URL session: {err}");
+
+                self.reconnect().await
+            }
+        }
+    }
+
     async fn run(&mut self) -> Result<()> {
         let mut stream = self.connect().await?;
 
+        // Any message from the server, keepalive or otherwise, proves that the connection is still alive.
+        // <https://dev.twitch.tv/docs/eventsub/handling-websocket-events/#keepalive-message>
+        let mut last_message = Instant::now();
+
+        // Every channel's stream status was just fetched when it was joined
+        let mut stream_status_interval = interval_at(
+            Instant::now() + STREAM_STATUS_INTERVAL,
+            STREAM_STATUS_INTERVAL,
+        );
+        stream_status_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
         loop {
+            let keepalive_deadline =
+                last_message + self.keepalive_timeout() + KEEPALIVE_GRACE_PERIOD;
+
             tokio::select! {
                 biased;
 
@@ -147,17 +355,38 @@ impl TwitchWebsocketThread {
                         error!("Failed to handle twitch action: {err}");
                     }
                 }
-                Some(message) = stream.next() => {
+                _ = stream_status_interval.tick() => {
+                    if let Err(err) = handle_stream_status_refresh(&mut self.context, &self.event_tx).await {
+                        warn!("Failed to refresh the stream status of the joined channels: {err}");
+                    }
+                }
+                message = stream.next() => {
                     match message {
-                        Ok(msg) => if let Err(err) = self.handle_websocket_stream_message(msg).await {
-                            error!("Failed to handle websocket message: {err}");
+                        Some(Ok(msg)) => match self.handle_websocket_stream_message(msg).await {
+                            Ok(Some(reconnect_url)) => {
+                                stream = self.handle_session_reconnect(&reconnect_url).await?;
+                            }
+                            Ok(None) => {}
+                            Err(err) => error!("Failed to handle websocket message: {err}"),
                         },
-                        Err(err) => {
+                        Some(Err(err)) => {
                             error!("Twitch connection error encountered: {err}, attempting to reconnect.");
+                            stream = self.reconnect().await?;
+                        }
+                        None => {
+                            error!("Twitch connection was closed, attempting to reconnect.");
+                            stream = self.reconnect().await?;
                         }
                     }
+
+                    last_message = Instant::now();
+                }
+                // Checked after the stream, so that messages which are already waiting to be read count
+                () = sleep_until(keepalive_deadline) => {
+                    error!("No message received from Twitch within the keepalive timeout, attempting to reconnect.");
+                    stream = self.reconnect().await?;
+                    last_message = Instant::now();
                 }
-                else => {}
             };
         }
     }
@@ -180,30 +409,134 @@ impl TwitchWebsocketThread {
                         return Err(err);
                     }
                 } else {
-                    handle_send_message(&self.context, message).await?;
+                    handle_send_message(&self.context, message, None).await?;
                 }
             }
+            TwitchAction::Reply {
+                message,
+                parent_message_id,
+            } => {
+                handle_send_message(&self.context, message, Some(parent_message_id)).await?;
+            }
+            TwitchAction::DeleteMessage(message_id) => {
+                let result = handle_delete_message(&self.context, &self.event_tx, message_id).await;
+
+                self.report_moderation_error(result, "delete message")
+                    .await?;
+            }
+            TwitchAction::TimeoutUser {
+                user_id,
+                username,
+                duration,
+            } => {
+                let result = handle_user_timeout(
+                    &self.context,
+                    &self.event_tx,
+                    user_id,
+                    &username,
+                    duration,
+                )
+                .await;
+
+                self.report_moderation_error(result, "timeout user").await?;
+            }
             TwitchAction::JoinChannel(channel_name) => {
                 handle_channel_join(&mut self.context, &self.event_tx, channel_name, false).await?;
             }
+            TwitchAction::OpenChannel(channel_name) => {
+                handle_channel_open(&mut self.context, &self.event_tx, channel_name).await?;
+            }
+            TwitchAction::LeaveChannel(channel_name) => {
+                handle_channel_leave(&mut self.context, &channel_name).await?;
+            }
+            TwitchAction::FocusChannel(channel_name) => {
+                handle_channel_focus(&mut self.context, channel_name)?;
+            }
         }
 
         Ok(())
     }
 
-    async fn handle_websocket_stream_message(&self, message: Message) -> Result<()> {
+    /// Shows the user why a moderation action failed, since it was most likely not permitted
+    async fn report_moderation_error(&self, result: Result<()>, action: &str) -> Result<()> {
+        if let Err(err) = result {
+            self.event_tx
+                .send(DataBuilder::twitch(format!("Failed to {action}: {err}")).into())
+                .await?;
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the URL of the session to move to if Twitch has requested a reconnect
+    async fn handle_websocket_stream_message(
+        &mut self,
+        message: Message,
+    ) -> Result<Option<String>> {
         let Message::Text(message_text) = message else {
-            return Ok(());
+            return Ok(None);
         };
 
+        // Recorded before deserializing, so that frames which fail to deserialize can be looked at
+        self.record_frame(&message_text);
+
         let received_message = serde_json::from_str::<ReceivedTwitchMessage>(&message_text)?;
 
-        handle_incoming_message(
-            self.config.clone(),
-            &self.context,
-            &self.event_tx,
-            received_message,
+        if received_message.message_type() == Some(SESSION_RECONNECT_MESSAGE_TYPE) {
+            let reconnect_url = received_message
+                .reconnect_url()
+                .context("Reconnect message did not contain a reconnect URL")?;
+
+            return Ok(Some(reconnect_url));
+        }
+
+        handle_incoming_message(&mut self.context, &self.event_tx, received_message).await?;
+
+        Ok(None)
+    }
+}
+
+/// If the websocket server turned down the connection because of something on our end,
+/// in which case trying again will not help. Being rate limited is only a reason to wait longer.
+fn is_handshake_rejected(err: &Report) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<tungstenite::Error>(),
+            Some(tungstenite::Error::Http(response))
+                if response.status().is_client_error()
+                    && response.status() != StatusCode::TOO_MANY_REQUESTS
         )
-        .await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::eyre;
+    use tokio_tungstenite::tungstenite::http::Response;
+
+    use super::*;
+
+    fn http_error(status: StatusCode) -> Report {
+        let response = Response::builder().status(status).body(None).unwrap();
+
+        Report::new(tungstenite::Error::Http(response)).wrap_err("Failed to connect")
+    }
+
+    #[test]
+    fn test_unauthorized_handshake_is_rejected() {
+        assert!(is_handshake_rejected(&http_error(StatusCode::UNAUTHORIZED)));
+    }
+
+    #[test]
+    fn test_server_error_is_retried() {
+        assert!(!is_handshake_rejected(&http_error(
+            StatusCode::SERVICE_UNAVAILABLE
+        )));
+        assert!(!is_handshake_rejected(&http_error(
+            StatusCode::TOO_MANY_REQUESTS
+        )));
+        assert!(!is_handshake_rejected(&eyre!("Connection reset")));
     }
 }
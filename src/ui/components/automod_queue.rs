@@ -0,0 +1,188 @@
+use color_eyre::Result;
+use tokio::sync::mpsc::Sender;
+use tui::{Frame, layout::Rect, text::Line};
+
+use super::utils::{STATUS_POPUP, SearchItemGetter, SearchWidget, Status, popup_area};
+use crate::{
+    config::SharedCoreConfig,
+    events::{Event, get_keybind_text},
+    handlers::{
+        automod::{HeldMessage, SharedAutoModQueue},
+        channels::SharedChannels,
+    },
+    twitch::{
+        api::automod::{AutoModAction, ManageHeldMessagePayload, manage_held_message},
+        oauth::TwitchOauth,
+    },
+    ui::components::Component,
+};
+
+static AUTOMOD_QUEUE_ERROR_MESSAGE: &[&str] = &[
+    "Failed to get the messages held by AutoMod.",
+    "",
+    "Hit ESC to dismiss this error.",
+];
+
+pub struct AutoModQueue {
+    automod_queue: SharedAutoModQueue,
+    channels: SharedChannels,
+}
+
+impl SearchItemGetter<HeldMessage> for AutoModQueue {
+    async fn get_items(&mut self) -> Result<Vec<HeldMessage>> {
+        let channel = self.channels.borrow().current_name().to_string();
+
+        Ok(self.automod_queue.borrow().channel_messages(&channel))
+    }
+}
+
+/// A popup listing the messages of the current channel that `AutoMod` is holding for review
+pub struct AutoModQueueWidget {
+    config: SharedCoreConfig,
+    twitch_oauth: TwitchOauth,
+    automod_queue: SharedAutoModQueue,
+    search_widget: SearchWidget<HeldMessage, AutoModQueue>,
+    status: Option<Status>,
+}
+
+impl AutoModQueueWidget {
+    pub fn new(
+        config: SharedCoreConfig,
+        twitch_oauth: TwitchOauth,
+        event_tx: Sender<Event>,
+        automod_queue: SharedAutoModQueue,
+        channels: SharedChannels,
+    ) -> Self {
+        let item_getter = AutoModQueue {
+            automod_queue: automod_queue.clone(),
+            channels,
+        };
+
+        let search_widget = SearchWidget::new(
+            config.clone(),
+            event_tx,
+            item_getter,
+            "AutoMod queue",
+            AUTOMOD_QUEUE_ERROR_MESSAGE.to_vec(),
+        );
+
+        Self {
+            config,
+            twitch_oauth,
+            automod_queue,
+            search_widget,
+            status: None,
+        }
+    }
+
+    pub const fn is_focused(&self) -> bool {
+        self.search_widget.is_focused()
+    }
+
+    pub async fn toggle_focus(&mut self) {
+        self.search_widget.toggle_focus().await;
+
+        if !self.is_focused() {
+            self.search_widget.clear_search();
+            self.status = None;
+        }
+    }
+
+    /// Approves or denies the selected message, taking it out of the queue
+    async fn manage_selected(&mut self, action: AutoModAction) {
+        let Some(held_message) = self.search_widget.selected_item() else {
+            return;
+        };
+
+        let description = format!("{}'s message", held_message.user_name);
+
+        let (Some(client), Some(user_id)) =
+            (self.twitch_oauth.client(), self.twitch_oauth.user_id())
+        else {
+            self.status = Some(Status::Failed(
+                "Twitch client could not be found".to_string(),
+            ));
+            return;
+        };
+
+        let payload = ManageHeldMessagePayload::new(user_id, held_message.id.clone(), action);
+
+        self.status = Some(match manage_held_message(&client, payload).await {
+            Ok(()) => {
+                self.automod_queue.borrow_mut().remove(&held_message.id);
+
+                Status::Done(format!("{action} {description}"))
+            }
+            Err(err) => Status::Failed(format!(
+                "{description} could not be {action}, the token may be missing the moderator:manage:automod scope: {err}"
+            )),
+        });
+
+        self.search_widget.refresh_items().await;
+    }
+
+    fn status_lines(&self) -> Vec<Line<'static>> {
+        let line = self.status.as_ref().map_or_else(
+            || {
+                let keybinds = &self.config.keybinds.selection;
+
+                Line::raw(format!(
+                    "Press {} to approve the selected message and send it to chat, or {} to deny it.",
+                    get_keybind_text(&keybinds.select),
+                    get_keybind_text(&keybinds.delete_item)
+                ))
+            },
+            Status::line,
+        );
+
+        vec![line]
+    }
+}
+
+impl Component for AutoModQueueWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.search_widget.set_config(config);
+    }
+
+    fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
+        let r = area.unwrap_or_else(|| popup_area(f.area(), 60, 60));
+        let status_lines = self.status_lines();
+
+        STATUS_POPUP.draw(
+            f,
+            r,
+            &mut self.search_widget,
+            status_lines,
+            self.config.frontend.border_type.clone().into(),
+        );
+    }
+
+    async fn event(&mut self, event: &Event) -> Result<()> {
+        if let Event::Input(key) = event {
+            let keybinds = &self.config.keybinds.selection;
+
+            // Selecting a message would otherwise join a channel by its name
+            if keybinds.select.contains(key) {
+                self.manage_selected(AutoModAction::Allow).await;
+
+                return Ok(());
+            }
+
+            if keybinds.delete_item.contains(key) {
+                self.manage_selected(AutoModAction::Deny).await;
+
+                return Ok(());
+            }
+        }
+
+        self.search_widget.event(event).await?;
+
+        if !self.is_focused() {
+            self.search_widget.clear_search();
+            self.status = None;
+        }
+
+        Ok(())
+    }
+}
@@ -37,6 +37,8 @@ pub struct ChannelSwitcherWidget {
     config: SharedCoreConfig,
     event_tx: Sender<Event>,
     focused: bool,
+    /// If the selected channel should be joined in a new tab rather than in place of the current one
+    open_in_new_tab: bool,
     storage: SharedStorage,
     search_input: InputWidget<SharedStorage>,
     list_state: ListState,
@@ -82,6 +84,7 @@ impl ChannelSwitcherWidget {
             config,
             event_tx,
             focused: false,
+            open_in_new_tab: false,
             storage,
             search_input,
             list_state: ListState::default(),
@@ -133,6 +136,26 @@ impl ChannelSwitcherWidget {
 
     pub const fn toggle_focus(&mut self) {
         self.focused = !self.focused;
+
+        if self.focused {
+            self.open_in_new_tab = false;
+        }
+    }
+
+    /// Focuses the switcher, joining the selected channel in a new tab
+    pub const fn toggle_focus_to_open(&mut self) {
+        self.toggle_focus();
+        self.open_in_new_tab = true;
+    }
+
+    const fn join_action(&self, channel: String) -> Event {
+        let action = if self.open_in_new_tab {
+            TwitchAction::OpenChannel(channel)
+        } else {
+            TwitchAction::JoinChannel(channel)
+        };
+
+        Event::Twitch(TwitchEvent::Action(action))
     }
 }
 
@@ -143,6 +166,11 @@ impl Display for ChannelSwitcherWidget {
 }
 
 impl Component for ChannelSwitcherWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.search_input.set_config(config);
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         let r = area.unwrap_or_else(|| popup_area(f.area(), 60, 60));
 
@@ -189,7 +217,11 @@ impl Component for ChannelSwitcherWidget {
             self.filtered_channels = Some(matched);
         }
 
-        let title_binding = [TitleStyle::Single("Channel switcher")];
+        let title_binding = [TitleStyle::Single(if self.open_in_new_tab {
+            "Open channel"
+        } else {
+            "Channel switcher"
+        })];
 
         let list = List::new(items.clone())
             .block(
@@ -300,9 +332,7 @@ impl Component for ChannelSwitcherWidget {
                                     self.search_input.clear();
 
                                     self.event_tx
-                                        .send(Event::Twitch(TwitchEvent::Action(
-                                            TwitchAction::JoinChannel(selected_channel),
-                                        )))
+                                        .send(self.join_action(selected_channel))
                                         .await?;
                                 }
                             }
@@ -326,9 +356,7 @@ impl Component for ChannelSwitcherWidget {
                         );
 
                         self.event_tx
-                            .send(Event::Twitch(TwitchEvent::Action(
-                                TwitchAction::JoinChannel(selected_channel.clone()),
-                            )))
+                            .send(self.join_action(selected_channel.clone()))
                             .await?;
                     } else if self.search_input.is_valid() {
                         self.toggle_focus();
@@ -347,9 +375,7 @@ impl Component for ChannelSwitcherWidget {
                         info!("Joining new channel {selected_channel:?}");
 
                         self.event_tx
-                            .send(Event::Twitch(TwitchEvent::Action(
-                                TwitchAction::JoinChannel(selected_channel),
-                            )))
+                            .send(self.join_action(selected_channel))
                             .await?;
                     }
                 }
@@ -1,29 +1,49 @@
-use std::{collections::VecDeque, slice::Iter};
+use std::{
+    collections::{HashMap, VecDeque},
+    slice::Iter,
+};
 
-use chrono::Local;
+use chrono::{Local, Utc};
 use color_eyre::Result;
 use tokio::sync::mpsc::Sender;
 use tui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols::DOT,
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, TitlePosition},
+    widgets::{Block, Borders, List, ListItem, Tabs, TitlePosition},
 };
 
 use crate::{
-    app::SharedMessages,
     config::SharedCoreConfig,
     emotes::SharedEmotes,
-    events::{Event, InternalEvent, TwitchAction, TwitchEvent},
-    handlers::{data::MessageData, filters::SharedFilters, state::State, storage::SharedStorage},
+    events::{
+        ConnectionState, Event, InternalEvent, Key, TwitchAction, TwitchEvent, get_keybind_text,
+    },
+    handlers::{
+        automod::SharedAutoModQueue,
+        channels::SharedChannels,
+        data::{MessageData, ReplyParent},
+        filters::SharedFilters,
+        highlights::SharedHighlights,
+        polls::SharedPolls,
+        state::State,
+        storage::SharedStorage,
+        stream_status::StreamStatus,
+        whispers::SharedWhispers,
+    },
     twitch::oauth::TwitchOauth,
     ui::components::{
-        ChannelSwitcherWidget, ChatInputWidget, Component, FollowingWidget, MessageSearchWidget,
-        utils::Scrolling,
+        AutoModQueueWidget, ChannelSwitcherWidget, ChatInputWidget, Component, FilterEditorWidget,
+        FilterPresetsWidget, FollowingWidget, MessageSearchWidget, PollsWidget,
+        RedemptionQueueWidget, WhispersWidget, utils::Scrolling,
     },
     utils::{
-        styles::{NO_COLOR, TEXT_DARK_STYLE, TITLE_STYLE},
+        clipboard::copy_to_clipboard,
+        styles::{
+            NO_COLOR, SELECTED_MESSAGE_STYLE, STATE_TABS_STYLE, TEXT_DARK_STYLE, TITLE_STYLE,
+        },
         text::{TitleStyle, title_line},
     },
 };
@@ -31,14 +51,31 @@ use crate::{
 pub struct ChatWidget {
     config: SharedCoreConfig,
     event_tx: Sender<Event>,
-    messages: SharedMessages,
+    channels: SharedChannels,
     chat_input: ChatInputWidget,
     channel_input: ChannelSwitcherWidget,
     search_input: MessageSearchWidget,
     following: FollowingWidget,
+    filter_presets: FilterPresetsWidget,
+    filter_editor: FilterEditorWidget,
+    whispers_pane: WhispersWidget,
+    redemption_queue: RedemptionQueueWidget,
+    automod_queue_popup: AutoModQueueWidget,
+    polls_panel: PollsWidget,
     filters: SharedFilters,
+    whispers: SharedWhispers,
+    automod_queue: SharedAutoModQueue,
+    highlights: SharedHighlights,
+    /// What is known about the stream of each joined channel, keyed by channel name
+    stream_statuses: HashMap<String, StreamStatus>,
     pub scroll_offset: Scrolling,
-    current_channel_name: String,
+    pub connection_state: ConnectionState,
+    /// Index of the selected message within the current channel's messages
+    selected_message: Option<usize>,
+    /// The ID and name of the user that is banned if the ban key is pressed again
+    pending_ban: Option<(String, String)>,
+    /// How many messages fit entirely within the chat window when it was last drawn
+    rendered_messages: usize,
     // theme: Theme,
 }
 
@@ -47,13 +84,15 @@ impl ChatWidget {
         config: SharedCoreConfig,
         twitch_oauth: TwitchOauth,
         event_tx: Sender<Event>,
-        messages: SharedMessages,
+        channels: SharedChannels,
         storage: &SharedStorage,
         emotes: &SharedEmotes,
         filters: SharedFilters,
+        highlights: SharedHighlights,
+        whispers: SharedWhispers,
+        polls: SharedPolls,
+        automod_queue: SharedAutoModQueue,
     ) -> Self {
-        let current_channel_name = config.twitch.channel.clone();
-
         let chat_input: ChatInputWidget = ChatInputWidget::new(
             config.clone(),
             event_tx.clone(),
@@ -63,43 +102,386 @@ impl ChatWidget {
         let channel_input =
             ChannelSwitcherWidget::new(config.clone(), event_tx.clone(), storage.clone());
         let search_input = MessageSearchWidget::new(config.clone(), event_tx.clone());
-        let following = FollowingWidget::new(config.clone(), twitch_oauth, event_tx.clone());
+        let following =
+            FollowingWidget::new(config.clone(), twitch_oauth.clone(), event_tx.clone());
+        let redemption_queue = RedemptionQueueWidget::new(
+            config.clone(),
+            twitch_oauth.clone(),
+            event_tx.clone(),
+            channels.clone(),
+        );
+        let automod_queue_popup = AutoModQueueWidget::new(
+            config.clone(),
+            twitch_oauth,
+            event_tx.clone(),
+            automod_queue.clone(),
+            channels.clone(),
+        );
+
+        let filter_presets = FilterPresetsWidget::new(config.clone(), filters.clone());
+        let filter_editor = FilterEditorWidget::new(
+            config.clone(),
+            event_tx.clone(),
+            filters.clone(),
+            channels.clone(),
+        );
+        let whispers_pane = WhispersWidget::new(config.clone(), event_tx.clone(), whispers.clone());
+        let polls_panel = PollsWidget::new(config.clone(), polls, channels.clone());
 
         let scroll_offset = Scrolling::new(config.frontend.inverted_scrolling);
 
         Self {
             config,
             event_tx,
-            messages,
+            channels,
             chat_input,
             channel_input,
             search_input,
             following,
+            filter_presets,
+            filter_editor,
+            whispers_pane,
+            redemption_queue,
+            automod_queue_popup,
+            polls_panel,
             filters,
+            whispers,
+            automod_queue,
+            highlights,
+            stream_statuses: HashMap::new(),
             scroll_offset,
-            current_channel_name,
+            connection_state: ConnectionState::default(),
+            selected_message: None,
+            pending_ban: None,
+            rendered_messages: 0,
         }
     }
 
     pub async fn open_in_player(&self) -> Result<()> {
-        let channel_name = self.current_channel_name.as_str();
+        let channel_name = self.channels.borrow().current_name().to_string();
         if self.config.frontend.view_command.is_empty() {
             webbrowser::open(format!(
             "https://player.twitch.tv/?channel={channel_name}&enableExtensions=true&parent=twitch.tv&quality=chunked",
             ).as_str())?;
         } else {
             self.event_tx
-                .send(Event::Internal(InternalEvent::OpenStream(
-                    channel_name.to_string(),
-                )))
+                .send(Event::Internal(InternalEvent::OpenStream(channel_name)))
                 .await?;
         }
 
         Ok(())
     }
 
+    fn draw_channel_tabs(&self, f: &mut Frame, area: Rect) {
+        let channels = self.channels.borrow();
+
+        let tab_titles = channels
+            .names()
+            .map(|name| Line::from(name.as_str()))
+            .collect::<Vec<Line>>();
+
+        let tabs = Tabs::new(tab_titles)
+            .style(*STATE_TABS_STYLE)
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .remove_modifier(Modifier::DIM)
+                    .add_modifier(Modifier::UNDERLINED),
+            )
+            .divider(DOT)
+            .select(channels.current_index());
+
+        f.render_widget(tabs, area);
+    }
+
+    pub fn set_stream_status(&mut self, channel: &str, status: StreamStatus) {
+        self.stream_statuses.insert(channel.to_lowercase(), status);
+    }
+
+    /// The stream status of the current channel, if it is known and should be shown
+    fn current_stream_status(&self) -> Option<&StreamStatus> {
+        if !self.config.frontend.stream_status_shown {
+            return None;
+        }
+
+        let channel = self.channels.borrow().current_name().to_lowercase();
+
+        self.stream_statuses.get(&channel)
+    }
+
+    fn draw_stream_status(f: &mut Frame, area: Rect, status: &StreamStatus) {
+        let uptime = status.uptime(Utc::now());
+        let viewers = status.viewers.map(|viewers| viewers.to_string());
+
+        let mut spans = vec![];
+
+        if let Some(uptime) = &uptime {
+            spans.push(TitleStyle::Combined("Live", uptime));
+        } else {
+            spans.push(TitleStyle::Single("Offline"));
+        }
+
+        if let Some(viewers) = &viewers {
+            spans.push(TitleStyle::Combined("Viewers", viewers));
+        }
+
+        if !status.category.is_empty() {
+            spans.push(TitleStyle::Combined("Category", &status.category));
+        }
+
+        if !status.title.is_empty() {
+            spans.push(TitleStyle::Combined("Title", &status.title));
+        }
+
+        let style = if *NO_COLOR {
+            Style::default()
+        } else if status.is_live() {
+            Style::default().fg(Color::LightRed)
+        } else {
+            *TITLE_STYLE
+        };
+
+        f.render_widget(Line::from(title_line(&spans, style)), area);
+    }
+
+    fn is_filtered(&self, data: &MessageData) -> bool {
+        self.filters.borrow().hides(data)
+    }
+
+    /// How many visible messages are newer than the message at `index`
+    fn visible_position(&self, messages: &VecDeque<MessageData>, index: usize) -> usize {
+        messages
+            .iter()
+            .take(index)
+            .filter(|data| !self.is_filtered(data))
+            .count()
+    }
+
+    /// Selects the newest message that is currently in view
+    fn select_message(&mut self) {
+        let current_messages = self.channels.borrow().current_messages();
+        let messages = current_messages.borrow();
+
+        self.selected_message = messages
+            .iter()
+            .enumerate()
+            .filter(|(_, data)| !self.is_filtered(data))
+            .nth(self.scroll_offset.get_offset())
+            .map(|(index, _)| index);
+    }
+
+    /// Moves the selection to an older message, scrolling up if it goes out of view
+    fn select_older_message(&mut self) {
+        let Some(selected) = self.selected_message else {
+            return;
+        };
+
+        let current_messages = self.channels.borrow().current_messages();
+        let messages = current_messages.borrow();
+
+        let Some(index) = (selected + 1..messages.len()).find(|&i| !self.is_filtered(&messages[i]))
+        else {
+            return;
+        };
+
+        self.selected_message = Some(index);
+
+        if self.visible_position(&messages, index)
+            >= self.scroll_offset.get_offset() + self.rendered_messages
+        {
+            self.scroll_offset.up();
+        }
+    }
+
+    /// Moves the selection to a newer message, scrolling down if it goes out of view
+    fn select_newer_message(&mut self) {
+        let Some(selected) = self.selected_message else {
+            return;
+        };
+
+        let current_messages = self.channels.borrow().current_messages();
+        let messages = current_messages.borrow();
+
+        let Some(index) = (0..selected)
+            .rev()
+            .find(|&i| !self.is_filtered(&messages[i]))
+        else {
+            return;
+        };
+
+        self.selected_message = Some(index);
+
+        let position = self.visible_position(&messages, index);
+
+        if position < self.scroll_offset.get_offset() {
+            self.scroll_offset.jump_to(position);
+        }
+    }
+
+    /// Selects the next older message that mentions the current user,
+    /// starting over from the newest one after the oldest has been reached
+    fn select_next_mention(&mut self) {
+        let current_messages = self.channels.borrow().current_messages();
+        let messages = current_messages.borrow();
+
+        let is_mention =
+            |&index: &usize| messages[index].ping && !self.is_filtered(&messages[index]);
+
+        let start = self
+            .selected_message
+            .map_or(0, |selected| (selected + 1).min(messages.len()));
+
+        let Some(index) = (start..messages.len())
+            .find(is_mention)
+            .or_else(|| (0..start).find(is_mention))
+        else {
+            return;
+        };
+
+        self.selected_message = Some(index);
+
+        let position = self.visible_position(&messages, index);
+        let offset = self.scroll_offset.get_offset();
+
+        if position < offset || position >= offset + self.rendered_messages {
+            self.scroll_offset.jump_to(position);
+        }
+    }
+
+    fn selected_message_data(&self) -> Option<MessageData> {
+        let current_messages = self.channels.borrow().current_messages();
+
+        self.selected_message
+            .and_then(|index| current_messages.borrow().get(index).cloned())
+    }
+
+    /// Keeps the same message selected after a new message has been pushed in front of it
+    pub const fn shift_selection(&mut self) {
+        if let Some(index) = self.selected_message.as_mut() {
+            *index += 1;
+        }
+    }
+
+    /// Keeps the same message selected after the message at `index` has been removed
+    pub fn message_removed(&mut self, index: usize) {
+        match self.selected_message {
+            Some(selected) if selected == index => self.clear_selection(),
+            Some(selected) if index < selected => self.selected_message = Some(selected - 1),
+            _ => {}
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected_message = None;
+        self.pending_ban = None;
+    }
+
+    /// Handles a key while a message is selected, returning if the key was used
+    async fn handle_selection_key(&mut self, key: Key) -> Result<bool> {
+        let keybinds = &self.config.keybinds.normal;
+
+        // Any other key cancels a ban that hasn't been confirmed yet
+        let pending_ban = self.pending_ban.take();
+
+        let (older, newer) = if self.scroll_offset.is_inverted() {
+            (&keybinds.scroll_down, &keybinds.scroll_up)
+        } else {
+            (&keybinds.scroll_up, &keybinds.scroll_down)
+        };
+
+        if older.contains(&key) {
+            self.select_older_message();
+            return Ok(true);
+        }
+        if newer.contains(&key) {
+            self.select_newer_message();
+            return Ok(true);
+        }
+        if keybinds.select_message.contains(&key) || keybinds.back_to_previous_window.contains(&key)
+        {
+            self.clear_selection();
+            return Ok(true);
+        }
+        if keybinds.next_mention.contains(&key) {
+            self.select_next_mention();
+            return Ok(true);
+        }
+
+        let Some(data) = self.selected_message_data() else {
+            return Ok(false);
+        };
+
+        match key {
+            key if keybinds.copy_message.contains(&key) => {
+                copy_to_clipboard(&data.payload)?;
+                self.clear_selection();
+            }
+            key if keybinds.open_profile.contains(&key) => {
+                if !data.system {
+                    webbrowser::open(&format!("https://www.twitch.tv/{}", data.author))?;
+                }
+            }
+            // Twitch only allows messages from chatters to be replied to or moderated
+            _ if data.system => {}
+            key if keybinds.reply_to_message.contains(&key) => {
+                if let Some(message_id) = data.message_id {
+                    self.chat_input.toggle_focus_with_reply(ReplyParent {
+                        message_id,
+                        author: data.author,
+                        payload: data.payload,
+                    });
+                    self.clear_selection();
+                }
+            }
+            key if keybinds.delete_message.contains(&key) => {
+                if let Some(message_id) = data.message_id {
+                    self.event_tx
+                        .send(Event::Twitch(TwitchEvent::Action(
+                            TwitchAction::DeleteMessage(message_id),
+                        )))
+                        .await?;
+                }
+            }
+            key if keybinds.timeout_user.contains(&key) => {
+                if let Some(user_id) = data.user_id {
+                    self.event_tx
+                        .send(Event::Twitch(TwitchEvent::Action(
+                            TwitchAction::TimeoutUser {
+                                user_id,
+                                username: data.author,
+                                duration: Some(self.config.terminal.timeout_duration),
+                            },
+                        )))
+                        .await?;
+                }
+            }
+            key if keybinds.ban_user.contains(&key) => {
+                let Some(user_id) = data.user_id else {
+                    return Ok(true);
+                };
+
+                if pending_ban.is_some_and(|(pending_user_id, _)| pending_user_id == user_id) {
+                    self.event_tx
+                        .send(Event::Twitch(TwitchEvent::Action(
+                            TwitchAction::TimeoutUser {
+                                user_id,
+                                username: data.author,
+                                duration: None,
+                            },
+                        )))
+                        .await?;
+                } else {
+                    self.pending_ban = Some((user_id, data.author));
+                }
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
     pub fn get_messages<'a>(
-        &self,
+        &mut self,
         frame: &Frame,
         area: Rect,
         messages_data: &'a VecDeque<MessageData>,
@@ -125,12 +507,12 @@ impl ChatWidget {
 
         let message_chunk_width = h_chunk[0].width as usize;
 
-        'outer: for data in messages_data {
-            let filters = self.filters.borrow();
-            let contaminated_message = filters.message.contaminated(&data.payload);
-            let contaminated_username = filters.username.contaminated(&data.author.clone());
+        let mut rendered_messages = 0;
 
-            if contaminated_message || contaminated_username {
+        let highlights = self.highlights.borrow();
+
+        'outer: for (index, data) in messages_data.iter().enumerate() {
+            if self.is_filtered(data) {
                 continue;
             }
 
@@ -158,18 +540,29 @@ impl ChatWidget {
                     None
                 },
                 username_highlight,
+                &highlights,
             );
 
+            let is_selected = self.selected_message == Some(index);
+
             for span in lines.into_iter().rev() {
                 if total_row_height < general_chunk_height {
-                    messages.push_front(span);
+                    messages.push_front(if is_selected {
+                        span.patch_style(*SELECTED_MESSAGE_STYLE)
+                    } else {
+                        span
+                    });
                     total_row_height += 1;
                 } else {
                     break 'outer;
                 }
             }
+
+            rendered_messages += 1;
         }
 
+        self.rendered_messages = rendered_messages;
+
         // Padding with empty rows so chat can go from bottom to top.
         if general_chunk_height > total_row_height {
             for _ in 0..(general_chunk_height - total_row_height) {
@@ -182,10 +575,48 @@ impl ChatWidget {
 }
 
 impl Component for ChatWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.chat_input.set_config(config);
+        self.channel_input.set_config(config);
+        self.search_input.set_config(config);
+        self.following.set_config(config);
+        self.filter_presets.set_config(config);
+        self.filter_editor.set_config(config);
+        self.whispers_pane.set_config(config);
+        self.redemption_queue.set_config(config);
+        self.automod_queue_popup.set_config(config);
+        self.polls_panel.set_config(config);
+        self.scroll_offset
+            .set_inverted(config.frontend.inverted_scrolling);
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         let r = area.unwrap_or_else(|| f.area());
 
-        let mut v_constraints = vec![Constraint::Min(1)];
+        // Only show the channel tabs once there is more than one channel to switch between
+        let show_channel_tabs = self.channels.borrow().len() > 1;
+
+        let mut v_constraints = vec![];
+
+        if show_channel_tabs {
+            v_constraints.push(Constraint::Length(1));
+        }
+
+        let stream_status = self.current_stream_status().cloned();
+
+        if stream_status.is_some() {
+            v_constraints.push(Constraint::Length(1));
+        }
+
+        // Running polls and predictions are shown above the chat, without taking up most of it
+        let polls_height = self.polls_panel.height().min(r.height / 2);
+
+        if polls_height > 0 {
+            v_constraints.push(Constraint::Length(polls_height));
+        }
+
+        v_constraints.push(Constraint::Min(1));
 
         if self.chat_input.is_focused() || self.search_input.is_focused() {
             v_constraints.push(Constraint::Length(3));
@@ -199,15 +630,30 @@ impl Component for ChatWidget {
 
         let mut v_chunks: Iter<Rect> = v_chunks_binding.iter();
 
-        let first_v_chunk = v_chunks.next().unwrap();
+        if show_channel_tabs {
+            self.draw_channel_tabs(f, *v_chunks.next().unwrap());
+        }
 
-        if self.messages.borrow().len() > self.config.terminal.maximum_messages {
-            self.messages
-                .borrow_mut()
-                .truncate(self.config.terminal.maximum_messages);
+        if let Some(status) = &stream_status {
+            Self::draw_stream_status(f, *v_chunks.next().unwrap(), status);
         }
 
-        let messages_data = self.messages.borrow();
+        if polls_height > 0 {
+            self.polls_panel.draw(f, v_chunks.next().copied());
+        }
+
+        let first_v_chunk = v_chunks.next().unwrap();
+
+        let current_messages = self.channels.borrow().current_messages();
+
+        let messages_data = current_messages.borrow();
+
+        if self
+            .selected_message
+            .is_some_and(|index| index >= messages_data.len())
+        {
+            self.clear_selection();
+        }
 
         let messages = self.get_messages(f, *first_v_chunk, &messages_data);
 
@@ -215,13 +661,24 @@ impl Component for ChatWidget {
             .format(&self.config.frontend.datetime_format)
             .to_string();
 
+        let current_channel_name = self.channels.borrow().current_name().to_string();
+
         let filters = self.filters.borrow();
         let reversed_filters = filters.message.is_reversed() || filters.username.is_reversed();
         let enabled_filters = filters.message.is_enabled() || filters.username.is_enabled();
 
-        let spans = [
+        let mentions = messages_data.iter().filter(|data| data.ping).count();
+        let mentions_count = mentions.to_string();
+
+        let unread_whispers = self.whispers.borrow().unread();
+        let unread_whispers_count = unread_whispers.to_string();
+
+        let held_messages = self.automod_queue.borrow().count(&current_channel_name);
+        let held_messages_count = held_messages.to_string();
+
+        let mut spans = vec![
             TitleStyle::Combined("Time", &current_time),
-            TitleStyle::Combined("Channel", self.current_channel_name.as_str()),
+            TitleStyle::Combined("Channel", &current_channel_name),
             TitleStyle::Custom(Span::styled(
                 if reversed_filters { "retliF" } else { "Filter" },
                 if *NO_COLOR {
@@ -236,8 +693,50 @@ impl Component for ChatWidget {
                         })
                 },
             )),
+            TitleStyle::Custom(Span::styled(
+                self.connection_state.as_str(),
+                if *NO_COLOR {
+                    Style::default()
+                } else {
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .fg(match self.connection_state {
+                            ConnectionState::Connected => Color::Green,
+                            ConnectionState::Connecting => Color::Yellow,
+                            ConnectionState::Reconnecting | ConnectionState::Disconnected => {
+                                Color::Red
+                            }
+                        })
+                },
+            )),
         ];
 
+        if mentions > 0 {
+            spans.push(TitleStyle::Combined("Mentions", &mentions_count));
+        }
+
+        if unread_whispers > 0 {
+            spans.push(TitleStyle::Combined("Whispers", &unread_whispers_count));
+        }
+
+        if held_messages > 0 {
+            spans.push(TitleStyle::Combined("Held", &held_messages_count));
+        }
+
+        if let Some((_, username)) = &self.pending_ban {
+            spans.push(TitleStyle::Custom(Span::styled(
+                format!(
+                    "Press {} again to ban {username}",
+                    get_keybind_text(&self.config.keybinds.normal.ban_user)
+                ),
+                if *NO_COLOR {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().add_modifier(Modifier::BOLD).fg(Color::Red)
+                },
+            )));
+        }
+
         let chat_title = if self.config.frontend.title_shown {
             Line::from(title_line(&spans, *TITLE_STYLE))
         } else {
@@ -301,14 +800,20 @@ impl Component for ChatWidget {
             self.search_input.draw(f, v_chunks.next().copied());
         } else if self.following.is_focused() {
             self.following.draw(f, None);
+        } else if self.filter_presets.is_focused() {
+            self.filter_presets.draw(f, None);
+        } else if self.filter_editor.is_focused() {
+            self.filter_editor.draw(f, None);
+        } else if self.whispers_pane.is_focused() {
+            self.whispers_pane.draw(f, None);
+        } else if self.redemption_queue.is_focused() {
+            self.redemption_queue.draw(f, None);
+        } else if self.automod_queue_popup.is_focused() {
+            self.automod_queue_popup.draw(f, None);
         }
     }
 
     async fn event(&mut self, event: &Event) -> Result<()> {
-        if let Event::Twitch(TwitchEvent::Action(TwitchAction::JoinChannel(channel))) = event {
-            self.current_channel_name.clone_from(channel);
-        }
-
         if self.chat_input.is_focused() {
             return self.chat_input.event(event).await;
         } else if self.channel_input.is_focused() {
@@ -317,11 +822,26 @@ impl Component for ChatWidget {
             return self.search_input.event(event).await;
         } else if self.following.is_focused() {
             return self.following.event(event).await;
+        } else if self.filter_presets.is_focused() {
+            return self.filter_presets.event(event).await;
+        } else if self.filter_editor.is_focused() {
+            return self.filter_editor.event(event).await;
+        } else if self.whispers_pane.is_focused() {
+            return self.whispers_pane.event(event).await;
+        } else if self.redemption_queue.is_focused() {
+            return self.redemption_queue.event(event).await;
+        } else if self.automod_queue_popup.is_focused() {
+            return self.automod_queue_popup.event(event).await;
         }
 
         if let Event::Input(key) = event {
+            if self.selected_message.is_some() && self.handle_selection_key(*key).await? {
+                return Ok(());
+            }
+
+            let current_messages = self.channels.borrow().current_messages();
             let limit =
-                self.scroll_offset.get_offset() < self.messages.borrow().len().saturating_sub(1);
+                self.scroll_offset.get_offset() < current_messages.borrow().len().saturating_sub(1);
 
             let keybinds = &self.config.keybinds.normal;
             match key {
@@ -335,6 +855,38 @@ impl Component for ChatWidget {
                 key if keybinds.recent_channels_search.contains(key) => {
                     self.channel_input.toggle_focus();
                 }
+                key if keybinds.select_message.contains(key) => self.select_message(),
+                key if keybinds.next_mention.contains(key) => self.select_next_mention(),
+                key if keybinds.open_channel.contains(key) => {
+                    self.channel_input.toggle_focus_to_open();
+                }
+                key if keybinds.leave_channel.contains(key) => {
+                    let channel = self.channels.borrow().current_name().to_string();
+
+                    self.event_tx
+                        .send(Event::Twitch(TwitchEvent::Action(
+                            TwitchAction::LeaveChannel(channel),
+                        )))
+                        .await?;
+                }
+                key if keybinds.next_channel.contains(key) => {
+                    let channel = self.channels.borrow().next_name().to_string();
+
+                    self.event_tx
+                        .send(Event::Twitch(TwitchEvent::Action(
+                            TwitchAction::FocusChannel(channel),
+                        )))
+                        .await?;
+                }
+                key if keybinds.previous_channel.contains(key) => {
+                    let channel = self.channels.borrow().previous_name().to_string();
+
+                    self.event_tx
+                        .send(Event::Twitch(TwitchEvent::Action(
+                            TwitchAction::FocusChannel(channel),
+                        )))
+                        .await?;
+                }
                 key if keybinds.search_messages.contains(key) => {
                     self.search_input.toggle_focus();
                 }
@@ -352,6 +904,21 @@ impl Component for ChatWidget {
                     filters.message.reverse();
                     filters.username.reverse();
                 }
+                key if keybinds.filter_presets.contains(key) => {
+                    self.filter_presets.toggle_focus();
+                }
+                key if keybinds.filter_editor.contains(key) => {
+                    self.filter_editor.toggle_focus().await;
+                }
+                key if keybinds.whispers.contains(key) => {
+                    self.whispers_pane.toggle_focus();
+                }
+                key if keybinds.redemption_queue.contains(key) => {
+                    self.redemption_queue.toggle_focus().await;
+                }
+                key if keybinds.automod_queue.contains(key) => {
+                    self.automod_queue_popup.toggle_focus().await;
+                }
                 key if keybinds.enter_dashboard.contains(key) => {
                     self.event_tx
                         .send(Event::Internal(InternalEvent::SwitchState(
@@ -377,7 +944,7 @@ impl Component for ChatWidget {
                 }
                 key if keybinds.scroll_to_start.contains(key) => {
                     // TODO: Make this not jump to nothingness
-                    self.scroll_offset.jump_to(self.messages.borrow().len());
+                    self.scroll_offset.jump_to(current_messages.borrow().len());
                 }
                 key if keybinds.back_to_previous_window.contains(key) => {
                     if self.scroll_offset.get_offset() == 0 {
@@ -8,7 +8,7 @@ use crate::{
     config::SharedCoreConfig,
     emotes::SharedEmotes,
     events::{Event, InternalEvent, TwitchAction, TwitchEvent},
-    handlers::storage::SharedStorage,
+    handlers::{data::ReplyParent, storage::SharedStorage},
     ui::{
         components::{Component, EmotePickerWidget, utils::InputWidget},
         statics::{SUPPORTED_COMMANDS, TWITCH_MESSAGE_LIMIT},
@@ -16,12 +16,16 @@ use crate::{
     utils::text::first_similarity,
 };
 
+const CHAT_INPUT_TITLE: &str = "Chat";
+
 pub struct ChatInputWidget {
     config: SharedCoreConfig,
     event_tx: Sender<Event>,
     storage: SharedStorage,
     input: InputWidget<SharedStorage>,
     emote_picker: EmotePickerWidget,
+    /// The message that is being replied to, if any
+    reply_parent: Option<ReplyParent>,
 }
 
 impl ChatInputWidget {
@@ -82,7 +86,7 @@ impl ChatInputWidget {
         let input = InputWidget::builder()
             .config(config.clone())
             .event_tx(event_tx.clone())
-            .title("Chat")
+            .title(CHAT_INPUT_TITLE)
             .input_validator((storage.clone(), input_validator))
             .visual_indicator(visual_indicator)
             .input_suggester((storage.clone(), input_suggester))
@@ -96,6 +100,7 @@ impl ChatInputWidget {
             storage,
             input,
             emote_picker,
+            reply_parent: None,
         }
     }
 
@@ -103,13 +108,28 @@ impl ChatInputWidget {
         self.input.is_focused()
     }
 
-    pub const fn toggle_focus(&mut self) {
+    pub fn toggle_focus(&mut self) {
+        self.stop_replying();
         self.input.toggle_focus();
     }
 
     pub fn toggle_focus_with(&mut self, s: &str) {
+        self.stop_replying();
         self.input.toggle_focus_with(s);
     }
+
+    /// Focuses the input, sending the next message as a reply
+    pub fn toggle_focus_with_reply(&mut self, reply_parent: ReplyParent) {
+        self.input
+            .set_title(&format!("Reply to @{}", reply_parent.author));
+        self.reply_parent = Some(reply_parent);
+        self.input.toggle_focus();
+    }
+
+    fn stop_replying(&mut self) {
+        self.reply_parent = None;
+        self.input.set_title(CHAT_INPUT_TITLE);
+    }
 }
 
 impl Display for ChatInputWidget {
@@ -119,6 +139,12 @@ impl Display for ChatInputWidget {
 }
 
 impl Component for ChatInputWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.input.set_config(config);
+        self.emote_picker.set_config(config);
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         self.input.draw(f, area);
 
@@ -154,10 +180,22 @@ impl Component for ChatInputWidget {
                             }
                         }
 
+                        let reply_parent = self.reply_parent.take();
+                        self.stop_replying();
+
+                        // Commands cannot be replies
+                        let action = match reply_parent {
+                            Some(reply_parent) if !current_input.starts_with('/') => {
+                                TwitchAction::Reply {
+                                    message: current_input,
+                                    parent_message_id: reply_parent.message_id,
+                                }
+                            }
+                            _ => TwitchAction::Message(current_input),
+                        };
+
                         self.event_tx
-                            .send(Event::Twitch(TwitchEvent::Action(TwitchAction::Message(
-                                current_input,
-                            ))))
+                            .send(Event::Twitch(TwitchEvent::Action(action)))
                             .await?;
                     }
                 }
@@ -167,6 +205,7 @@ impl Component for ChatInputWidget {
                     }
                 }
                 key if keybinds.back_to_previous_window.contains(key) => {
+                    self.stop_replying();
                     self.input.toggle_focus();
                 }
                 _ => {
@@ -3,11 +3,14 @@ use tokio::sync::mpsc::Sender;
 use tui::{Frame, layout::Rect};
 
 use crate::{
-    app::SharedMessages,
     config::SharedCoreConfig,
     emotes::SharedEmotes,
     events::Event,
-    handlers::{filters::SharedFilters, storage::SharedStorage},
+    handlers::{
+        automod::SharedAutoModQueue, channels::SharedChannels, filters::SharedFilters,
+        highlights::SharedHighlights, polls::SharedPolls, storage::SharedStorage,
+        whispers::SharedWhispers,
+    },
     twitch::oauth::TwitchOauth,
     ui::components::{
         ChatWidget, DashboardWidget, DebugWidget, ErrorWidget, HelpWidget, StateTabsWidget,
@@ -20,6 +23,8 @@ static WINDOW_SIZE_TOO_SMALL_ERROR: &[&str] = &[
     "Restart and resize.",
 ];
 
+static CONFIG_ERROR: &[&str] = &["The config could not be reloaded."];
+
 pub trait Component {
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>);
 
@@ -27,6 +32,11 @@ pub trait Component {
         let _ = event;
         Ok(())
     }
+
+    /// Swaps in the config after it has been reloaded while twt is running
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        let _ = config;
+    }
 }
 
 pub struct Components {
@@ -41,6 +51,7 @@ pub struct Components {
 
     // Errors
     pub window_size_error: ErrorWidget,
+    pub config_error: ErrorWidget,
 }
 
 #[bon]
@@ -52,7 +63,11 @@ impl Components {
         event_tx: Sender<Event>,
         storage: SharedStorage,
         filters: SharedFilters,
-        messages: SharedMessages,
+        highlights: SharedHighlights,
+        channels: SharedChannels,
+        whispers: SharedWhispers,
+        polls: SharedPolls,
+        automod_queue: SharedAutoModQueue,
         emotes: &SharedEmotes,
     ) -> Self {
         let window_size_error = ErrorWidget::new(
@@ -60,6 +75,8 @@ impl Components {
             event_tx.clone(),
             WINDOW_SIZE_TOO_SMALL_ERROR.to_vec(),
         );
+        let config_error =
+            ErrorWidget::new(config.clone(), event_tx.clone(), CONFIG_ERROR.to_vec());
 
         Self {
             tabs: StateTabsWidget::new(config.clone()),
@@ -69,10 +86,14 @@ impl Components {
                 config.clone(),
                 twitch_oauth.clone(),
                 event_tx.clone(),
-                messages,
+                channels,
                 &storage,
                 emotes,
                 filters,
+                highlights,
+                whispers,
+                polls,
+                automod_queue,
             ),
             dashboard: DashboardWidget::new(
                 config.clone(),
@@ -82,6 +103,17 @@ impl Components {
             ),
             help: HelpWidget::new(config.clone(), event_tx),
             window_size_error,
+            config_error,
         }
     }
+
+    /// Swaps in the config for every component after it has been reloaded
+    pub fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.debug.set_config(config);
+        self.chat.set_config(config);
+        self.dashboard.set_config(config);
+        self.help.set_config(config);
+        self.window_size_error.set_config(config);
+        self.config_error.set_config(config);
+    }
 }
@@ -174,6 +174,12 @@ impl DashboardWidget {
 }
 
 impl Component for DashboardWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.channel_input.set_config(config);
+        self.following.set_config(config);
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         let r = area.unwrap_or_else(|| f.area());
 
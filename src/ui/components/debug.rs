@@ -47,7 +47,7 @@ impl DebugWidget {
     }
 
     fn get_config_values(&self) -> Vec<(String, Vec<(String, String)>)> {
-        vec![
+        let mut configs = vec![
             (
                 "Twitch Config".to_string(),
                 self.config.twitch.clone().into(),
@@ -60,6 +60,14 @@ impl DebugWidget {
                 "Storage Config".to_string(),
                 self.config.storage.clone().into(),
             ),
+            (
+                "Chat Logs Config".to_string(),
+                self.config.chat_logs.clone().into(),
+            ),
+            (
+                "Notifications Config".to_string(),
+                self.config.notifications.clone().into(),
+            ),
             (
                 "Message Filters Config".to_string(),
                 self.config.filters.message.clone().into(),
@@ -69,14 +77,43 @@ impl DebugWidget {
                 self.config.filters.username.clone().into(),
             ),
             (
-                "Frontend Config".to_string(),
-                self.config.frontend.clone().into(),
+                "Filter Presets Config".to_string(),
+                self.config
+                    .filters
+                    .presets
+                    .iter()
+                    .map(|preset| {
+                        (
+                            preset.name.clone(),
+                            format!("{} (enabled: {})", preset.expression, preset.enabled),
+                        )
+                    })
+                    .collect(),
             ),
-        ]
+        ];
+
+        configs.extend(
+            self.config
+                .highlights
+                .iter()
+                .enumerate()
+                .map(|(i, rule)| (format!("Highlight Rule {}", i + 1), rule.clone().into())),
+        );
+
+        configs.push((
+            "Frontend Config".to_string(),
+            self.config.frontend.clone().into(),
+        ));
+
+        configs
     }
 }
 
 impl Component for DebugWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         let r = area.unwrap_or_else(|| f.area());
 
@@ -117,6 +117,11 @@ impl EmotePickerWidget {
 }
 
 impl Component for EmotePickerWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.input.set_config(config);
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         let r = area.unwrap_or_else(|| popup_area(f.area(), 60, 60));
 
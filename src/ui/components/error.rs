@@ -18,24 +18,24 @@ use crate::{
 pub struct ErrorWidget {
     config: SharedCoreConfig,
     event_tx: Sender<Event>,
-    message: Vec<&'static str>,
+    message: Vec<String>,
     focused: bool,
 }
 
 impl ErrorWidget {
-    pub const fn new(
-        config: SharedCoreConfig,
-        event_tx: Sender<Event>,
-        message: Vec<&'static str>,
-    ) -> Self {
+    pub fn new(config: SharedCoreConfig, event_tx: Sender<Event>, message: Vec<&str>) -> Self {
         Self {
             config,
             event_tx,
-            message,
+            message: message.into_iter().map(ToString::to_string).collect(),
             focused: false,
         }
     }
 
+    pub fn set_message(&mut self, message: Vec<String>) {
+        self.message = message;
+    }
+
     pub const fn is_focused(&self) -> bool {
         self.focused
     }
@@ -46,13 +46,17 @@ impl ErrorWidget {
 }
 
 impl Component for ErrorWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         let r = area.unwrap_or_else(|| f.area());
 
         let paragraph = Paragraph::new(
             self.message
                 .iter()
-                .map(|&s| Line::from(vec![Span::raw(s)]))
+                .map(|s| Line::from(vec![Span::raw(s.as_str())]))
                 .collect::<Vec<Line>>(),
         )
         .block(
@@ -0,0 +1,350 @@
+use std::fmt::Display;
+
+use color_eyre::Result;
+use regex::Regex;
+use tokio::sync::mpsc::Sender;
+use tui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use super::utils::{ListPopup, SearchItemGetter, SearchWidget, Status, popup_area};
+use crate::{
+    config::SharedCoreConfig,
+    events::{Event, Key, get_keybind_text},
+    handlers::{
+        channels::SharedChannels,
+        filters::{FilterKind, SharedFilters},
+    },
+    ui::components::Component,
+};
+
+/// Shows the recent messages that the selected filter matches below the list, on 10 lines including the borders
+const TEST_RESULTS_POPUP: ListPopup = ListPopup::new("Recent messages", 10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterEntry {
+    kind: FilterKind,
+    pattern: String,
+}
+
+impl Display for FilterEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:<8} {}", self.kind, self.pattern)
+    }
+}
+
+pub struct FilterEntries {
+    filters: SharedFilters,
+}
+
+impl SearchItemGetter<FilterEntry> for FilterEntries {
+    async fn get_items(&mut self) -> Result<Vec<FilterEntry>> {
+        let filters = self.filters.borrow();
+
+        Ok([FilterKind::Message, FilterKind::Username]
+            .into_iter()
+            .flat_map(|kind| {
+                filters.captures(kind).iter().map(move |re| FilterEntry {
+                    kind,
+                    pattern: re.as_str().to_string(),
+                })
+            })
+            .collect())
+    }
+}
+
+/// A popup to add, remove and test message and username filters, saving them to the filter files
+pub struct FilterEditorWidget {
+    config: SharedCoreConfig,
+    filters: SharedFilters,
+    channels: SharedChannels,
+    search_widget: SearchWidget<FilterEntry, FilterEntries>,
+    /// The kind of filter that typed patterns are added as
+    kind: FilterKind,
+    status: Option<Status>,
+}
+
+impl FilterEditorWidget {
+    pub fn new(
+        config: SharedCoreConfig,
+        event_tx: Sender<Event>,
+        filters: SharedFilters,
+        channels: SharedChannels,
+    ) -> Self {
+        let item_getter = FilterEntries {
+            filters: filters.clone(),
+        };
+
+        let search_widget = SearchWidget::new(
+            config.clone(),
+            event_tx,
+            item_getter,
+            "Filters",
+            vec!["The filters could not be listed."],
+        )
+        // Typed patterns are added as filters, even if they look like one that is already there
+        .without_selecting_first_match();
+
+        Self {
+            config,
+            filters,
+            channels,
+            search_widget,
+            kind: FilterKind::Message,
+            status: None,
+        }
+    }
+
+    pub const fn is_focused(&self) -> bool {
+        self.search_widget.is_focused()
+    }
+
+    pub async fn toggle_focus(&mut self) {
+        self.search_widget.toggle_focus().await;
+
+        if !self.is_focused() {
+            self.search_widget.clear_search();
+            self.status = None;
+        }
+    }
+
+    /// The filter that is being tested, either the selected one or the pattern being typed
+    fn tested_filter(&self) -> Option<(FilterKind, Result<Regex, regex::Error>)> {
+        if let Some(entry) = self.search_widget.selected_item() {
+            return Some((entry.kind, Regex::new(&entry.pattern)));
+        }
+
+        let pattern = self.search_widget.search_text();
+
+        (!pattern.is_empty()).then(|| (self.kind, Regex::new(&pattern)))
+    }
+
+    async fn add_typed_filter(&mut self) {
+        let pattern = self.search_widget.search_text();
+
+        if pattern.is_empty() {
+            return;
+        }
+
+        let filter = match Regex::new(&pattern) {
+            Ok(filter) => filter,
+            Err(err) => {
+                self.status = Some(Status::Failed(format!("Invalid filter: {err}")));
+                return;
+            }
+        };
+
+        if !self.filters.borrow_mut().add(self.kind, filter) {
+            self.status = Some(Status::Failed(format!(
+                "There is already a {} filter {pattern:?}",
+                self.kind
+            )));
+            return;
+        }
+
+        self.save(self.kind, format!("Added {} filter {pattern:?}", self.kind));
+        self.search_widget.clear_search();
+        self.search_widget.refresh_items().await;
+    }
+
+    async fn remove_selected_filter(&mut self) {
+        let Some(entry) = self.search_widget.selected_item() else {
+            return;
+        };
+
+        if self.filters.borrow_mut().remove(entry.kind, &entry.pattern) {
+            self.save(
+                entry.kind,
+                format!("Removed {} filter {:?}", entry.kind, entry.pattern),
+            );
+            self.search_widget.refresh_items().await;
+        }
+    }
+
+    fn save(&mut self, kind: FilterKind, done: String) {
+        self.status = Some(match self.filters.borrow().save(&self.config, kind) {
+            Ok(()) => Status::Done(done),
+            Err(err) => Status::Failed(format!("{done}, but it could not be saved: {err}")),
+        });
+    }
+
+    fn test_results(&self, max_lines: usize) -> Vec<Line<'static>> {
+        let mut lines = vec![];
+
+        if let Some(status) = &self.status {
+            lines.push(status.line());
+        }
+
+        let Some((kind, filter)) = self.tested_filter() else {
+            let keybinds = &self.config.keybinds.selection;
+
+            lines.push(Line::raw(format!(
+                "Type a pattern and press {} to add it as a {} filter, or Tab to add {} filters instead.",
+                get_keybind_text(&keybinds.select),
+                self.kind,
+                self.kind.other(),
+            )));
+            lines.push(Line::raw(format!(
+                "Select a filter to test it against recent messages, and press {} to remove it.",
+                get_keybind_text(&keybinds.delete_item)
+            )));
+
+            return lines;
+        };
+
+        let filter = match filter {
+            Ok(filter) => filter,
+            Err(err) => {
+                lines.push(Line::raw(format!("Invalid filter: {err}")));
+                return lines;
+            }
+        };
+
+        let current_messages = self.channels.borrow().current_messages();
+        let messages = current_messages.borrow();
+
+        let recent = messages.iter().filter(|m| !m.system).collect::<Vec<_>>();
+
+        let matched = recent
+            .iter()
+            .filter(|m| match kind {
+                FilterKind::Message => filter.is_match(&m.payload),
+                FilterKind::Username => filter.is_match(&m.author),
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "{} of the last {} messages match the {kind} filter {:?}",
+                matched.len(),
+                recent.len(),
+                filter.as_str()
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )]));
+
+        let remaining = max_lines.saturating_sub(lines.len());
+
+        lines.extend(matched.iter().take(remaining).map(|m| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{}: ", m.author),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(m.payload.clone()),
+            ])
+        }));
+
+        lines
+    }
+}
+
+impl Component for FilterEditorWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.search_widget.set_config(config);
+    }
+
+    fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
+        let r = area.unwrap_or_else(|| popup_area(f.area(), 60, 80));
+        let test_results = self.test_results(TEST_RESULTS_POPUP.panel_lines(r));
+
+        TEST_RESULTS_POPUP.draw(
+            f,
+            r,
+            &mut self.search_widget,
+            test_results,
+            self.config.frontend.border_type.clone().into(),
+        );
+    }
+
+    async fn event(&mut self, event: &Event) -> Result<()> {
+        if let Event::Input(key) = event {
+            let keybinds = &self.config.keybinds.selection;
+
+            match key {
+                key if keybinds.select.contains(key) => {
+                    // Selected filters are tested as soon as they are selected, so only typed ones are added
+                    if self.search_widget.selected_item().is_none() {
+                        self.add_typed_filter().await;
+                    }
+
+                    return Ok(());
+                }
+                key if keybinds.delete_item.contains(key) => {
+                    self.remove_selected_filter().await;
+
+                    return Ok(());
+                }
+                Key::Tab => {
+                    self.kind = self.kind.other();
+
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        self.search_widget.event(event).await?;
+
+        if !self.is_focused() {
+            self.search_widget.clear_search();
+            self.status = None;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use tui::{Terminal, backend::TestBackend};
+
+    use super::*;
+    use crate::{
+        config::CoreConfig,
+        handlers::{channels::Channels, filters::Filters},
+    };
+
+    #[tokio::test]
+    async fn test_typed_pattern_matching_a_filter_is_added() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let mut config = CoreConfig::default();
+        config.filters.message.path = Some(directory.path().join("message.txt"));
+        let config = SharedCoreConfig::new(config);
+
+        let filters = Rc::new(RefCell::new(Filters::new(&config).unwrap()));
+        filters
+            .borrow_mut()
+            .add(FilterKind::Message, Regex::new("a+bc").unwrap());
+
+        let (event_tx, _event_rx) = tokio::sync::mpsc::channel(10);
+        let channels = Rc::new(RefCell::new(Channels::new("channel".to_string(), 10)));
+
+        let mut editor = FilterEditorWidget::new(config, event_tx, filters.clone(), channels);
+        let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+
+        editor.toggle_focus().await;
+
+        // The popup is drawn between key presses, which is when the search is matched against the filters
+        for key in [Key::Char('a'), Key::Char('b'), Key::Enter] {
+            terminal.draw(|f| editor.draw(f, None)).unwrap();
+            editor.event(&Event::Input(key)).await.unwrap();
+        }
+
+        let patterns = filters
+            .borrow()
+            .captures(FilterKind::Message)
+            .iter()
+            .map(|re| re.as_str().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(patterns, vec!["a+bc", "ab"]);
+    }
+}
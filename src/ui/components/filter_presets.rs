@@ -0,0 +1,143 @@
+use color_eyre::Result;
+use tui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+};
+
+use crate::{
+    config::SharedCoreConfig,
+    events::Event,
+    handlers::filters::SharedFilters,
+    ui::components::{Component, utils::popup_area},
+    utils::{
+        styles::{NO_COLOR, TITLE_STYLE},
+        text::{TitleStyle, title_line},
+    },
+};
+
+/// A popup listing the filter presets, where each of them can be turned on or off
+pub struct FilterPresetsWidget {
+    config: SharedCoreConfig,
+    filters: SharedFilters,
+    focused: bool,
+    list_state: ListState,
+}
+
+impl FilterPresetsWidget {
+    pub fn new(config: SharedCoreConfig, filters: SharedFilters) -> Self {
+        Self {
+            config,
+            filters,
+            focused: false,
+            list_state: ListState::default(),
+        }
+    }
+
+    pub const fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focused = !self.focused;
+
+        let has_presets = !self.filters.borrow().presets.is_empty();
+        self.list_state
+            .select((self.focused && has_presets).then_some(0));
+    }
+
+    fn toggle_selected(&self) {
+        let Some(i) = self.list_state.selected() else {
+            return;
+        };
+
+        if let Some(preset) = self.filters.borrow_mut().presets.get_mut(i) {
+            preset.toggle();
+        }
+    }
+}
+
+impl Component for FilterPresetsWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+    }
+
+    fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
+        let r = area.unwrap_or_else(|| popup_area(f.area(), 60, 60));
+
+        let filters = self.filters.borrow();
+
+        let items = if filters.presets.is_empty() {
+            vec![ListItem::new(
+                "No filter presets, add them with [[filters.presets]] in the config",
+            )]
+        } else {
+            filters
+                .presets
+                .iter()
+                .map(|preset| {
+                    ListItem::new(Line::from(vec![
+                        Span::raw(if preset.is_enabled() { "[x] " } else { "[ ] " }),
+                        Span::styled(
+                            preset.name.clone(),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw(format!("  {}", preset.source)),
+                    ]))
+                })
+                .collect()
+        };
+
+        let title_binding = [TitleStyle::Single("Filter presets")];
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(title_line(&title_binding, *TITLE_STYLE))
+                    .borders(Borders::ALL)
+                    .border_type(self.config.frontend.border_type.clone().into()),
+            )
+            .highlight_style(if *NO_COLOR {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+                    .bg(Color::LightGreen)
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            });
+
+        f.render_widget(Clear, r);
+        f.render_stateful_widget(list, r, &mut self.list_state);
+    }
+
+    async fn event(&mut self, event: &Event) -> Result<()> {
+        if let Event::Input(key) = event {
+            let keybinds = &self.config.keybinds.selection;
+            let preset_count = self.filters.borrow().presets.len();
+
+            match key {
+                key if keybinds.back_to_previous_window.contains(key) => self.toggle_focus(),
+                key if keybinds.next_item.contains(key) && preset_count > 0 => {
+                    let i = self
+                        .list_state
+                        .selected()
+                        .map_or(0, |i| (i + 1).min(preset_count - 1));
+                    self.list_state.select(Some(i));
+                }
+                key if keybinds.prev_item.contains(key) && preset_count > 0 => {
+                    let i = self
+                        .list_state
+                        .selected()
+                        .map_or(0, |i| i.saturating_sub(1));
+                    self.list_state.select(Some(i));
+                }
+                key if keybinds.select.contains(key) => self.toggle_selected(),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -35,6 +35,7 @@ impl FollowingWidget {
             config,
             event_tx,
             item_getter,
+            "Following",
             INCORRECT_SCOPES_ERROR_MESSAGE.to_vec(),
         );
 
@@ -51,6 +52,10 @@ impl FollowingWidget {
 }
 
 impl Component for FollowingWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.search_widget.set_config(config);
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         self.search_widget.draw(f, area);
     }
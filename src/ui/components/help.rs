@@ -80,6 +80,26 @@ impl HelpWidget {
                 get_keybind_text(&keybinds.normal.reverse_filters),
                 "Reverse filters",
             ),
+            (
+                get_keybind_text(&keybinds.normal.filter_presets),
+                "Toggle filter presets",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.filter_editor),
+                "Edit message and username filters",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.whispers),
+                "Read and reply to whispers",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.redemption_queue),
+                "Fulfill or cancel channel point redemptions",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.automod_queue),
+                "Approve or deny messages held by AutoMod",
+            ),
             (
                 get_keybind_text(&keybinds.normal.back_to_previous_window),
                 "Go back to the previous window",
@@ -104,6 +124,54 @@ impl HelpWidget {
                 get_keybind_text(&keybinds.normal.open_in_player),
                 "Open current stream in player",
             ),
+            (
+                get_keybind_text(&keybinds.normal.select_message),
+                "Select a message, moving the selection with the scroll keys",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.reply_to_message),
+                "Reply to the selected message",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.delete_message),
+                "Delete the selected message",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.timeout_user),
+                "Timeout the author of the selected message",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.ban_user),
+                "Ban the author of the selected message, pressing it again to confirm",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.copy_message),
+                "Copy the selected message to the clipboard",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.open_profile),
+                "Open the profile of the selected message's author",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.next_mention),
+                "Select the next message that mentions you",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.open_channel),
+                "Join another channel in a new tab",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.leave_channel),
+                "Leave the current channel tab",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.next_channel),
+                "Switch to the next channel tab",
+            ),
+            (
+                get_keybind_text(&keybinds.normal.previous_channel),
+                "Switch to the previous channel tab",
+            ),
             (
                 get_keybind_text(&keybinds.normal.recent_channels_search),
                 "Open the recent channel search widget",
@@ -228,6 +296,10 @@ impl HelpWidget {
 }
 
 impl Component for HelpWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         let r = area.unwrap_or_else(|| f.area());
 
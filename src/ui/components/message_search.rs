@@ -54,6 +54,10 @@ impl Display for MessageSearchWidget {
 }
 
 impl Component for MessageSearchWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.input.set_config(config);
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         self.input.draw(f, area);
     }
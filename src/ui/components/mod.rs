@@ -1,3 +1,4 @@
+mod automod_queue;
 mod channel_switcher;
 mod chat;
 mod chat_input;
@@ -6,12 +7,18 @@ mod dashboard;
 mod debug;
 mod emote_picker;
 mod error;
+mod filter_editor;
+mod filter_presets;
 mod following;
 mod help;
 mod message_search;
+mod polls;
+mod redemption_queue;
 mod state_tabs;
 pub mod utils;
+mod whispers;
 
+pub use automod_queue::AutoModQueueWidget;
 pub use channel_switcher::ChannelSwitcherWidget;
 pub use chat::ChatWidget;
 pub use chat_input::ChatInputWidget;
@@ -20,7 +27,12 @@ pub use dashboard::DashboardWidget;
 pub use debug::DebugWidget;
 pub use emote_picker::EmotePickerWidget;
 pub use error::ErrorWidget;
+pub use filter_editor::FilterEditorWidget;
+pub use filter_presets::FilterPresetsWidget;
 pub use following::FollowingWidget;
 pub use help::HelpWidget;
 pub use message_search::MessageSearchWidget;
+pub use polls::PollsWidget;
+pub use redemption_queue::RedemptionQueueWidget;
 pub use state_tabs::StateTabsWidget;
+pub use whispers::WhispersWidget;
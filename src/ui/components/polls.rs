@@ -0,0 +1,188 @@
+use chrono::Utc;
+use tui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    config::SharedCoreConfig,
+    handlers::{
+        channels::SharedChannels,
+        polls::{LiveChoice, LivePoll, PollKind, PollStatus, SharedPolls},
+    },
+    ui::components::Component,
+    utils::{
+        styles::{NO_COLOR, TITLE_STYLE},
+        text::{TitleStyle, title_line},
+    },
+};
+
+/// How many characters of a choice are shown before it's cut off
+const MAXIMUM_CHOICE_WIDTH: usize = 25;
+
+/// The polls and predictions of the current channel, drawn above the chat while they're running
+pub struct PollsWidget {
+    config: SharedCoreConfig,
+    polls: SharedPolls,
+    channels: SharedChannels,
+}
+
+impl PollsWidget {
+    pub const fn new(
+        config: SharedCoreConfig,
+        polls: SharedPolls,
+        channels: SharedChannels,
+    ) -> Self {
+        Self {
+            config,
+            polls,
+            channels,
+        }
+    }
+
+    /// How many rows are needed to show every poll of the current channel, which is none if there aren't any
+    pub fn height(&self) -> u16 {
+        let channel = self.channels.borrow().current_name().to_string();
+
+        self.polls
+            .borrow()
+            .visible(&channel, Utc::now())
+            .iter()
+            .map(|poll| poll.choices.len() as u16 + 2)
+            .sum()
+    }
+
+    fn status_text(poll: &LivePoll) -> String {
+        match &poll.status {
+            PollStatus::Active(Some(ends_at)) => {
+                let seconds = (*ends_at - Utc::now()).num_seconds().max(0);
+
+                match poll.kind {
+                    PollKind::Poll => format!("{seconds}s left"),
+                    PollKind::Prediction => format!("Locks in {seconds}s"),
+                }
+            }
+            PollStatus::Active(None) => "Open".to_string(),
+            PollStatus::Locked => "Locked".to_string(),
+            PollStatus::Ended { outcome, .. } => {
+                let mut chars = outcome.chars();
+
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_uppercase().chain(chars).collect()
+                })
+            }
+        }
+    }
+
+    /// A choice along with a bar of its share of the total, filling the width that is given
+    fn choice_line(poll: &LivePoll, choice: &LiveChoice, width: usize) -> Line<'static> {
+        let title = format!(
+            "{:<MAXIMUM_CHOICE_WIDTH$.MAXIMUM_CHOICE_WIDTH$}",
+            choice.title
+        );
+
+        let share = poll.share(choice);
+
+        let tally = format!(
+            " {:>3.0}% {} {}{}",
+            share * 100.0,
+            choice.amount,
+            poll.kind.unit(),
+            choice
+                .users
+                .map_or_else(String::new, |users| format!(", {users} users"))
+        );
+
+        let bar_width = width.saturating_sub(title.chars().count() + tally.chars().count() + 1);
+        let filled = (share * bar_width as f64).round() as usize;
+
+        let (title_style, bar_style) = if *NO_COLOR {
+            (
+                if choice.winner {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                },
+                Style::default(),
+            )
+        } else if choice.winner {
+            (
+                Style::default()
+                    .fg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(Color::LightGreen),
+            )
+        } else {
+            (
+                Style::default(),
+                Style::default().fg(match poll.kind {
+                    PollKind::Poll => Color::LightMagenta,
+                    PollKind::Prediction => Color::LightBlue,
+                }),
+            )
+        };
+
+        Line::from(vec![
+            Span::styled(title, title_style),
+            Span::raw(" "),
+            Span::styled("█".repeat(filled), bar_style),
+            Span::raw(" ".repeat(bar_width - filled)),
+            Span::raw(tally),
+        ])
+    }
+}
+
+impl Component for PollsWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+    }
+
+    fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
+        let Some(r) = area else {
+            return;
+        };
+
+        let channel = self.channels.borrow().current_name().to_string();
+        let polls = self.polls.borrow();
+        let visible_polls = polls.visible(&channel, Utc::now());
+
+        let v_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                visible_polls
+                    .iter()
+                    .map(|poll| Constraint::Length(poll.choices.len() as u16 + 2)),
+            )
+            .split(r);
+
+        f.render_widget(Clear, r);
+
+        for (poll, rect) in visible_polls.iter().zip(v_chunks.iter()) {
+            let width = rect.width.saturating_sub(2) as usize;
+
+            let lines = poll
+                .choices
+                .iter()
+                .map(|choice| Self::choice_line(poll, choice, width))
+                .collect::<Vec<_>>();
+
+            let status = Self::status_text(poll);
+            let title_binding = [
+                TitleStyle::Combined(poll.kind.as_str(), &poll.title),
+                TitleStyle::Single(&status),
+            ];
+
+            let paragraph = Paragraph::new(lines).block(
+                Block::default()
+                    .title(title_line(&title_binding, *TITLE_STYLE))
+                    .borders(Borders::ALL)
+                    .border_type(self.config.frontend.border_type.clone().into()),
+            );
+
+            f.render_widget(paragraph, *rect);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use color_eyre::{Result, eyre::ContextCompat};
+use tokio::sync::mpsc::Sender;
+use tracing::warn;
+use tui::{Frame, layout::Rect, text::Line};
+
+use super::utils::{STATUS_POPUP, SearchItemGetter, SearchWidget, Status, popup_area};
+use crate::{
+    config::SharedCoreConfig,
+    events::{Event, get_keybind_text},
+    handlers::channels::SharedChannels,
+    twitch::{
+        api::{
+            channel_points::{
+                Redemption, RedemptionQuery, RedemptionStatus, get_custom_rewards,
+                get_unfulfilled_redemptions, update_redemption_status,
+            },
+            channels::get_channel_id,
+        },
+        oauth::TwitchOauth,
+    },
+    ui::components::Component,
+};
+
+static REDEMPTION_QUEUE_ERROR_MESSAGE: &[&str] = &[
+    "Failed to get the channel point redemption queue.",
+    "Twitch only lets the broadcaster see the redemptions of their own channel,",
+    "and the token needs the channel:manage:redemptions scope to do so.",
+    "",
+    "Hit ESC to dismiss this error.",
+];
+
+pub struct RedemptionQueue {
+    twitch_oauth: TwitchOauth,
+    channels: SharedChannels,
+}
+
+impl SearchItemGetter<Redemption> for RedemptionQueue {
+    async fn get_items(&mut self) -> Result<Vec<Redemption>> {
+        let client = self
+            .twitch_oauth
+            .client()
+            .context("Twitch client could not be found")?;
+
+        let channel = self.channels.borrow().current_name().to_string();
+        let broadcaster_id = get_channel_id(&client, &channel).await?;
+
+        let mut redemptions = vec![];
+
+        // Redemptions of rewards made elsewhere can't be fulfilled or canceled from here
+        for reward in get_custom_rewards(&client, &broadcaster_id, true).await? {
+            match get_unfulfilled_redemptions(&client, &broadcaster_id, &reward.id).await {
+                Ok(reward_redemptions) => redemptions.extend(reward_redemptions),
+                Err(err) => warn!(
+                    "Failed to get the redemptions of reward '{}': {err}",
+                    reward.title
+                ),
+            }
+        }
+
+        // Redemptions of every reward are in one queue, so the ones that have waited the longest come first
+        redemptions.sort_by_key(|redemption| redemption.redeemed_at);
+
+        Ok(redemptions)
+    }
+}
+
+/// A popup listing the channel point redemptions of the current channel that are waiting to be fulfilled or canceled
+pub struct RedemptionQueueWidget {
+    config: SharedCoreConfig,
+    twitch_oauth: TwitchOauth,
+    search_widget: SearchWidget<Redemption, RedemptionQueue>,
+    status: Option<Status>,
+}
+
+impl RedemptionQueueWidget {
+    pub fn new(
+        config: SharedCoreConfig,
+        twitch_oauth: TwitchOauth,
+        event_tx: Sender<Event>,
+        channels: SharedChannels,
+    ) -> Self {
+        let item_getter = RedemptionQueue {
+            twitch_oauth: twitch_oauth.clone(),
+            channels,
+        };
+
+        let search_widget = SearchWidget::new(
+            config.clone(),
+            event_tx,
+            item_getter,
+            "Redemption queue",
+            REDEMPTION_QUEUE_ERROR_MESSAGE.to_vec(),
+        );
+
+        Self {
+            config,
+            twitch_oauth,
+            search_widget,
+            status: None,
+        }
+    }
+
+    pub const fn is_focused(&self) -> bool {
+        self.search_widget.is_focused()
+    }
+
+    pub async fn toggle_focus(&mut self) {
+        self.search_widget.toggle_focus().await;
+
+        if !self.is_focused() {
+            self.search_widget.clear_search();
+            self.status = None;
+        }
+    }
+
+    /// Takes the selected redemption out of the queue
+    async fn update_selected(&mut self, status: RedemptionStatus) {
+        let Some(redemption) = self.search_widget.selected_item() else {
+            return;
+        };
+
+        let description = format!(
+            "{}'s redemption of {}",
+            redemption.user_name, redemption.reward.title
+        );
+
+        let Some(client) = self.twitch_oauth.client() else {
+            self.status = Some(Status::Failed(
+                "Twitch client could not be found".to_string(),
+            ));
+            return;
+        };
+
+        let query = RedemptionQuery::new(
+            redemption.id,
+            redemption.broadcaster_id,
+            redemption.reward.id,
+        );
+
+        self.status = Some(
+            match update_redemption_status(&client, query, status).await {
+                Ok(()) => Status::Done(format!("{status} {description}")),
+                Err(err) => Status::Failed(format!(
+                    "{description} could not be {status}, rewards can only be managed by the app that created them: {err}"
+                )),
+            },
+        );
+
+        self.search_widget.refresh_items().await;
+    }
+
+    fn status_lines(&self) -> Vec<Line<'static>> {
+        let line = self.status.as_ref().map_or_else(
+            || {
+                let keybinds = &self.config.keybinds.selection;
+
+                Line::raw(format!(
+                    "Press {} to fulfill the selected redemption, or {} to cancel it and refund the points.",
+                    get_keybind_text(&keybinds.select),
+                    get_keybind_text(&keybinds.delete_item)
+                ))
+            },
+            Status::line,
+        );
+
+        vec![line]
+    }
+}
+
+impl Component for RedemptionQueueWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.search_widget.set_config(config);
+    }
+
+    fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
+        let r = area.unwrap_or_else(|| popup_area(f.area(), 60, 60));
+        let status_lines = self.status_lines();
+
+        STATUS_POPUP.draw(
+            f,
+            r,
+            &mut self.search_widget,
+            status_lines,
+            self.config.frontend.border_type.clone().into(),
+        );
+    }
+
+    async fn event(&mut self, event: &Event) -> Result<()> {
+        if let Event::Input(key) = event {
+            let keybinds = &self.config.keybinds.selection;
+
+            // Selecting a redemption would otherwise join a channel by its name
+            if keybinds.select.contains(key) {
+                self.update_selected(RedemptionStatus::Fulfilled).await;
+
+                return Ok(());
+            }
+
+            if keybinds.delete_item.contains(key) {
+                self.update_selected(RedemptionStatus::Canceled).await;
+
+                return Ok(());
+            }
+        }
+
+        self.search_widget.event(event).await?;
+
+        if !self.is_focused() {
+            self.search_widget.clear_search();
+            self.status = None;
+        }
+
+        Ok(())
+    }
+}
@@ -85,6 +85,10 @@ impl<T: Clone> InputWidget<T> {
         self.input.update("", 0, &mut self.input_listener);
     }
 
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+
     pub const fn is_focused(&self) -> bool {
         self.focused
     }
@@ -124,6 +128,10 @@ impl<T: Clone> Display for InputWidget<T> {
 }
 
 impl<T: Clone> Component for InputWidget<T> {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         let r = area.map_or_else(|| popup_area(f.area(), 60, 60), |a| a.clamp(f.area()));
 
@@ -0,0 +1,146 @@
+use tui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::{SearchItemGetter, SearchWidget};
+use crate::{
+    ui::components::Component,
+    utils::{
+        styles::{NO_COLOR, TITLE_STYLE},
+        text::{TitleStyle, title_line},
+    },
+};
+
+/// Height of the search input that is drawn below the list
+const SEARCH_INPUT_HEIGHT: u16 = 3;
+
+/// A popup with a searchable list, and a panel of text below it
+pub struct ListPopup {
+    panel_title: &'static str,
+    /// How many lines the panel is shown on, including the borders
+    panel_height: u16,
+}
+
+/// A popup whose panel shows the outcome of the last change made to the list
+pub const STATUS_POPUP: ListPopup = ListPopup::new("Status", 4);
+
+impl ListPopup {
+    pub const fn new(panel_title: &'static str, panel_height: u16) -> Self {
+        Self {
+            panel_title,
+            panel_height,
+        }
+    }
+
+    /// The areas of the list and of the panel, leaving room between them for the search input
+    fn areas(&self, area: Rect) -> (Rect, Rect) {
+        let list_height = area
+            .height
+            .saturating_sub(SEARCH_INPUT_HEIGHT + self.panel_height);
+
+        let list_rect = Rect::new(area.x, area.y, area.width, list_height);
+
+        let panel_rect = Rect::new(
+            area.x,
+            list_rect.bottom() + SEARCH_INPUT_HEIGHT,
+            area.width,
+            self.panel_height.min(
+                area.bottom()
+                    .saturating_sub(list_rect.bottom() + SEARCH_INPUT_HEIGHT),
+            ),
+        );
+
+        (list_rect, panel_rect)
+    }
+
+    /// How many lines of text fit inside of the panel
+    pub fn panel_lines(&self, area: Rect) -> usize {
+        self.areas(area).1.height.saturating_sub(2) as usize
+    }
+
+    pub fn draw<T, U>(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        search_widget: &mut SearchWidget<T, U>,
+        panel_lines: Vec<Line<'static>>,
+        border_type: BorderType,
+    ) where
+        T: ToString + Clone,
+        U: SearchItemGetter<T>,
+    {
+        let (list_rect, panel_rect) = self.areas(area);
+
+        // The search widget draws its input just below the area it is given
+        search_widget.draw(f, Some(list_rect));
+
+        let title_binding = [TitleStyle::Single(self.panel_title)];
+
+        let paragraph = Paragraph::new(panel_lines)
+            .block(
+                Block::default()
+                    .title(title_line(&title_binding, *TITLE_STYLE))
+                    .borders(Borders::ALL)
+                    .border_type(border_type),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(Clear, panel_rect);
+        f.render_widget(paragraph, panel_rect);
+    }
+}
+
+/// The outcome of the last change made through a popup
+pub enum Status {
+    Done(String),
+    Failed(String),
+}
+
+impl Status {
+    pub fn line(&self) -> Line<'static> {
+        match self {
+            Self::Done(message) => Line::styled(
+                message.clone(),
+                if *NO_COLOR {
+                    Style::default()
+                } else {
+                    Style::default().fg(Color::Green)
+                },
+            ),
+            Self::Failed(message) => Line::styled(
+                message.clone(),
+                if *NO_COLOR {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Red)
+                },
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_areas_leave_room_for_search_input() {
+        let (list_rect, panel_rect) = STATUS_POPUP.areas(Rect::new(0, 0, 50, 20));
+
+        assert_eq!(list_rect, Rect::new(0, 0, 50, 13));
+        assert_eq!(panel_rect, Rect::new(0, 16, 50, 4));
+        assert_eq!(STATUS_POPUP.panel_lines(Rect::new(0, 0, 50, 20)), 2);
+    }
+
+    #[test]
+    fn test_areas_shrink_panel_in_small_popup() {
+        let (list_rect, panel_rect) = STATUS_POPUP.areas(Rect::new(0, 0, 50, 5));
+
+        assert_eq!(list_rect.height, 0);
+        assert_eq!(panel_rect, Rect::new(0, 3, 50, 2));
+    }
+}
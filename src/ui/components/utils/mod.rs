@@ -1,4 +1,5 @@
 mod input_widget;
+mod list_popup;
 mod popups;
 mod scrolling;
 mod search_widget;
@@ -6,6 +7,7 @@ mod search_widget;
 #[cfg(test)]
 pub use input_widget::InputListener;
 pub use input_widget::InputWidget;
+pub use list_popup::{ListPopup, STATUS_POPUP, Status};
 pub use popups::popup_area;
 pub use scrolling::Scrolling;
 pub use search_widget::{SearchItemGetter, SearchWidget};
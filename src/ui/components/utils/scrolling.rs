@@ -17,6 +17,10 @@ impl Scrolling {
         self.inverted
     }
 
+    pub const fn set_inverted(&mut self, inverted: bool) {
+        self.inverted = inverted;
+    }
+
     pub const fn up(&mut self) {
         self.offset = self.offset.saturating_add(1);
     }
@@ -43,6 +43,9 @@ where
     config: SharedCoreConfig,
     event_tx: Sender<Event>,
     focused: bool,
+    title: &'static str,
+    /// If the first item that matches the search is selected as the search changes
+    select_first_match: bool,
 
     item_getter: U,
     items: Result<Vec<T>>,
@@ -65,6 +68,7 @@ where
         config: SharedCoreConfig,
         event_tx: Sender<Event>,
         item_getter: U,
+        title: &'static str,
         error_message: Vec<&'static str>,
     ) -> Self {
         let search_input = InputWidget::builder()
@@ -78,6 +82,8 @@ where
             config,
             event_tx,
             focused: false,
+            title,
+            select_first_match: true,
             item_getter,
             items: Ok(vec![]),
             filtered_items: None,
@@ -89,6 +95,12 @@ where
         }
     }
 
+    /// Leaves items unselected while searching, for when the typed text is used for something other than finding an item
+    pub const fn without_selecting_first_match(mut self) -> Self {
+        self.select_first_match = false;
+        self
+    }
+
     fn next(&mut self) {
         let i = match self.list_state.selected() {
             Some(i) => {
@@ -132,6 +144,40 @@ where
         self.focused
     }
 
+    /// The item that is selected, out of the ones that match the search
+    pub fn selected_item(&self) -> Option<T> {
+        let i = self.list_state.selected()?;
+
+        self.filtered_items.as_ref().map_or_else(
+            || {
+                self.items
+                    .as_ref()
+                    .ok()
+                    .and_then(|items| items.get(i).cloned())
+            },
+            |filtered| filtered.get(i).cloned(),
+        )
+    }
+
+    /// What has been typed into the search input
+    pub fn search_text(&self) -> String {
+        self.search_input.to_string()
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search_input.clear();
+    }
+
+    /// Gets the items again, after they have changed while the widget is open
+    pub async fn refresh_items(&mut self) {
+        self.items = self.item_getter.get_items().await;
+
+        let count = self.items.as_ref().map_or(0, Vec::len);
+        if self.list_state.selected().is_some_and(|i| i >= count) {
+            self.list_state.select(count.checked_sub(1));
+        }
+    }
+
     pub async fn toggle_focus(&mut self) {
         if !self.focused {
             self.items = self.item_getter.get_items().await;
@@ -150,6 +196,12 @@ where
     T: ToString + Clone,
     U: SearchItemGetter<T>,
 {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.search_input.set_config(config);
+        self.error_widget.set_config(config);
+    }
+
     fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
         let r = area.unwrap_or_else(|| popup_area(f.area(), 60, 60));
 
@@ -202,7 +254,7 @@ where
             self.filtered_items = Some(matched);
         }
 
-        let title_binding = [TitleStyle::Single("Following")];
+        let title_binding = [TitleStyle::Single(self.title)];
 
         let list = List::new(items.clone())
             .block(
@@ -315,7 +367,7 @@ where
 
                     // Assuming that the user inputted something that modified the input
                     if let Some(v) = &self.filtered_items {
-                        if !v.is_empty() {
+                        if !v.is_empty() && self.select_first_match {
                             self.list_state.select(Some(0));
                         }
                     }
@@ -0,0 +1,324 @@
+use color_eyre::Result;
+use tokio::sync::mpsc::Sender;
+use tui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::{
+    config::SharedCoreConfig,
+    events::{Event, TwitchAction, TwitchEvent, get_keybind_text},
+    handlers::whispers::SharedWhispers,
+    ui::components::{
+        Component,
+        utils::{InputWidget, popup_area},
+    },
+    utils::{
+        styles::{NO_COLOR, TITLE_STYLE},
+        text::{TitleStyle, title_line},
+    },
+};
+
+/// Height of the reply input, including its borders
+const REPLY_INPUT_HEIGHT: u16 = 3;
+
+/// A pane with every whisper conversation on the left, and the selected conversation on the right
+pub struct WhispersWidget {
+    config: SharedCoreConfig,
+    event_tx: Sender<Event>,
+    whispers: SharedWhispers,
+    focused: bool,
+    /// The user of the selected conversation, so that it stays selected when conversations are reordered
+    selected: Option<String>,
+    list_state: ListState,
+    reply_input: InputWidget<()>,
+}
+
+impl WhispersWidget {
+    pub fn new(
+        config: SharedCoreConfig,
+        event_tx: Sender<Event>,
+        whispers: SharedWhispers,
+    ) -> Self {
+        let reply_input = InputWidget::builder()
+            .config(config.clone())
+            .event_tx(event_tx.clone())
+            .title("Reply")
+            .build();
+
+        Self {
+            config,
+            event_tx,
+            whispers,
+            focused: false,
+            selected: None,
+            list_state: ListState::default(),
+            reply_input,
+        }
+    }
+
+    pub const fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focused = !self.focused;
+
+        if self.reply_input.is_focused() {
+            self.reply_input.toggle_focus();
+        }
+        self.reply_input.clear();
+
+        self.selected = if self.focused {
+            self.whispers
+                .borrow()
+                .conversations()
+                .first()
+                .map(|conversation| conversation.user().to_string())
+        } else {
+            None
+        };
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+
+        self.whispers
+            .borrow()
+            .conversations()
+            .iter()
+            .position(|conversation| conversation.user() == selected)
+    }
+
+    fn select_index(&mut self, index: usize) {
+        self.selected = self
+            .whispers
+            .borrow()
+            .conversations()
+            .get(index)
+            .map(|conversation| conversation.user().to_string());
+    }
+
+    async fn send_reply(&mut self) -> Result<()> {
+        let text = self.reply_input.to_string();
+
+        let Some(user) = self.selected.clone().filter(|_| !text.trim().is_empty()) else {
+            return Ok(());
+        };
+
+        self.reply_input.clear();
+        self.reply_input.toggle_focus();
+
+        self.event_tx
+            .send(Event::Twitch(TwitchEvent::Action(TwitchAction::Message(
+                format!("/w {user} {text}"),
+            ))))
+            .await?;
+
+        Ok(())
+    }
+
+    fn conversation_lines(&self) -> Vec<Line<'static>> {
+        let whispers = self.whispers.borrow();
+
+        let Some(conversation) = self
+            .selected
+            .as_ref()
+            .and_then(|user| whispers.conversation(user))
+        else {
+            return vec![Line::raw(
+                "No whispers yet, send one with /w <user> <message>",
+            )];
+        };
+
+        let mut lines = conversation
+            .messages()
+            .iter()
+            .map(|whisper| {
+                let author = if whisper.sent {
+                    self.config.twitch.username.clone()
+                } else {
+                    whisper.user.clone()
+                };
+
+                let author_style = if *NO_COLOR || whisper.sent {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                        .fg(Color::LightMagenta)
+                        .add_modifier(Modifier::BOLD)
+                };
+
+                Line::from(vec![
+                    Span::raw(format!(
+                        "{} ",
+                        whisper
+                            .time_sent
+                            .format(&self.config.frontend.datetime_format)
+                    )),
+                    Span::styled(format!("{author}: "), author_style),
+                    Span::raw(whisper.payload.clone()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        if !self.reply_input.is_focused() {
+            lines.push(Line::styled(
+                format!(
+                    "Press {} to reply",
+                    get_keybind_text(&self.config.keybinds.selection.select)
+                ),
+                Style::default().add_modifier(Modifier::DIM),
+            ));
+        }
+
+        lines
+    }
+}
+
+impl Component for WhispersWidget {
+    fn set_config(&mut self, config: &SharedCoreConfig) {
+        self.config = config.clone();
+        self.reply_input.set_config(config);
+    }
+
+    fn draw(&mut self, f: &mut Frame, area: Option<Rect>) {
+        let r = area.unwrap_or_else(|| popup_area(f.area(), 70, 70));
+
+        if self.selected_index().is_none() {
+            self.select_index(0);
+        }
+
+        // Whatever is being looked at has been read
+        if let Some(user) = &self.selected {
+            self.whispers.borrow_mut().mark_read(user);
+        }
+
+        let h_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+            .split(r);
+
+        let items = self
+            .whispers
+            .borrow()
+            .conversations()
+            .iter()
+            .map(|conversation| {
+                let mut spans = vec![Span::raw(conversation.user().to_string())];
+
+                if conversation.unread() > 0 {
+                    spans.push(Span::styled(
+                        format!(" ({})", conversation.unread()),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ));
+                }
+
+                ListItem::new(Line::from(spans))
+            })
+            .collect::<Vec<_>>();
+
+        self.list_state.select(self.selected_index());
+
+        let title_binding = [TitleStyle::Single("Whispers")];
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(title_line(&title_binding, *TITLE_STYLE))
+                    .borders(Borders::ALL)
+                    .border_type(self.config.frontend.border_type.clone().into()),
+            )
+            .highlight_style(if *NO_COLOR {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+                    .bg(Color::LightGreen)
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            });
+
+        f.render_widget(Clear, r);
+        f.render_stateful_widget(list, h_chunks[0], &mut self.list_state);
+
+        let conversation_rect = if self.reply_input.is_focused() {
+            let v_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(REPLY_INPUT_HEIGHT)])
+                .split(h_chunks[1]);
+
+            self.reply_input.draw(f, Some(v_chunks[1]));
+
+            v_chunks[0]
+        } else {
+            h_chunks[1]
+        };
+
+        let lines = self.conversation_lines();
+
+        // Keep the most recent whispers in view
+        let visible_lines = conversation_rect.height.saturating_sub(2) as usize;
+        let scroll = lines.len().saturating_sub(visible_lines) as u16;
+
+        let conversation_title = self.selected.clone().unwrap_or_default();
+        let conversation_title_binding = [TitleStyle::Single(&conversation_title)];
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(title_line(&conversation_title_binding, *TITLE_STYLE))
+                    .borders(Borders::ALL)
+                    .border_type(self.config.frontend.border_type.clone().into()),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0));
+
+        f.render_widget(paragraph, conversation_rect);
+    }
+
+    async fn event(&mut self, event: &Event) -> Result<()> {
+        let Event::Input(key) = event else {
+            return Ok(());
+        };
+
+        if self.reply_input.is_focused() {
+            let keybinds = &self.config.keybinds.insert;
+
+            match key {
+                key if keybinds.confirm_text_input.contains(key) => self.send_reply().await?,
+                key if keybinds.back_to_previous_window.contains(key) => {
+                    self.reply_input.toggle_focus();
+                }
+                _ => self.reply_input.event(event).await?,
+            }
+
+            return Ok(());
+        }
+
+        let keybinds = &self.config.keybinds.selection;
+        let conversation_count = self.whispers.borrow().conversations().len();
+
+        match key {
+            key if keybinds.back_to_previous_window.contains(key) => self.toggle_focus(),
+            key if keybinds.next_item.contains(key) && conversation_count > 0 => {
+                let i = self
+                    .selected_index()
+                    .map_or(0, |i| (i + 1).min(conversation_count - 1));
+                self.select_index(i);
+            }
+            key if keybinds.prev_item.contains(key) && conversation_count > 0 => {
+                let i = self.selected_index().map_or(0, |i| i.saturating_sub(1));
+                self.select_index(i);
+            }
+            key if keybinds.select.contains(key) && self.selected.is_some() => {
+                self.reply_input.toggle_focus();
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
@@ -24,6 +24,9 @@ pub static SUPPORTED_COMMANDS: &[&str] = &[
     "commercial",
     "uniquechat",
     "uniquechatoff",
+    "w",
+    "poll",
+    "prediction",
     // "color",
     // "delete",
     // "disconnect",
@@ -37,7 +40,6 @@ pub static SUPPORTED_COMMANDS: &[&str] = &[
     // "r9kbetaoff",
     // "untimeout",
     // "vips",
-    // "w",
 
     // The following commands are not normally chat commands so they're separated for now
     "title",
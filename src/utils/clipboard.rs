@@ -0,0 +1,39 @@
+use std::fmt;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use color_eyre::Result;
+use tui::crossterm::{Command, execute};
+
+/// Sets the contents of the system clipboard through the terminal with the OSC 52 escape sequence.
+/// This also works over SSH, as long as the terminal supports it.
+pub struct SetClipboard<'a>(pub &'a str);
+
+impl Command for SetClipboard<'_> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1b]52;c;{}\x07", STANDARD.encode(self.0))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::result::Result<(), std::io::Error> {
+        Err(std::io::Error::other(
+            "Copying to the clipboard requires a terminal with ANSI support.",
+        ))
+    }
+}
+
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    Ok(execute!(std::io::stdout(), SetClipboard(text))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_clipboard_sequence() {
+        let mut sequence = String::new();
+        SetClipboard("hello").write_ansi(&mut sequence).unwrap();
+
+        assert_eq!(sequence, "\x1b]52;c;aGVsbG8=\x07");
+    }
+}
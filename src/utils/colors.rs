@@ -1,5 +1,12 @@
 use tui::style::Color::{self, Rgb};
 
+/// The lowest contrast ratio that a colored name may have against the background,
+/// from <https://www.w3.org/TR/WCAG21/#contrast-minimum>
+const MINIMUM_CONTRAST_RATIO: f64 = 4.5;
+
+/// How much lightness is changed by at a time when making a color readable
+const LIGHTNESS_STEP: f64 = 0.05;
+
 /// <https://css-tricks.com/converting-color-spaces-in-javascript/#hsl-to-rgb/>
 pub fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> [u8; 3] {
     // Color intensity
@@ -32,6 +39,99 @@ pub fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> [u8; 3] {
     [red as u8, green as u8, blue as u8]
 }
 
+/// The inverse of [`hsl_to_rgb`], with the hue in degrees and the rest between 0 and 1
+pub fn rgb_to_hsl(rgb: [u8; 3]) -> (f64, f64, f64) {
+    let [red, green, blue] = rgb.map(|channel| f64::from(channel) / 255.);
+
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let delta = max - min;
+
+    let lightness = f64::midpoint(max, min);
+
+    if delta == 0. {
+        return (0., 0., lightness);
+    }
+
+    let saturation = delta / (1. - (2. * lightness - 1.).abs());
+
+    let hue = if (max - red).abs() < f64::EPSILON {
+        60. * ((green - blue) / delta).rem_euclid(6.)
+    } else if (max - green).abs() < f64::EPSILON {
+        60. * ((blue - red) / delta + 2.)
+    } else {
+        60. * ((red - green) / delta + 4.)
+    };
+
+    (hue, saturation, lightness)
+}
+
+/// <https://www.w3.org/TR/WCAG21/#dfn-relative-luminance>
+fn relative_luminance(rgb: [u8; 3]) -> f64 {
+    let [red, green, blue] = rgb.map(|channel| {
+        let channel = f64::from(channel) / 255.;
+
+        if channel <= 0.039_28 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    });
+
+    0.0722f64.mul_add(blue, 0.2126f64.mul_add(red, 0.7152 * green))
+}
+
+/// <https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio>
+pub fn contrast_ratio(first: [u8; 3], second: [u8; 3]) -> f64 {
+    let first = relative_luminance(first);
+    let second = relative_luminance(second);
+
+    (first.max(second) + 0.05) / (first.min(second) + 0.05)
+}
+
+/// Parses a color in the `#RRGGBB` format that Twitch uses for the names of chatters
+pub fn hex_to_color(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#')?;
+
+    if hex.len() != 6 {
+        return None;
+    }
+
+    u32::from_str_radix(hex, 16).ok().map(u32_to_color)
+}
+
+/// Lightens or darkens a color until it can be read on top of the background, keeping its hue.
+/// Colors that are not RGB are left as they are, since the terminal decides what they look like.
+pub fn readable_color(color: Color, background: [u8; 3]) -> Color {
+    let Rgb(red, green, blue) = color else {
+        return color;
+    };
+
+    let mut rgb = [red, green, blue];
+
+    if contrast_ratio(rgb, background) >= MINIMUM_CONTRAST_RATIO {
+        return color;
+    }
+
+    let (hue, saturation, mut lightness) = rgb_to_hsl(rgb);
+
+    // Go towards whichever end is further away from the background
+    let step = if relative_luminance(background) < 0.5 {
+        LIGHTNESS_STEP
+    } else {
+        -LIGHTNESS_STEP
+    };
+
+    while contrast_ratio(rgb, background) < MINIMUM_CONTRAST_RATIO
+        && (0. ..=1.).contains(&(lightness + step))
+    {
+        lightness += step;
+        rgb = hsl_to_rgb(hue, saturation, lightness);
+    }
+
+    Rgb(rgb[0], rgb[1], rgb[2])
+}
+
 // Convert a u32 to RGB values, discarding the highest byte.
 pub const fn u32_to_color(value: u32) -> Color {
     Rgb((value >> 16) as u8, (value >> 8) as u8, value as u8)
@@ -68,4 +168,58 @@ mod tests {
 
         assert_eq!(Rgb(138, 46, 160), rgb);
     }
+
+    #[test]
+    fn test_hex_to_color() {
+        assert_eq!(hex_to_color("#00FF7F"), Some(Rgb(0, 255, 127)));
+        assert_eq!(hex_to_color("#8a2ea0"), Some(Rgb(138, 46, 160)));
+        assert_eq!(hex_to_color(""), None);
+        assert_eq!(hex_to_color("00FF7F"), None);
+        assert_eq!(hex_to_color("#00FF7"), None);
+        assert_eq!(hex_to_color("#GGGGGG"), None);
+    }
+
+    #[test]
+    fn test_rgb_to_hsl_round_trip() {
+        for rgb in [[138, 46, 160], [0, 255, 127], [30, 144, 255], [255, 69, 0]] {
+            let (hue, saturation, lightness) = rgb_to_hsl(rgb);
+            let round_trip = hsl_to_rgb(hue, saturation, lightness);
+
+            for (channel, round_trip) in rgb.iter().zip(round_trip) {
+                assert!(
+                    channel.abs_diff(round_trip) <= 1,
+                    "{rgb:?} became {round_trip:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_readable_color_keeps_readable_colors() {
+        assert_eq!(
+            readable_color(Rgb(0, 255, 127), [0, 0, 0]),
+            Rgb(0, 255, 127)
+        );
+        assert_eq!(readable_color(Color::Blue, [0, 0, 0]), Color::Blue);
+    }
+
+    #[test]
+    fn test_readable_color_lightens_on_dark_background() {
+        let Rgb(red, green, blue) = readable_color(Rgb(0, 0, 255), [0, 0, 0]) else {
+            panic!("Readable color should be RGB");
+        };
+
+        assert!(contrast_ratio([red, green, blue], [0, 0, 0]) >= MINIMUM_CONTRAST_RATIO);
+        assert!(blue > red && blue > green);
+    }
+
+    #[test]
+    fn test_readable_color_darkens_on_light_background() {
+        let Rgb(red, green, blue) = readable_color(Rgb(255, 255, 0), [255, 255, 255]) else {
+            panic!("Readable color should be RGB");
+        };
+
+        assert!(contrast_ratio([red, green, blue], [255, 255, 255]) >= MINIMUM_CONTRAST_RATIO);
+        assert!(red > blue && green > blue);
+    }
 }
@@ -1,3 +1,4 @@
+pub mod clipboard;
 pub mod colors;
 pub mod emotes;
 pub mod sanitization;
@@ -84,6 +84,32 @@ define_style!(DASHBOARD_TITLE_COLOR_STYLE,
     fg: color!(Color::Rgb(135, 120, 165))
 );
 
+define_style!(REPLY_STYLE,
+    fg: color!(Color::Gray),
+    add_modifier: Modifier::ITALIC
+);
+
+define_style!(PING_DARK_STYLE,
+    bg: color!(Color::Rgb(70, 40, 90))
+);
+
+define_style!(PING_LIGHT_STYLE,
+    bg: color!(Color::Rgb(235, 215, 245))
+);
+
+define_style!(HISTORY_STYLE,
+    add_modifier: Modifier::DIM
+);
+
+define_style!(SELECTED_MESSAGE_STYLE,
+    bg: color!(Color::DarkGray),
+    add_modifier: if *NO_COLOR {
+        Modifier::REVERSED
+    } else {
+        Modifier::empty()
+    }
+);
+
 define_style!(SEARCH_STYLE,
     fg: color!(Color::Red),
     add_modifier: *BOLD
@@ -93,3 +119,46 @@ define_style!(TITLE_STYLE,
     fg: color!(Color::Red),
     add_modifier: *BOLD
 );
+
+define_style!(SUB_NOTICE_STYLE,
+    fg: color!(Color::Rgb(145, 70, 255)),
+    add_modifier: *BOLD
+);
+
+define_style!(GIFT_NOTICE_STYLE,
+    fg: color!(Color::LightMagenta),
+    add_modifier: *BOLD
+);
+
+define_style!(RAID_NOTICE_STYLE,
+    fg: color!(Color::LightCyan),
+    add_modifier: *BOLD
+);
+
+define_style!(BITS_NOTICE_STYLE,
+    fg: color!(Color::LightBlue),
+    add_modifier: *BOLD
+);
+
+define_style!(CHARITY_NOTICE_STYLE,
+    fg: color!(Color::LightGreen),
+    add_modifier: *BOLD
+);
+
+define_style!(REDEMPTION_NOTICE_STYLE,
+    fg: color!(Color::LightYellow),
+    add_modifier: *BOLD
+);
+
+define_style!(NOTICE_STYLE,
+    add_modifier: Modifier::ITALIC
+);
+
+/// The style of the amount of bits cheered with a message, in the color of its tier
+pub fn bits_style(tier_color: Color) -> Style {
+    Style {
+        fg: color!(tier_color),
+        add_modifier: *BOLD,
+        ..Style::default()
+    }
+}